@@ -20,9 +20,7 @@ fn raw_hapi_function_names() -> HashSet<Item> {
         "_IsNode",
         "_Create",
         "_Init",
-        "HAPI_CreateCustomSession",
         "HAPI_GetHandleInfo",
-        "HAPI_BindCustomImplementation",
         "HAPI_GetImageFilePath",
         "HAPI_GetHandleBindingInfo",
         "HAPI_GetWorkitemResultInfo",
@@ -0,0 +1,83 @@
+//! Generates the `hapi_functions!` table consumed by the backend layer in hapi-rs.
+//!
+//! The table is derived from the bindgen output rather than from the headers, so the
+//! signatures always match the `extern "C"` declarations byte for byte.
+
+const HEADER: &str = "\
+// This file is generated by hapi-bindgen from bindings.rs. Do not edit by hand.
+
+/// Invokes `$callback!` with the signature of every function declared in `bindings.rs`.
+///
+/// Each entry has the form `fn HAPI_Name(arg: Type, ...) -> Ret;` where the return type
+/// is omitted for functions returning nothing.
+macro_rules! hapi_functions {
+    ($callback:ident) => {
+        $callback! {
+";
+
+const FOOTER: &str = "\
+        }
+    };
+}
+
+pub(crate) use hapi_functions;
+";
+
+/// A single `pub fn` declaration found in an `extern \"C\"` block.
+#[derive(Debug, PartialEq)]
+pub struct Function {
+    pub name: String,
+    pub args: Vec<(String, String)>,
+    pub ret: Option<String>,
+}
+
+/// Extract all extern function declarations from bindgen output.
+pub fn parse_functions(bindings: &str) -> Vec<Function> {
+    let mut functions = Vec::new();
+    let mut rest = bindings;
+    while let Some(start) = rest.find("pub fn ") {
+        rest = &rest[start + "pub fn ".len()..];
+        let Some(end) = rest.find(';') else { break };
+        let decl = &rest[..end];
+        rest = &rest[end + 1..];
+
+        let Some(open) = decl.find('(') else { continue };
+        let Some(close) = decl.rfind(')') else {
+            continue;
+        };
+        let name = decl[..open].trim().to_string();
+        let args = decl[open + 1..close]
+            .split(',')
+            .map(str::trim)
+            .filter(|arg| !arg.is_empty())
+            .filter_map(|arg| {
+                let (name, ty) = arg.split_once(':')?;
+                Some((name.trim().to_string(), normalize(ty)))
+            })
+            .collect();
+        let ret = decl[close + 1..].trim().strip_prefix("->").map(normalize);
+        functions.push(Function { name, args, ret });
+    }
+    functions
+}
+
+fn normalize(ty: &str) -> String {
+    ty.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Render the `hapi_functions!` macro for the given bindings source.
+pub fn generate_function_table(bindings: &str) -> String {
+    let mut out = String::from(HEADER);
+    for func in parse_functions(bindings) {
+        let args = func
+            .args
+            .iter()
+            .map(|(name, ty)| format!("{name}: {ty}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let ret = func.ret.map(|ret| format!(" -> {ret}")).unwrap_or_default();
+        out.push_str(&format!("            fn {}({args}){ret};\n", func.name));
+    }
+    out.push_str(FOOTER);
+    out
+}
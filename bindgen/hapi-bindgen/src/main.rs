@@ -11,6 +11,8 @@ use std::path::{Path, PathBuf};
 use bindgen::callbacks::{EnumVariantValue, ParseCallbacks};
 use once_cell::sync::Lazy;
 
+mod functions;
+
 #[derive(Debug, Copy, Clone)]
 pub enum StripMode {
     /// Strip N items at front, e.g N=1: FOO_BAR_ZOO => BAR_ZOO
@@ -253,5 +255,11 @@ fn main() -> anyhow::Result<()> {
         .write_to_file(out_path.clone())
         .context("Could not write bindings to file")?;
     println!("Generated: {}", out_path.to_string_lossy());
+
+    let table_path = out_path.with_file_name("hapi_functions.rs");
+    let bindings = std::fs::read_to_string(&out_path).context("Could not read bindings back")?;
+    std::fs::write(&table_path, functions::generate_function_table(&bindings))
+        .context("Could not write function table")?;
+    println!("Generated: {}", table_path.to_string_lossy());
     Ok(())
 }
@@ -4,6 +4,10 @@ fn main() {
     if std::env::var("DOCS_RS").is_ok() {
         return;
     }
+    if std::env::var("CARGO_FEATURE_LINK").is_err() {
+        // Nothing to link, the Engine API is provided by a backend at runtime.
+        return;
+    }
     if std::env::var("CI").is_ok() {
         println!("cargo:warning=Skipping build script in CI");
         return;
@@ -15,7 +15,7 @@ fn gather_all_messages(asset: HoudiniNode, message_nodes: &[NodeHandle]) -> Resu
     for handle in message_nodes {
         let node = handle.to_node(&asset.session)?;
         match node.cook_blocking()? {
-            CookResult::Succeeded => {}
+            CookResult::Succeeded | CookResult::Interrupted => {}
             CookResult::FatalErrors(_) | CookResult::CookErrors(_) => {
                 let err = node.get_cook_result_string(StatusVerbosity::Statusverbosity2)?;
                 message.push_str(&err);
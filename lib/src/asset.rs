@@ -4,8 +4,12 @@ use crate::ffi::raw as ffi;
 use crate::ffi::raw::{ChoiceListType, ParmType};
 use crate::node::ManagerType;
 use crate::{
-    HapiError, errors::Result, ffi::ParmChoiceInfo, ffi::ParmInfo, node::HoudiniNode,
-    session::Session,
+    HapiError,
+    errors::Result,
+    ffi::ParmChoiceInfo,
+    ffi::ParmInfo,
+    node::HoudiniNode,
+    session::{LibrarySource, Session},
 };
 use log::debug;
 use std::ffi::{CStr, CString};
@@ -177,7 +181,10 @@ impl AssetLibrary {
         debug!("Loading library file: {:?}", file);
         debug_assert!(session.is_valid());
         let cs = CString::new(file.as_os_str().to_string_lossy().to_string())?;
-        let lib_id = crate::ffi::load_library_from_file(&cs, &session, true)?;
+        let lib_id = session.watch("load_asset_file", || {
+            crate::ffi::load_library_from_file(&cs, &session, true)
+        })?;
+        session.journal(|journal| journal.add_library(LibrarySource::File(cs)));
         Ok(AssetLibrary {
             lib_id,
             session,
@@ -191,6 +198,7 @@ impl AssetLibrary {
         debug_assert!(session.is_valid());
         let data: &[i8] = unsafe { std::mem::transmute(data) };
         let lib_id = crate::ffi::load_library_from_memory(&session, data, true)?;
+        session.journal(|journal| journal.add_library(LibrarySource::Memory(data.to_vec())));
         Ok(AssetLibrary {
             lib_id,
             session,
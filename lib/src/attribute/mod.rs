@@ -305,6 +305,21 @@ impl<T: AttribValueType> NumericAttr<T> {
         )
     }
 
+    /// Start setting the attribute data asynchronously and return a job id.
+    /// It's important to keep `values` alive until the job is complete
+    pub fn set_async(&self, part_id: i32, values: &[T]) -> Result<JobId> {
+        debug_assert_eq!(self.0.info.storage(), T::storage());
+        T::set_async(
+            &self.0.name,
+            &self.0.node,
+            &self.0.info,
+            part_id,
+            values,
+            0,
+            self.0.info.count().min(values.len() as i32),
+        )
+    }
+
     /// Set multiple attribute data to the same value.
     /// value length must be less or equal to attribute tuple size.
     pub fn set_unique(&self, part_id: i32, value: &[T]) -> Result<()> {
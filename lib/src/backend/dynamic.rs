@@ -0,0 +1,184 @@
+//! Runtime loading of `libHAPIL`.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use libloading::Library;
+
+use super::{HapiBackend, Unsupported};
+use crate::errors::{HapiError, Result};
+use crate::ffi::hapi_functions;
+use crate::ffi::raw::*;
+
+/// How many missing symbols to list in the error message.
+const MAX_REPORTED_SYMBOLS: usize = 10;
+
+/// Backend which resolves all `HAPI_*` functions from `libHAPIL` loaded at runtime.
+///
+/// Install it explicitly with [`set_backend`](super::set_backend) to use a specific Houdini
+/// installation, or let the crate create one from the `HFS` environment variable
+/// when the first session is created.
+pub struct DynamicBackend {
+    path: PathBuf,
+    symbols: Box<Symbols>,
+    // Keeps the function pointers in `symbols` valid.
+    _library: Library,
+}
+
+impl fmt::Debug for DynamicBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamicBackend")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl DynamicBackend {
+    /// Load `libHAPIL` from the Houdini installation pointed by the `HFS` environment variable.
+    pub fn discover() -> Result<Self> {
+        let hfs = std::env::var_os("HFS").ok_or_else(|| {
+            HapiError::Backend("HFS variable not set, can't locate libHAPIL".to_string())
+        })?;
+        Self::from_hfs(hfs)
+    }
+
+    /// Load `libHAPIL` from a Houdini installation directory.
+    pub fn from_hfs(hfs: impl AsRef<Path>) -> Result<Self> {
+        Self::from_library(library_path(hfs.as_ref()))
+    }
+
+    /// Load the Engine library from a file.
+    ///
+    /// Fails if any function is missing from the library or its Houdini version doesn't match the
+    /// version this crate was built with.
+    pub fn from_library(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        // SAFETY: libHAPIL has no initialization routines with preconditions.
+        let library = unsafe { open_library(&path) }
+            .map_err(|e| HapiError::Backend(format!("Could not load {}: {e}", path.display())))?;
+        let mut missing = Vec::new();
+        // SAFETY: symbol types come from the bindings generated for this Houdini version.
+        let symbols = Box::new(unsafe { Symbols::load(&library, &mut missing) });
+        if !missing.is_empty() {
+            let mut names = missing[..missing.len().min(MAX_REPORTED_SYMBOLS)].join(", ");
+            if missing.len() > MAX_REPORTED_SYMBOLS {
+                names.push_str(&format!(
+                    " and {} more",
+                    missing.len() - MAX_REPORTED_SYMBOLS
+                ));
+            }
+            return Err(HapiError::Backend(format!(
+                "{} is missing {} symbols: {names}",
+                path.display(),
+                missing.len()
+            )));
+        }
+        let backend = DynamicBackend {
+            path,
+            symbols,
+            _library: library,
+        };
+        backend.check_version()?;
+        Ok(backend)
+    }
+
+    /// Path of the loaded library.
+    pub fn library_path(&self) -> &Path {
+        &self.path
+    }
+
+    fn check_version(&self) -> Result<()> {
+        let env_int = |int_type| {
+            let mut value = -1;
+            // SAFETY: value is a valid pointer.
+            match unsafe { self.HAPI_GetEnvInt(int_type, &mut value) } {
+                HapiResult::Success => Ok(value),
+                err => Err(HapiError::from(err)),
+            }
+        };
+        let major = env_int(EnvIntType::HoudiniMajor)?;
+        let minor = env_int(EnvIntType::HoudiniMinor)?;
+        if major as u32 != HAPI_VERSION_HOUDINI_MAJOR || minor as u32 != HAPI_VERSION_HOUDINI_MINOR
+        {
+            return Err(HapiError::Backend(format!(
+                "Version mismatch: hapi-rs was built for Houdini {}.{}, but {} is Houdini {major}.{minor}",
+                HAPI_VERSION_HOUDINI_MAJOR,
+                HAPI_VERSION_HOUDINI_MINOR,
+                self.path.display()
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn library_path(hfs: &Path) -> PathBuf {
+    if cfg!(target_os = "macos") {
+        hfs.parent()
+            .unwrap_or(hfs)
+            .join("Libraries")
+            .join("libHAPIL.dylib")
+    } else if cfg!(target_os = "windows") {
+        hfs.join("bin").join("libHAPIL.dll")
+    } else {
+        hfs.join("dsolib").join("libHAPIL.so")
+    }
+}
+
+#[cfg(unix)]
+unsafe fn open_library(path: &Path) -> std::result::Result<Library, libloading::Error> {
+    unsafe { Library::new(path) }
+}
+
+#[cfg(windows)]
+unsafe fn open_library(path: &Path) -> std::result::Result<Library, libloading::Error> {
+    use libloading::os::windows::{LOAD_WITH_ALTERED_SEARCH_PATH, Library as WinLibrary};
+    // Resolve the library dependencies from its own directory rather than from PATH.
+    unsafe { WinLibrary::load_with_flags(path, LOAD_WITH_ALTERED_SEARCH_PATH) }.map(Library::from)
+}
+
+macro_rules! declare_dynamic_backend {
+    ($(fn $name:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)?;)*) => {
+        #[allow(non_snake_case)]
+        struct Symbols {
+            $($name: Option<unsafe extern "C" fn($($ty),*) $(-> $ret)?>,)*
+        }
+
+        impl Symbols {
+            unsafe fn load(library: &Library, missing: &mut Vec<&'static str>) -> Self {
+                Symbols {
+                    $(
+                        $name: match unsafe {
+                            library.get::<unsafe extern "C" fn($($ty),*) $(-> $ret)?>(
+                                concat!(stringify!($name), "\0").as_bytes(),
+                            )
+                        } {
+                            Ok(symbol) => Some(*symbol),
+                            Err(_) => {
+                                missing.push(stringify!($name));
+                                None
+                            }
+                        },
+                    )*
+                }
+            }
+        }
+
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        impl HapiBackend for DynamicBackend {
+            $(
+                #[inline]
+                unsafe fn $name(&self, $($arg: $ty),*) $(-> $ret)? {
+                    match self.symbols.$name {
+                        Some(function) => unsafe { function($($arg),*) },
+                        None => {
+                            self.unsupported(stringify!($name));
+                            Unsupported::unsupported()
+                        }
+                    }
+                }
+            )*
+        }
+    };
+}
+
+hapi_functions!(declare_dynamic_backend);
@@ -0,0 +1,4101 @@
+//! In-memory implementation of a subset of the Engine API.
+//!
+//! [`FakeEngine`] keeps sessions, nodes, parameters and geometry in plain Rust data structures,
+//! which is enough to exercise code built on [`Session`](crate::session::Session),
+//! [`HoudiniNode`](crate::node::HoudiniNode), [`Parameter`](crate::parameter::Parameter) and
+//! [`Geometry`](crate::geometry::Geometry) on machines without a Houdini installation.
+//!
+//! What the fake engine supports:
+//! - In-process, Thrift and custom sessions. Servers are never actually started, [`FakeEngine::kill_server`]
+//!   simulates a server crash and [`FakeEngine::hang_server`] an unresponsive server. Custom
+//!   implementations are only remembered, not loaded.
+//! - Creating, deleting, renaming and connecting nodes of registered types, see [`FakeEngine::with_node_type`].
+//! - Reading and writing int, float and string parameters. Parameter expressions are stored, but
+//!   not evaluated.
+//! - Input geometry: parts, int/float/string attributes, vertex lists and face counts.
+//!   Async int and float attribute sets read their input when the job status is first queried.
+//! - Cooking: input nodes output their committed geometry, `Sop/box` generates a cube,
+//!   `Sop/merge` combines its inputs, `Sop/xform` translates its input and other SOPs pass their
+//!   first input through. `Sop/error` reports a cook error with the message from its `text` parm.
+//!   Interrupting a threaded cook which is still running ends it with a cook error.
+//! - Session time, timeline and compositor options, server environment variables and custom strings.
+//! - License check out, see [`FakeEngine::set_licenses`].
+//! - Memory caches with their size limits, every SOP cook adds a megabyte to the `SOP Cache`.
+//! - Saving and loading hip files, which only reference the saved nodes kept by the engine.
+//!   Geometry saved to files or memory is a text dump of the cooked parts.
+//!   Asset library files are remembered, but not loaded.
+//!
+//! Everything else (assets, PDG, volumes, materials, ...) is reported as unsupported.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use super::HapiBackend;
+use crate::ffi::raw::*;
+
+/// Parameter template of a fake node type.
+#[derive(Debug, Clone)]
+pub struct FakeParm {
+    name: String,
+    label: String,
+    parm_type: ParmType,
+    value: ParmValue,
+    // Expressions by component index, not evaluated
+    expressions: BTreeMap<i32, String>,
+}
+
+#[derive(Debug, Clone)]
+enum ParmValue {
+    Int(Vec<i32>),
+    Float(Vec<f32>),
+    String(Vec<String>),
+}
+
+impl FakeParm {
+    fn new(name: &str, parm_type: ParmType, value: ParmValue) -> Self {
+        FakeParm {
+            name: name.to_string(),
+            label: name.to_string(),
+            parm_type,
+            value,
+            expressions: BTreeMap::new(),
+        }
+    }
+
+    /// Integer parameter, the tuple size is the length of `default`.
+    pub fn int(name: &str, default: &[i32]) -> Self {
+        assert!(!default.is_empty(), "parameter {name} must have a value");
+        Self::new(name, ParmType::Int, ParmValue::Int(default.to_vec()))
+    }
+
+    /// Float parameter, the tuple size is the length of `default`.
+    pub fn float(name: &str, default: &[f32]) -> Self {
+        assert!(!default.is_empty(), "parameter {name} must have a value");
+        Self::new(name, ParmType::Float, ParmValue::Float(default.to_vec()))
+    }
+
+    /// String parameter, the tuple size is the length of `default`.
+    pub fn string(name: &str, default: &[&str]) -> Self {
+        assert!(!default.is_empty(), "parameter {name} must have a value");
+        let value = default.iter().map(|s| s.to_string()).collect();
+        Self::new(name, ParmType::String, ParmValue::String(value))
+    }
+
+    /// Toggle parameter.
+    pub fn toggle(name: &str, default: bool) -> Self {
+        Self::new(name, ParmType::Toggle, ParmValue::Int(vec![default as i32]))
+    }
+
+    /// Button parameter.
+    pub fn button(name: &str) -> Self {
+        Self::new(name, ParmType::Button, ParmValue::Int(vec![0]))
+    }
+
+    /// Set the parameter label, defaults to the name.
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = label.to_string();
+        self
+    }
+
+    fn size(&self) -> i32 {
+        (match &self.value {
+            ParmValue::Int(v) => v.len(),
+            ParmValue::Float(v) => v.len(),
+            ParmValue::String(v) => v.len(),
+        }) as i32
+    }
+
+    fn script_type(&self) -> PrmScriptType {
+        match self.parm_type {
+            ParmType::Toggle => PrmScriptType::Toggle,
+            ParmType::Button => PrmScriptType::Button,
+            ParmType::Float => PrmScriptType::Float,
+            ParmType::String => PrmScriptType::String,
+            _ => PrmScriptType::Int,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NodeTypeDef {
+    category: NodeType,
+    parms: Vec<FakeParm>,
+    inputs: i32,
+}
+
+fn parse_category(category: &str) -> Option<NodeType> {
+    Some(match category {
+        "Object" => NodeType::Obj,
+        "Sop" => NodeType::Sop,
+        "Chop" => NodeType::Chop,
+        "Driver" => NodeType::Rop,
+        "Shop" => NodeType::Shop,
+        "Cop2" => NodeType::Cop2,
+        "Cop" => NodeType::Cop,
+        "Vop" => NodeType::Vop,
+        "Dop" => NodeType::Dop,
+        "Top" => NodeType::Top,
+        "Lop" => NodeType::Lop,
+        _ => return None,
+    })
+}
+
+fn category_name(category: NodeType) -> &'static str {
+    match category {
+        NodeType::Obj => "Object",
+        NodeType::Sop => "Sop",
+        NodeType::Chop => "Chop",
+        NodeType::Rop => "Driver",
+        NodeType::Shop => "Shop",
+        NodeType::Cop2 => "Cop2",
+        NodeType::Cop => "Cop",
+        NodeType::Vop => "Vop",
+        NodeType::Dop => "Dop",
+        NodeType::Top => "Top",
+        NodeType::Lop => "Lop",
+        _ => "",
+    }
+}
+
+const MANAGERS: [(NodeType, &str); 5] = [
+    (NodeType::Obj, "obj"),
+    (NodeType::Chop, "ch"),
+    (NodeType::Cop, "img"),
+    (NodeType::Rop, "out"),
+    (NodeType::Top, "tasks"),
+];
+
+const INPUT_OP: &str = "__input__";
+
+/// An Engine API backend which lives entirely in memory. See the [module](self) documentation.
+///
+/// Clones share the engine state, keep a clone around to manipulate the engine after it's installed
+/// with [`set_backend`](super::set_backend).
+#[derive(Clone)]
+pub struct FakeEngine {
+    node_types: HashMap<String, NodeTypeDef>,
+    cook_polls: u32,
+    state: Arc<Mutex<EngineState>>,
+}
+
+impl Default for FakeEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakeEngine {
+    /// Create a fake engine with a few common node types registered.
+    pub fn new() -> Self {
+        let transform = || {
+            [
+                FakeParm::float("t", &[0.0; 3]).with_label("Translate"),
+                FakeParm::float("r", &[0.0; 3]).with_label("Rotate"),
+                FakeParm::float("s", &[1.0; 3]).with_label("Scale"),
+            ]
+        };
+        FakeEngine {
+            node_types: HashMap::new(),
+            cook_polls: 0,
+            state: Arc::new(Mutex::new(EngineState::default())),
+        }
+        .with_node_type("Object/geo", transform())
+        .with_node_type("Object/null", transform())
+        .with_node_type("Object/subnet", transform())
+        .with_node_type(
+            "Sop/box",
+            [
+                FakeParm::float("size", &[1.0; 3]).with_label("Size"),
+                FakeParm::float("t", &[0.0; 3]).with_label("Center"),
+            ],
+        )
+        .with_node_type(
+            "Sop/sphere",
+            [
+                FakeParm::float("rad", &[1.0; 3]).with_label("Radius"),
+                FakeParm::float("t", &[0.0; 3]).with_label("Center"),
+            ],
+        )
+        .with_node_type("Sop/null", [])
+        .with_node_type("Sop/merge", [])
+        .with_node_type("Sop/subnet", [])
+        .with_node_type(
+            "Sop/xform",
+            [
+                FakeParm::string("group", &[""]).with_label("Group"),
+                FakeParm::float("t", &[0.0; 3]).with_label("Translate"),
+                FakeParm::float("r", &[0.0; 3]).with_label("Rotate"),
+                FakeParm::float("s", &[1.0; 3]).with_label("Scale"),
+                FakeParm::float("scale", &[1.0]).with_label("Uniform Scale"),
+            ],
+        )
+        .with_node_type(
+            "Sop/attribwrangle",
+            [
+                FakeParm::string("snippet", &[""]).with_label("VEXpression"),
+                FakeParm::int("class", &[2]).with_label("Run Over"),
+            ],
+        )
+        .with_node_type(
+            "Sop/file",
+            [FakeParm::string("file", &["default.bgeo"]).with_label("Geometry File")],
+        )
+        .with_node_type(
+            "Sop/error",
+            [
+                FakeParm::string("text", &[""]).with_label("Message"),
+                FakeParm::int("severity", &[2]).with_label("Severity"),
+            ],
+        )
+    }
+
+    /// In threaded sessions, report the session as cooking for the next `polls`
+    /// cook state queries after every cook.
+    pub fn with_cook_polls(mut self, polls: u32) -> Self {
+        self.cook_polls = polls;
+        self
+    }
+
+    /// Licenses available to servers, in the order they are checked out. Servers started with a
+    /// [`LicensePreference`](crate::server::LicensePreference) only check out licenses it allows
+    /// and fail to initialize with [`HapiResult::NoLicenseFound`] if there's none. By default, every
+    /// session gets a Houdini Engine license.
+    pub fn set_licenses(&self, licenses: impl IntoIterator<Item = License>) {
+        self.state.lock().licenses = Some(licenses.into_iter().collect());
+    }
+
+    /// Simulate a crash of the server process `pid`: the server stops accepting connections and
+    /// all sessions connected to it become invalid.
+    pub fn kill_server(&self, pid: u32) {
+        let mut state = self.state.lock();
+        state.processes.retain(|process| *process as u32 != pid);
+        state.disconnect(pid);
+    }
+
+    /// Simulate a server process `pid` which hangs: it keeps running until killed, but stops
+    /// accepting connections and all sessions connected to it become invalid.
+    pub fn hang_server(&self, pid: u32) {
+        self.state.lock().disconnect(pid);
+    }
+
+    /// Returns `true` if the server process `pid` was started and hasn't crashed or been killed.
+    pub fn is_server_running(&self, pid: u32) -> bool {
+        self.state
+            .lock()
+            .processes
+            .iter()
+            .any(|process| *process as u32 == pid)
+    }
+
+    /// Register a node type. `name` must be fully qualified, e.g `"Sop/mynode"` or `"Object/myasset"`.
+    pub fn with_node_type(mut self, name: &str, parms: impl IntoIterator<Item = FakeParm>) -> Self {
+        let (category, _) = name
+            .split_once('/')
+            .unwrap_or_else(|| panic!("Node type name must be fully qualified: {name}"));
+        let category = parse_category(category)
+            .unwrap_or_else(|| panic!("Unknown node type category: {category}"));
+        let inputs = match category {
+            NodeType::Sop => 4,
+            NodeType::Obj => 1,
+            _ => 0,
+        };
+        self.node_types.insert(
+            name.to_string(),
+            NodeTypeDef {
+                category,
+                parms: parms.into_iter().collect(),
+                inputs,
+            },
+        );
+        self
+    }
+
+    fn call_with(
+        &self,
+        session: *const HAPI_Session,
+        require_init: bool,
+        f: impl FnOnce(&mut SessionData) -> Outcome,
+    ) -> HapiResult {
+        self.call_with_scenes(session, require_init, |data, _| f(data))
+    }
+
+    /// Same as [`FakeEngine::call`] with access to the scenes saved to hip files.
+    fn call_with_scenes(
+        &self,
+        session: *const HAPI_Session,
+        require_init: bool,
+        f: impl FnOnce(&mut SessionData, &mut Vec<Scene>) -> Outcome,
+    ) -> HapiResult {
+        // SAFETY: session pointers come from the caller, same as with the real library.
+        let Some(id) = (unsafe { session.as_ref() }).map(|s| s.id) else {
+            return HapiResult::InvalidArgument;
+        };
+        let mut state = self.state.lock();
+        let state = &mut *state;
+        let Some(data) = state.sessions.get_mut(&id) else {
+            return HapiResult::InvalidSession;
+        };
+        if require_init && !data.initialized {
+            data.last_error = "Session is not initialized".to_string();
+            return HapiResult::NotInitialized;
+        }
+        match f(data, &mut state.scenes) {
+            Ok(()) => HapiResult::Success,
+            Err(Fail(code, message)) => {
+                data.last_error = message;
+                code
+            }
+        }
+    }
+
+    /// Run `f` against an initialized session.
+    fn call(
+        &self,
+        session: *const HAPI_Session,
+        f: impl FnOnce(&mut SessionData) -> Outcome,
+    ) -> HapiResult {
+        self.call_with(session, true, f)
+    }
+
+    fn new_session(&self, session: *mut HAPI_Session, session_type: SessionType) -> HapiResult {
+        if session.is_null() {
+            return HapiResult::InvalidArgument;
+        }
+        let mut state = self.state.lock();
+        state.next_session += 1;
+        let id = state.next_session;
+        state.sessions.insert(id, SessionData::new());
+        // SAFETY: checked for null above.
+        unsafe {
+            session.write(HAPI_Session {
+                type_: session_type,
+                id,
+            })
+        };
+        HapiResult::Success
+    }
+
+    fn start_server(&self, name: String, process_id: *mut HAPI_ProcessId) -> HapiResult {
+        let mut state = self.state.lock();
+        state.next_pid += 1;
+        let pid = 4_000_000 + state.next_pid;
+        state.servers.insert(name, pid);
+        state.processes.insert(pid);
+        // Servers are started with the variables from ServerOptions set in the environment.
+        if let Ok(options) = std::env::var("HOUDINI_PLUGIN_LIC_OPT") {
+            state.license_options.insert(pid, options);
+        }
+        if !process_id.is_null() {
+            // SAFETY: checked for null above.
+            unsafe { process_id.write(pid) };
+        }
+        HapiResult::Success
+    }
+
+    fn connect(&self, name: String, session: *mut HAPI_Session) -> HapiResult {
+        let server = self.state.lock().servers.get(&name).copied();
+        if let Some(pid) = server {
+            let result = self.new_session(session, SessionType::Thrift);
+            if result == HapiResult::Success {
+                // SAFETY: new_session succeeds only for a valid pointer.
+                let id = unsafe { (*session).id };
+                let mut state = self.state.lock();
+                let license_options = state.license_options.get(&pid).cloned();
+                if let Some(data) = state.sessions.get_mut(&id) {
+                    data.server = Some(pid);
+                    data.license_options = license_options;
+                }
+            }
+            result
+        } else {
+            self.state.lock().connection_error = format!("Could not connect to server: {name}");
+            HapiResult::Failure
+        }
+    }
+
+    fn create_node(
+        &self,
+        s: &mut SessionData,
+        parent: HAPI_NodeId,
+        operator: &str,
+        label: Option<&str>,
+    ) -> Outcome<HAPI_NodeId> {
+        let (full_name, parent) = match operator.split_once('/') {
+            Some((category, _)) if parent == -1 => {
+                let category = parse_category(category)
+                    .ok_or_else(|| invalid(format!("Invalid operator name: {operator}")))?;
+                let parent = match category {
+                    NodeType::Sop => {
+                        let obj_parent = s.manager(NodeType::Obj);
+                        let base = operator.rsplit('/').next().unwrap_or(operator);
+                        let def = &self.node_types["Object/geo"];
+                        let container =
+                            s.add_node(obj_parent, &format!("{base}_object"), "geo", def, None);
+                        s.node_mut(container)?.display = None;
+                        container
+                    }
+                    other => s.manager(other),
+                };
+                (operator.to_string(), parent)
+            }
+            Some(_) => {
+                return Err(invalid(
+                    "Cannot use fully qualified operator name with a parent node",
+                ));
+            }
+            None => {
+                let parent_node = s.node(parent)?;
+                let category = s.child_category(parent_node);
+                (format!("{}/{operator}", category_name(category)), parent)
+            }
+        };
+        let def = self
+            .node_types
+            .get(&full_name)
+            .ok_or_else(|| invalid(format!("Invalid operator name: {full_name}")))?;
+        let op = full_name
+            .rsplit('/')
+            .next()
+            .unwrap_or(&full_name)
+            .to_string();
+        let name = label
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{op}1"));
+        Ok(s.add_node(parent, &name, &op, def, None))
+    }
+
+    fn create_input_node(
+        &self,
+        s: &mut SessionData,
+        parent: HAPI_NodeId,
+        name: &str,
+    ) -> Outcome<HAPI_NodeId> {
+        let def = NodeTypeDef {
+            category: NodeType::Sop,
+            parms: Vec::new(),
+            inputs: 0,
+        };
+        let parent = if parent < 0 {
+            let obj = s.manager(NodeType::Obj);
+            s.add_node(obj, name, "geo", &self.node_types["Object/geo"], None)
+        } else {
+            parent
+        };
+        let id = s.add_node(parent, name, INPUT_OP, &def, None);
+        s.node_mut(id)?.editable = true;
+        Ok(id)
+    }
+}
+
+#[derive(Debug)]
+struct Fail(HapiResult, String);
+
+type Outcome<T = ()> = std::result::Result<T, Fail>;
+
+fn invalid(message: impl Into<String>) -> Fail {
+    Fail(HapiResult::InvalidArgument, message.into())
+}
+
+#[derive(Default)]
+struct EngineState {
+    next_session: i64,
+    next_pid: i32,
+    sessions: HashMap<i64, SessionData>,
+    servers: HashMap<String, HAPI_ProcessId>,
+    // Running server processes, including the ones which stopped accepting connections.
+    processes: HashSet<HAPI_ProcessId>,
+    // Custom session type to the bound implementation library.
+    custom_implementations: HashMap<i32, String>,
+    connection_error: String,
+    // Hip files only contain an index into this list.
+    scenes: Vec<Scene>,
+    // Licenses servers can check out, `None` gives every session a Houdini Engine license.
+    licenses: Option<Vec<License>>,
+    // HOUDINI_PLUGIN_LIC_OPT of the servers started with one.
+    license_options: HashMap<HAPI_ProcessId, String>,
+}
+
+impl EngineState {
+    // Stop the server from accepting connections and invalidate its sessions.
+    fn disconnect(&mut self, pid: u32) {
+        self.servers.retain(|_, server| *server as u32 != pid);
+        self.sessions
+            .retain(|_, session| session.server.is_none_or(|server| server as u32 != pid));
+    }
+}
+
+/// Nodes saved with `HAPI_SaveHIPFile`.
+#[derive(Clone)]
+struct Scene {
+    nodes: BTreeMap<HAPI_NodeId, Node>,
+    next_node_id: HAPI_NodeId,
+}
+
+const HIP_HEADER: &str = "fake-hip";
+
+#[derive(Default)]
+struct Strings {
+    values: Vec<String>,
+    lookup: HashMap<String, HAPI_StringHandle>,
+}
+
+impl Strings {
+    fn intern(&mut self, value: &str) -> HAPI_StringHandle {
+        if self.values.is_empty() {
+            self.values.push(String::new());
+            self.lookup.insert(String::new(), 0);
+        }
+        if let Some(handle) = self.lookup.get(value) {
+            return *handle;
+        }
+        let handle = self.values.len() as HAPI_StringHandle;
+        self.values.push(value.to_string());
+        self.lookup.insert(value.to_string(), handle);
+        handle
+    }
+
+    fn get(&self, handle: HAPI_StringHandle) -> Outcome<&str> {
+        match handle {
+            0 => Ok(""),
+            h if h > 0 => self
+                .values
+                .get(h as usize)
+                .map(String::as_str)
+                .ok_or_else(|| invalid(format!("Invalid string handle: {h}"))),
+            h => Err(invalid(format!("Invalid string handle: {h}"))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum EnvValue {
+    Int(i32),
+    String(String),
+}
+
+/// Memory cache, sizes are in MB like the Engine API reports them.
+#[derive(Debug, Clone)]
+struct Cache {
+    current: i32,
+    min: Option<i32>,
+    max: Option<i32>,
+    cull_level: i32,
+}
+
+impl Cache {
+    fn new(max: Option<i32>) -> Self {
+        Cache {
+            current: 0,
+            min: None,
+            max,
+            cull_level: 0,
+        }
+    }
+
+    fn grow(&mut self, mb: i32) {
+        self.current = match self.max {
+            Some(max) => (self.current + mb).min(max),
+            None => self.current + mb,
+        };
+    }
+}
+
+/// Product name of a license as used in `HOUDINI_PLUGIN_LIC_OPT`.
+fn license_product(license: License) -> &'static str {
+    match license {
+        License::HoudiniFx => "Houdini-Fx",
+        License::LicenseHoudini | License::HoudiniIndie | License::HoudiniEducation => {
+            "Houdini-Escape"
+        }
+        _ => "Houdini-Engine",
+    }
+}
+
+/// Pick the first available license allowed by `options`, checking products in the order listed.
+fn check_out_license(options: Option<&str>, available: Option<&[License]>) -> Outcome<License> {
+    let Some(available) = available else {
+        return Ok(License::HoudiniEngine);
+    };
+    let not_found = || Fail(HapiResult::NoLicenseFound, "No license found".to_string());
+    let Some(options) = options else {
+        return available.first().copied().ok_or_else(not_found);
+    };
+    let products = |flag: &str| -> Vec<&str> {
+        options
+            .split_whitespace()
+            .find_map(|arg| arg.strip_prefix(flag))
+            .map(|products| products.split(',').collect())
+            .unwrap_or_default()
+    };
+    let skipped = products("--skip-licenses=");
+    products("--check-licenses=")
+        .into_iter()
+        .filter(|product| !skipped.contains(product))
+        .find_map(|product| {
+            available
+                .iter()
+                .find(|license| license_product(**license) == product)
+        })
+        .copied()
+        .ok_or_else(not_found)
+}
+
+// Variables the server sets itself.
+fn default_env() -> BTreeMap<String, EnvValue> {
+    BTreeMap::from([
+        (
+            "HFS".to_string(),
+            EnvValue::String("/opt/hfs-fake".to_string()),
+        ),
+        ("HIP".to_string(), EnvValue::String("/tmp".to_string())),
+    ])
+}
+
+fn default_caches() -> BTreeMap<String, Cache> {
+    BTreeMap::from([
+        ("SOP Cache".to_string(), Cache::new(Some(4096))),
+        ("HDA Contents Cache".to_string(), Cache::new(None)),
+        ("COP Cook Cache".to_string(), Cache::new(Some(1024))),
+    ])
+}
+
+#[derive(Debug, Clone, Default)]
+struct GeoData {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone)]
+struct Part {
+    name: String,
+    part_type: PartType,
+    point_count: i32,
+    face_count: i32,
+    vertex_count: i32,
+    face_counts: Vec<i32>,
+    vertices: Vec<i32>,
+    attributes: Vec<Attrib>,
+}
+
+impl Part {
+    fn new(name: &str) -> Self {
+        Part {
+            name: name.to_string(),
+            part_type: PartType::Mesh,
+            point_count: 0,
+            face_count: 0,
+            vertex_count: 0,
+            face_counts: Vec::new(),
+            vertices: Vec::new(),
+            attributes: Vec::new(),
+        }
+    }
+
+    fn element_count(&self, owner: AttributeOwner) -> i32 {
+        match owner {
+            AttributeOwner::Vertex => self.vertex_count,
+            AttributeOwner::Point => self.point_count,
+            AttributeOwner::Prim => self.face_count,
+            AttributeOwner::Detail => 1,
+            _ => 0,
+        }
+    }
+
+    fn attribute(&self, name: &str, owner: AttributeOwner) -> Option<&Attrib> {
+        self.attributes
+            .iter()
+            .find(|a| a.name == name && (a.owner == owner || owner == AttributeOwner::Max))
+    }
+
+    fn attribute_mut(&mut self, name: &str, owner: AttributeOwner) -> Outcome<&mut Attrib> {
+        self.attributes
+            .iter_mut()
+            .find(|a| a.name == name && a.owner == owner)
+            .ok_or_else(|| invalid(format!("Attribute {name} does not exist")))
+    }
+
+    fn info(&self, id: HAPI_PartId, strings: &mut Strings) -> HAPI_PartInfo {
+        let mut attribute_counts = [0; 4];
+        for attr in &self.attributes {
+            attribute_counts[attr.owner as usize] += 1;
+        }
+        HAPI_PartInfo {
+            id,
+            nameSH: strings.intern(&self.name),
+            type_: self.part_type,
+            faceCount: self.face_count,
+            vertexCount: self.vertex_count,
+            pointCount: self.point_count,
+            attributeCounts: attribute_counts,
+            isInstanced: 0,
+            instancedPartCount: 0,
+            instanceCount: 0,
+            hasChanged: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Attrib {
+    name: String,
+    owner: AttributeOwner,
+    tuple_size: i32,
+    count: i32,
+    type_info: AttributeTypeInfo,
+    values: AttribValues,
+}
+
+impl Attrib {
+    fn info(&self) -> HAPI_AttributeInfo {
+        HAPI_AttributeInfo {
+            exists: 1,
+            owner: self.owner,
+            storage: self.values.storage(),
+            originalOwner: self.owner,
+            count: self.count,
+            tupleSize: self.tuple_size,
+            totalArrayElements: 0,
+            typeInfo: self.type_info,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum AttribValues {
+    Int(Vec<i32>),
+    Int64(Vec<i64>),
+    Float(Vec<f32>),
+    Float64(Vec<f64>),
+    String(Vec<String>),
+}
+
+impl AttribValues {
+    fn new(storage: StorageType, len: usize) -> Option<Self> {
+        Some(match storage {
+            StorageType::Int => AttribValues::Int(vec![0; len]),
+            StorageType::Int64 => AttribValues::Int64(vec![0; len]),
+            StorageType::Float => AttribValues::Float(vec![0.0; len]),
+            StorageType::Float64 => AttribValues::Float64(vec![0.0; len]),
+            StorageType::String => AttribValues::String(vec![String::new(); len]),
+            _ => return None,
+        })
+    }
+
+    fn storage(&self) -> StorageType {
+        match self {
+            AttribValues::Int(_) => StorageType::Int,
+            AttribValues::Int64(_) => StorageType::Int64,
+            AttribValues::Float(_) => StorageType::Float,
+            AttribValues::Float64(_) => StorageType::Float64,
+            AttribValues::String(_) => StorageType::String,
+        }
+    }
+}
+
+/// Numeric types stored in [`AttribValues`].
+trait Element: Copy {
+    fn values(values: &AttribValues) -> Option<&Vec<Self>>;
+    fn values_mut(values: &mut AttribValues) -> Option<&mut Vec<Self>>;
+}
+
+macro_rules! impl_element {
+    ($ty:ty, $variant:ident) => {
+        impl Element for $ty {
+            fn values(values: &AttribValues) -> Option<&Vec<Self>> {
+                match values {
+                    AttribValues::$variant(v) => Some(v),
+                    _ => None,
+                }
+            }
+            fn values_mut(values: &mut AttribValues) -> Option<&mut Vec<Self>> {
+                match values {
+                    AttribValues::$variant(v) => Some(v),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_element!(i32, Int);
+impl_element!(i64, Int64);
+impl_element!(f32, Float);
+impl_element!(f64, Float64);
+
+#[derive(Clone)]
+struct Node {
+    id: HAPI_NodeId,
+    parent: HAPI_NodeId,
+    name: String,
+    category: NodeType,
+    op: String,
+    parms: Vec<FakeParm>,
+    inputs: Vec<HAPI_NodeId>,
+    children: Vec<HAPI_NodeId>,
+    display: Option<HAPI_NodeId>,
+    cook_count: i32,
+    editable: bool,
+    staged: GeoData,
+    committed: GeoData,
+    output: Option<GeoData>,
+    cook_errors: String,
+    // Set by cooks, cleared when the geo info is queried.
+    geo_changed: bool,
+}
+
+impl Node {
+    fn parm(&self, name: &str) -> Outcome<(usize, &FakeParm)> {
+        self.parms
+            .iter()
+            .enumerate()
+            .find(|(_, p)| p.name == name)
+            .ok_or_else(|| invalid(format!("Parameter {name} does not exist on {}", self.name)))
+    }
+
+    fn parm_mut(&mut self, name: &str) -> Outcome<&mut FakeParm> {
+        let node_name = &self.name;
+        self.parms
+            .iter_mut()
+            .find(|p| p.name == name)
+            .ok_or_else(|| invalid(format!("Parameter {name} does not exist on {node_name}")))
+    }
+
+    fn float_parm(&self, name: &str) -> Vec<f32> {
+        match self.parm(name).map(|(_, p)| &p.value) {
+            Ok(ParmValue::Float(v)) => v.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns (int, float, string) value counts of all parms preceding `index`.
+    fn value_offsets(&self, index: usize) -> (i32, i32, i32) {
+        self.parms[..index]
+            .iter()
+            .fold((0, 0, 0), |(i, f, s), p| match p.value {
+                ParmValue::Int(_) => (i + p.size(), f, s),
+                ParmValue::Float(_) => (i, f + p.size(), s),
+                ParmValue::String(_) => (i, f, s + p.size()),
+            })
+    }
+
+    fn int_values(&mut self) -> Vec<&mut i32> {
+        self.parms
+            .iter_mut()
+            .flat_map(|p| match &mut p.value {
+                ParmValue::Int(v) => v.iter_mut().collect(),
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+
+    fn float_values(&mut self) -> Vec<&mut f32> {
+        self.parms
+            .iter_mut()
+            .flat_map(|p| match &mut p.value {
+                ParmValue::Float(v) => v.iter_mut().collect(),
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+
+    fn string_values(&self) -> Vec<&str> {
+        self.parms
+            .iter()
+            .flat_map(|p| match &p.value {
+                ParmValue::String(v) => v.iter().map(String::as_str).collect(),
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+
+    fn geometry(&self) -> &GeoData {
+        static EMPTY: GeoData = GeoData { parts: Vec::new() };
+        self.output.as_ref().unwrap_or(&EMPTY)
+    }
+
+    fn staged_part(&mut self, part_id: HAPI_PartId) -> Outcome<&mut Part> {
+        if !self.editable {
+            return Err(invalid(format!("Node {} is not editable", self.name)));
+        }
+        self.staged
+            .parts
+            .get_mut(part_id as usize)
+            .ok_or_else(|| invalid(format!("Invalid part id: {part_id}")))
+    }
+
+    /// Like Houdini, empty geometry still has an empty part 0.
+    fn part(&self, part_id: HAPI_PartId) -> Outcome<&Part> {
+        static EMPTY: Part = Part {
+            name: String::new(),
+            part_type: PartType::Mesh,
+            point_count: 0,
+            face_count: 0,
+            vertex_count: 0,
+            face_counts: Vec::new(),
+            vertices: Vec::new(),
+            attributes: Vec::new(),
+        };
+        let parts = &self.geometry().parts;
+        match parts.get(part_id as usize) {
+            Some(part) => Ok(part),
+            None if part_id == 0 && parts.is_empty() => Ok(&EMPTY),
+            None => Err(invalid(format!("Invalid part id: {part_id}"))),
+        }
+    }
+}
+
+struct SessionData {
+    initialized: bool,
+    threaded: bool,
+    strings: Strings,
+    last_error: String,
+    cook_result: String,
+    pending_cook_polls: u32,
+    server: Option<HAPI_ProcessId>,
+    nodes: BTreeMap<HAPI_NodeId, Node>,
+    next_node_id: HAPI_NodeId,
+    composed_children: HashMap<HAPI_NodeId, Vec<HAPI_NodeId>>,
+    composed_cook_result: String,
+    string_batch: Vec<u8>,
+    time: f64,
+    timeline: HAPI_TimelineOptions,
+    compositor: HAPI_CompositorOptions,
+    env: BTreeMap<String, EnvValue>,
+    // Asset library file paths, the index is the library id.
+    libraries: Vec<String>,
+    caches: BTreeMap<String, Cache>,
+    license_options: Option<String>,
+    license: License,
+    // Async jobs, the index is the job id. `None` once the job is done.
+    jobs: Vec<Option<AsyncSet>>,
+}
+
+/// Async attribute set which reads its input buffer when the job runs, like the Engine does
+/// some time before the job is done.
+struct AsyncSet {
+    node: HAPI_NodeId,
+    part: HAPI_PartId,
+    name: String,
+    owner: AttributeOwner,
+    data: AsyncData,
+    start: c_int,
+    length: c_int,
+}
+
+// The input buffer and its number of values.
+enum AsyncData {
+    Int(*const c_int, c_int),
+    Float(*const f32, c_int),
+}
+
+// SAFETY: the buffer is only read by the job, callers keep it alive until the job is done.
+unsafe impl Send for AsyncSet {}
+
+impl AsyncSet {
+    unsafe fn run(self, s: &mut SessionData) -> Outcome {
+        let (node, part, name, owner) = (self.node, self.part, &self.name, self.owner);
+        match self.data {
+            AsyncData::Int(ptr, len) => {
+                let data = unsafe { slice(ptr, len) }?;
+                set_numeric(s, node, part, name, owner, data, self.start, self.length)
+            }
+            AsyncData::Float(ptr, len) => {
+                let data = unsafe { slice(ptr, len) }?;
+                set_numeric(s, node, part, name, owner, data, self.start, self.length)
+            }
+        }
+    }
+}
+
+impl SessionData {
+    fn cook_state(&self) -> &'static str {
+        if self.pending_cook_polls > 0 {
+            "Cooking"
+        } else {
+            "Ready"
+        }
+    }
+
+    fn new() -> Self {
+        SessionData {
+            initialized: false,
+            threaded: false,
+            strings: Strings::default(),
+            last_error: String::new(),
+            cook_result: String::new(),
+            pending_cook_polls: 0,
+            server: None,
+            nodes: BTreeMap::new(),
+            next_node_id: 0,
+            composed_children: HashMap::new(),
+            composed_cook_result: String::new(),
+            string_batch: Vec::new(),
+            time: 0.0,
+            timeline: default_timeline(),
+            compositor: HAPI_CompositorOptions {
+                maximumResolutionX: 0,
+                maximumResolutionY: 0,
+            },
+            env: default_env(),
+            libraries: Vec::new(),
+            caches: default_caches(),
+            license_options: None,
+            license: License::HoudiniEngine,
+            jobs: Vec::new(),
+        }
+    }
+
+    /// Start an async attribute set, the job runs when its status is queried.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn start_set_job(
+        &mut self,
+        node: HAPI_NodeId,
+        part: HAPI_PartId,
+        name: *const c_char,
+        attr_info: *const HAPI_AttributeInfo,
+        data: AsyncData,
+        start: c_int,
+        length: c_int,
+        job_id: *mut c_int,
+    ) -> Outcome {
+        let (name, owner) = unsafe { (read_str(name)?, info_owner(attr_info)?) };
+        self.node(node)?;
+        self.jobs.push(Some(AsyncSet {
+            node,
+            part,
+            name: name.to_string(),
+            owner,
+            data,
+            start,
+            length,
+        }));
+        unsafe { out(job_id, self.jobs.len() as c_int - 1) }
+    }
+
+    fn initialize(&mut self, threaded: bool) {
+        self.initialized = true;
+        self.threaded = threaded;
+        let def = NodeTypeDef {
+            category: NodeType::None,
+            parms: Vec::new(),
+            inputs: 0,
+        };
+        for (category, name) in MANAGERS {
+            let id = self.add_node(-1, name, name, &def, Some(category));
+            self.node_mut(id).expect("manager node").category = category;
+        }
+    }
+
+    fn cleanup(&mut self) {
+        *self = SessionData::new();
+    }
+
+    fn node(&self, id: HAPI_NodeId) -> Outcome<&Node> {
+        self.nodes
+            .get(&id)
+            .ok_or_else(|| invalid(format!("Invalid node id: {id}")))
+    }
+
+    fn node_mut(&mut self, id: HAPI_NodeId) -> Outcome<&mut Node> {
+        self.nodes
+            .get_mut(&id)
+            .ok_or_else(|| invalid(format!("Invalid node id: {id}")))
+    }
+
+    fn manager(&self, category: NodeType) -> HAPI_NodeId {
+        self.nodes
+            .values()
+            .find(|n| n.parent == -1 && n.category == category)
+            .map(|n| n.id)
+            .unwrap_or(-1)
+    }
+
+    fn is_manager(&self, id: HAPI_NodeId) -> bool {
+        self.nodes.get(&id).is_some_and(|n| n.parent == -1)
+    }
+
+    fn child_category(&self, parent: &Node) -> NodeType {
+        match parent.category {
+            _ if parent.parent == -1 => parent.category,
+            NodeType::Obj => NodeType::Sop,
+            other => other,
+        }
+    }
+
+    fn add_node(
+        &mut self,
+        parent: HAPI_NodeId,
+        name: &str,
+        op: &str,
+        def: &NodeTypeDef,
+        category: Option<NodeType>,
+    ) -> HAPI_NodeId {
+        let id = self.next_node_id;
+        self.next_node_id += 1;
+        let name = self.unique_name(parent, name);
+        let category = category.unwrap_or(def.category);
+        self.nodes.insert(
+            id,
+            Node {
+                id,
+                parent,
+                name,
+                category,
+                op: op.to_string(),
+                parms: def.parms.clone(),
+                inputs: vec![-1; def.inputs as usize],
+                children: Vec::new(),
+                display: None,
+                cook_count: 0,
+                editable: false,
+                staged: GeoData::default(),
+                committed: GeoData::default(),
+                output: None,
+                cook_errors: String::new(),
+                geo_changed: false,
+            },
+        );
+        if let Some(parent) = self.nodes.get_mut(&parent) {
+            parent.children.push(id);
+            if category == NodeType::Sop && parent.display.is_none() {
+                parent.display = Some(id);
+            }
+        }
+        id
+    }
+
+    fn unique_name(&self, parent: HAPI_NodeId, name: &str) -> String {
+        let siblings: HashSet<&str> = self
+            .nodes
+            .values()
+            .filter(|n| n.parent == parent)
+            .map(|n| n.name.as_str())
+            .collect();
+        if !siblings.contains(name) {
+            return name.to_string();
+        }
+        let base = name.trim_end_matches(|c: char| c.is_ascii_digit());
+        (1..)
+            .map(|i| format!("{base}{i}"))
+            .find(|n| !siblings.contains(n.as_str()))
+            .expect("unique name")
+    }
+
+    fn path(&self, id: HAPI_NodeId) -> Outcome<String> {
+        let node = self.node(id)?;
+        if node.parent == -1 {
+            Ok(format!("/{}", node.name))
+        } else {
+            Ok(format!("{}/{}", self.path(node.parent)?, node.name))
+        }
+    }
+
+    fn find_path(&self, parent: HAPI_NodeId, path: &str) -> Option<HAPI_NodeId> {
+        let (mut current, rest) = match path.strip_prefix('/') {
+            Some(rest) => (None, rest),
+            None if parent < 0 => (None, path),
+            None => (Some(parent), path),
+        };
+        for segment in rest.split('/').filter(|s| !s.is_empty()) {
+            current = match segment {
+                "." => current,
+                ".." => Some(self.nodes.get(&current?)?.parent).filter(|p| *p >= 0),
+                name => self
+                    .nodes
+                    .values()
+                    .find(|n| n.parent == current.unwrap_or(-1) && n.name == name)
+                    .map(|n| n.id),
+            };
+            current?;
+        }
+        current
+    }
+
+    fn descendants(&self, id: HAPI_NodeId, recursive: bool) -> Vec<HAPI_NodeId> {
+        let mut out = Vec::new();
+        if let Some(node) = self.nodes.get(&id) {
+            for child in &node.children {
+                out.push(*child);
+                if recursive {
+                    out.extend(self.descendants(*child, true));
+                }
+            }
+        }
+        out
+    }
+
+    /// Nodes with an input connected to `id`, nodes only have a single output.
+    fn output_connections(&self, id: HAPI_NodeId, output: c_int) -> Outcome<Vec<HAPI_NodeId>> {
+        self.node(id)?;
+        if output != 0 {
+            return Ok(Vec::new());
+        }
+        let mut connected: Vec<_> = self
+            .nodes
+            .values()
+            .filter(|node| node.inputs.contains(&id))
+            .map(|node| node.id)
+            .collect();
+        connected.sort();
+        Ok(connected)
+    }
+
+    fn node_flags(&self, node: &Node) -> i32 {
+        let mut flags = 0;
+        if self
+            .nodes
+            .get(&node.parent)
+            .is_some_and(|p| p.display == Some(node.id))
+        {
+            flags |= NodeFlags::Display as i32 | NodeFlags::Render as i32;
+        }
+        if node.editable {
+            flags |= NodeFlags::Editable as i32;
+        }
+        if !node.children.is_empty() {
+            flags |= NodeFlags::Network as i32;
+        }
+        if node.category == NodeType::Obj && node.op == "geo" {
+            flags |= NodeFlags::Geometry as i32;
+        }
+        if node.op == "subnet" {
+            flags |= NodeFlags::Subnet as i32;
+        }
+        flags | NodeFlags::NonBypass as i32
+    }
+
+    fn matches_filter(&self, id: HAPI_NodeId, types: i32, flags: i32) -> bool {
+        let Some(node) = self.nodes.get(&id) else {
+            return false;
+        };
+        let type_match = types == NodeType::Any as i32 || (types & node.category as i32) != 0;
+        let flags_match = flags == NodeFlags::Any as i32
+            || flags == NodeFlags::None as i32
+            || (self.node_flags(node) & flags) == flags;
+        type_match && flags_match
+    }
+
+    fn delete(&mut self, id: HAPI_NodeId) {
+        for child in self.descendants(id, false) {
+            self.delete(child);
+        }
+        if let Some(node) = self.nodes.remove(&id)
+            && let Some(parent) = self.nodes.get_mut(&node.parent)
+        {
+            parent.children.retain(|c| *c != id);
+            if parent.display == Some(id) {
+                parent.display = parent.children.last().copied();
+            }
+        }
+        for node in self.nodes.values_mut() {
+            for input in node.inputs.iter_mut().filter(|i| **i == id) {
+                *input = -1;
+            }
+        }
+    }
+
+    fn node_info(&mut self, id: HAPI_NodeId) -> Outcome<HAPI_NodeInfo> {
+        let path = self.path(id)?;
+        let node = self.node(id)?;
+        let (name, category, parent, parm_count) = (
+            node.name.clone(),
+            node.category,
+            node.parent,
+            node.parms.len(),
+        );
+        let (int_count, float_count, string_count) = node.value_offsets(parm_count);
+        let (children, inputs, cook_count) =
+            (node.children.len(), node.inputs.len(), node.cook_count);
+        Ok(HAPI_NodeInfo {
+            id,
+            parentId: parent,
+            nameSH: self.strings.intern(&name),
+            type_: category,
+            isValid: 1,
+            totalCookCount: cook_count,
+            uniqueHoudiniNodeId: id,
+            internalNodePathSH: self.strings.intern(&path),
+            parmCount: parm_count as i32,
+            parmIntValueCount: int_count,
+            parmFloatValueCount: float_count,
+            parmStringValueCount: string_count,
+            parmChoiceCount: 0,
+            childNodeCount: children as i32,
+            inputCount: inputs as i32,
+            outputCount: (category == NodeType::Sop) as i32,
+            createdPostAssetLoad: 0,
+            isTimeDependent: 0,
+        })
+    }
+
+    fn parm_by_id_mut(
+        &mut self,
+        node: HAPI_NodeId,
+        parm_id: HAPI_ParmId,
+    ) -> Outcome<&mut FakeParm> {
+        let node = self.node_mut(node)?;
+        usize::try_from(parm_id)
+            .ok()
+            .and_then(|index| node.parms.get_mut(index))
+            .ok_or_else(|| invalid(format!("Invalid parameter id: {parm_id}")))
+    }
+
+    /// Every node reports its operator as asset info, like regular nodes in Houdini.
+    fn asset_info(&mut self, id: HAPI_NodeId) -> Outcome<HAPI_AssetInfo> {
+        let node = self.node(id)?;
+        let full_op_name = format!("{}/{}", category_name(node.category), node.op);
+        let (op, category) = (node.op.clone(), node.category);
+        // SAFETY: AssetInfo is plain C data.
+        let mut info: HAPI_AssetInfo = unsafe { std::mem::zeroed() };
+        info.nodeId = id;
+        info.objectNodeId = if category == NodeType::Obj { id } else { -1 };
+        info.hasEverCooked = 1;
+        info.nameSH = self.strings.intern(&op);
+        info.labelSH = self.strings.intern(&op);
+        info.fullOpNameSH = self.strings.intern(&full_op_name);
+        info.filePathSH = self.strings.intern("");
+        info.versionSH = self.strings.intern("");
+        info.helpTextSH = self.strings.intern("");
+        info.helpURLSH = self.strings.intern("");
+        Ok(info)
+    }
+
+    fn parm_info(&mut self, node: HAPI_NodeId, index: usize) -> Outcome<HAPI_ParmInfo> {
+        let node = self.node(node)?;
+        let parm = node
+            .parms
+            .get(index)
+            .ok_or_else(|| invalid(format!("Invalid parameter id: {index}")))?
+            .clone();
+        let (int_index, float_index, string_index) = node.value_offsets(index);
+        // SAFETY: ParmInfo is plain C data with zero discriminants for all its enum fields.
+        let mut info: HAPI_ParmInfo = unsafe { std::mem::zeroed() };
+        info.id = index as HAPI_ParmId;
+        info.parentId = -1;
+        info.type_ = parm.parm_type;
+        info.scriptType = parm.script_type();
+        info.permissions = Permissions::ReadWrite;
+        info.size = parm.size();
+        info.nameSH = self.strings.intern(&parm.name);
+        info.labelSH = self.strings.intern(&parm.label);
+        info.templateNameSH = info.nameSH;
+        info.intValuesIndex = -1;
+        info.floatValuesIndex = -1;
+        info.stringValuesIndex = -1;
+        info.choiceIndex = -1;
+        match parm.value {
+            ParmValue::Int(_) => info.intValuesIndex = int_index,
+            ParmValue::Float(_) => info.floatValuesIndex = float_index,
+            ParmValue::String(_) => info.stringValuesIndex = string_index,
+        }
+        info.inputNodeType = NodeType::Any;
+        info.inputNodeFlag = NodeFlags::Any;
+        info.rampType = RampType::Invalid;
+        Ok(info)
+    }
+
+    /// Node with the geometry for `id`: the node itself for SOPs, the display SOP for objects.
+    fn geometry_node(&self, id: HAPI_NodeId) -> Outcome<HAPI_NodeId> {
+        let node = self.node(id)?;
+        match node.category {
+            NodeType::Sop => Ok(id),
+            NodeType::Obj => node
+                .display
+                .ok_or_else(|| invalid(format!("Object {} has no display geometry", node.name))),
+            _ => Err(invalid(format!("Node {} has no geometry", node.name))),
+        }
+    }
+
+    /// Output geometry nodes: the display node of objects and SOP networks, leaf SOPs output themselves.
+    fn output_geos(&self, id: HAPI_NodeId) -> Outcome<Vec<HAPI_NodeId>> {
+        let node = self.node(id)?;
+        Ok(match node.category {
+            NodeType::Sop if node.children.is_empty() => vec![id],
+            NodeType::Obj | NodeType::Sop => node.display.into_iter().collect(),
+            _ => Vec::new(),
+        })
+    }
+
+    fn geo_info(&mut self, id: HAPI_NodeId) -> Outcome<HAPI_GeoInfo> {
+        let id = self.geometry_node(id)?;
+        let node = self.node(id)?;
+        let is_display = self
+            .nodes
+            .get(&node.parent)
+            .is_some_and(|p| p.display == Some(id));
+        let (name, editable, part_count, changed) = (
+            node.name.clone(),
+            node.editable,
+            node.geometry().parts.len(),
+            node.geo_changed,
+        );
+        self.node_mut(id)?.geo_changed = false;
+        Ok(HAPI_GeoInfo {
+            type_: if editable {
+                GeoType::Input
+            } else {
+                GeoType::Default
+            },
+            nameSH: self.strings.intern(&name),
+            nodeId: id,
+            isEditable: editable as HAPI_Bool,
+            isTemplated: 0,
+            isDisplayGeo: is_display as HAPI_Bool,
+            hasGeoChanged: changed as HAPI_Bool,
+            hasMaterialChanged: 0,
+            pointGroupCount: 0,
+            primitiveGroupCount: 0,
+            edgeGroupCount: 0,
+            partCount: part_count as i32,
+        })
+    }
+
+    /// Saved geometry is a text dump of the cooked parts, the requested format is ignored.
+    fn geo_bytes(&self, id: HAPI_NodeId) -> Outcome<Vec<u8>> {
+        let node = self.node(self.geometry_node(id)?)?;
+        Ok(format!("{:#?}", node.geometry()).into_bytes())
+    }
+
+    fn cook(&mut self, id: HAPI_NodeId, visited: &mut HashSet<HAPI_NodeId>) -> Outcome {
+        if !visited.insert(id) {
+            return Ok(());
+        }
+        let node = self.node(id)?;
+        let inputs: Vec<_> = node.inputs.iter().copied().filter(|i| *i >= 0).collect();
+        let display = node.display;
+        if node.category == NodeType::Obj {
+            if let Some(display) = display {
+                self.cook(display, visited)?;
+            }
+            self.node_mut(id)?.cook_count += 1;
+            return Ok(());
+        }
+        for input in &inputs {
+            self.cook(*input, visited)?;
+        }
+        let node = self.node(id)?;
+        let input_geo = |index: usize| {
+            inputs
+                .get(index)
+                .and_then(|i| self.nodes.get(i))
+                .map(|n| n.geometry().clone())
+                .unwrap_or_default()
+        };
+        let mut errors = String::new();
+        let output = match node.op.as_str() {
+            INPUT_OP => node.committed.clone(),
+            "box" => box_geometry(&node.float_parm("size"), &node.float_parm("t")),
+            "merge" => GeoData {
+                parts: (0..inputs.len()).flat_map(|i| input_geo(i).parts).collect(),
+            },
+            "xform" => {
+                let mut geo = input_geo(0);
+                translate(&mut geo, &node.float_parm("t"));
+                geo
+            }
+            "error" => {
+                if let Ok((_, parm)) = node.parm("text")
+                    && let ParmValue::String(text) = &parm.value
+                {
+                    let severity = match node.parm("severity").map(|(_, p)| &p.value) {
+                        Ok(ParmValue::Int(v)) => v.first().copied().unwrap_or(2),
+                        _ => 2,
+                    };
+                    let prefix = match severity {
+                        0 => "Message",
+                        1 => "Warning",
+                        _ => "Error",
+                    };
+                    errors = format!("{prefix}: {}", text[0]);
+                }
+                input_geo(0)
+            }
+            _ => input_geo(0),
+        };
+        if errors.starts_with("Error") {
+            self.cook_result = format!("{}: {errors}", self.path(id)?);
+        }
+        // Each SOP cook takes a megabyte of cache.
+        if let Some(cache) = self.caches.get_mut("SOP Cache") {
+            cache.grow(1);
+        }
+        let node = self.node_mut(id)?;
+        node.output = Some(output);
+        node.cook_errors = errors;
+        node.cook_count += 1;
+        node.geo_changed = true;
+        Ok(())
+    }
+
+    fn batch(&mut self, handles: &[HAPI_StringHandle]) -> Outcome<i32> {
+        let mut buffer = Vec::new();
+        for handle in handles {
+            buffer.extend_from_slice(self.strings.get(*handle)?.as_bytes());
+            buffer.push(0);
+        }
+        let len = buffer.len() as i32;
+        self.string_batch = buffer;
+        Ok(len)
+    }
+}
+
+fn default_timeline() -> HAPI_TimelineOptions {
+    HAPI_TimelineOptions {
+        fps: 24.0,
+        startTime: 1.0 / 24.0,
+        endTime: 10.0,
+    }
+}
+
+fn box_geometry(size: &[f32], center: &[f32]) -> GeoData {
+    let half = |i: usize| size.get(i).copied().unwrap_or(1.0) * 0.5;
+    let c = |i: usize| center.get(i).copied().unwrap_or(0.0);
+    let mut positions = Vec::with_capacity(24);
+    for corner in 0..8 {
+        let sign = |bit: usize| if corner & (1 << bit) == 0 { -1.0 } else { 1.0 };
+        positions.extend([
+            c(0) + sign(0) * half(0),
+            c(1) + sign(1) * half(1),
+            c(2) + sign(2) * half(2),
+        ]);
+    }
+    let vertices = vec![
+        0, 2, 3, 1, 4, 5, 7, 6, 0, 1, 5, 4, 2, 6, 7, 3, 0, 4, 6, 2, 1, 3, 7, 5,
+    ];
+    let mut part = Part::new("box");
+    part.point_count = 8;
+    part.face_count = 6;
+    part.vertex_count = 24;
+    part.face_counts = vec![4; 6];
+    part.vertices = vertices;
+    part.attributes.push(Attrib {
+        name: "P".to_string(),
+        owner: AttributeOwner::Point,
+        tuple_size: 3,
+        count: 8,
+        type_info: AttributeTypeInfo::Point,
+        values: AttribValues::Float(positions),
+    });
+    GeoData { parts: vec![part] }
+}
+
+fn translate(geo: &mut GeoData, offset: &[f32]) {
+    for part in &mut geo.parts {
+        if let Ok(p) = part.attribute_mut("P", AttributeOwner::Point)
+            && let AttribValues::Float(values) = &mut p.values
+        {
+            let tuple = p.tuple_size.max(1) as usize;
+            for point in values.chunks_mut(tuple) {
+                for (v, o) in point.iter_mut().zip(offset) {
+                    *v += o;
+                }
+            }
+        }
+    }
+}
+
+/// Write `value` through an output pointer.
+unsafe fn out<T>(ptr: *mut T, value: T) -> Outcome {
+    if ptr.is_null() {
+        return Err(invalid("Output pointer is null"));
+    }
+    unsafe { ptr.write(value) };
+    Ok(())
+}
+
+unsafe fn read_str<'a>(ptr: *const c_char) -> Outcome<&'a str> {
+    if ptr.is_null() {
+        return Err(invalid("String argument is null"));
+    }
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|_| invalid("String argument is not valid UTF-8"))
+}
+
+unsafe fn opt_str<'a>(ptr: *const c_char) -> Outcome<Option<&'a str>> {
+    if ptr.is_null() {
+        Ok(None)
+    } else {
+        unsafe { read_str(ptr) }.map(Some)
+    }
+}
+
+unsafe fn slice<'a, T>(ptr: *const T, len: c_int) -> Outcome<&'a [T]> {
+    match len {
+        0 => Ok(&[]),
+        _ if len < 0 || ptr.is_null() => Err(invalid("Invalid array argument")),
+        _ => Ok(unsafe { std::slice::from_raw_parts(ptr, len as usize) }),
+    }
+}
+
+unsafe fn slice_mut<'a, T>(ptr: *mut T, len: c_int) -> Outcome<&'a mut [T]> {
+    match len {
+        0 => Ok(&mut []),
+        _ if len < 0 || ptr.is_null() => Err(invalid("Invalid array argument")),
+        _ => Ok(unsafe { std::slice::from_raw_parts_mut(ptr, len as usize) }),
+    }
+}
+
+/// Copy `value` with a null terminator into a buffer of `len` bytes.
+unsafe fn copy_string(buffer: *mut c_char, len: c_int, value: &[u8]) -> Outcome {
+    let buffer = unsafe { slice_mut(buffer as *mut u8, len) }?;
+    let n = value.len().min(buffer.len().saturating_sub(1));
+    buffer[..n].copy_from_slice(&value[..n]);
+    if let Some(end) = buffer.get_mut(n) {
+        *end = 0;
+    }
+    Ok(())
+}
+
+fn range(start: c_int, length: c_int, tuple: i32, total: usize) -> Outcome<std::ops::Range<usize>> {
+    let tuple = tuple.max(1) as usize;
+    let (start, end) = (start as usize * tuple, (start + length) as usize * tuple);
+    if start > end || end > total {
+        return Err(invalid(format!(
+            "Data range {start}..{end} is out of bounds ({total})"
+        )));
+    }
+    Ok(start..end)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn set_numeric<T: Element>(
+    s: &mut SessionData,
+    node: HAPI_NodeId,
+    part: HAPI_PartId,
+    name: &str,
+    owner: AttributeOwner,
+    data: &[T],
+    start: c_int,
+    length: c_int,
+) -> Outcome {
+    let attr = s
+        .node_mut(node)?
+        .staged_part(part)?
+        .attribute_mut(name, owner)?;
+    let tuple = attr.tuple_size;
+    let values = T::values_mut(&mut attr.values)
+        .ok_or_else(|| invalid(format!("Attribute {name} storage mismatch")))?;
+    let range = range(start, length, tuple, values.len())?;
+    let count = range.len();
+    values[range].copy_from_slice(&data[..count.min(data.len())]);
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn get_numeric<T: Element>(
+    s: &mut SessionData,
+    node: HAPI_NodeId,
+    part: HAPI_PartId,
+    name: &str,
+    owner: AttributeOwner,
+    data: *mut T,
+    start: c_int,
+    length: c_int,
+) -> Outcome {
+    let attr = s
+        .node(node)?
+        .part(part)?
+        .attribute(name, owner)
+        .ok_or_else(|| invalid(format!("Attribute {name} does not exist")))?;
+    let values = T::values(&attr.values)
+        .ok_or_else(|| invalid(format!("Attribute {name} storage mismatch")))?;
+    let range = range(start, length, attr.tuple_size, values.len())?;
+    let out = unsafe { slice_mut(data, range.len() as c_int) }?;
+    out.copy_from_slice(&values[range]);
+    Ok(())
+}
+
+/// Resolve the attribute owner passed alongside attribute data.
+unsafe fn info_owner(info: *const HAPI_AttributeInfo) -> Outcome<AttributeOwner> {
+    unsafe { info.as_ref() }
+        .map(|i| i.owner)
+        .ok_or_else(|| invalid("Attribute info is null"))
+}
+
+macro_rules! zeroed_structs {
+    ($($create:ident, $init:ident => $ty:ty;)*) => {
+        $(
+            unsafe fn $create(&self) -> $ty {
+                // SAFETY: Engine structs are plain C data, all of their enum fields have a zero variant.
+                unsafe { std::mem::zeroed() }
+            }
+            unsafe fn $init(&self, in_: *mut $ty) {
+                if !in_.is_null() {
+                    unsafe { in_.write(std::mem::zeroed()) };
+                }
+            }
+        )*
+    };
+}
+
+#[allow(non_snake_case)]
+impl HapiBackend for FakeEngine {
+    fn is_process_alive(&self, pid: u32) -> bool {
+        self.is_server_running(pid)
+    }
+
+    fn kill_process(&self, pid: u32) -> bool {
+        let running = self.is_server_running(pid);
+        self.kill_server(pid);
+        running
+    }
+
+    zeroed_structs! {
+        HAPI_AssetInfo_Create, HAPI_AssetInfo_Init => HAPI_AssetInfo;
+        HAPI_CompositorOptions_Create, HAPI_CompositorOptions_Init => HAPI_CompositorOptions;
+        HAPI_CurveInfo_Create, HAPI_CurveInfo_Init => HAPI_CurveInfo;
+        HAPI_GeoInfo_Create, HAPI_GeoInfo_Init => HAPI_GeoInfo;
+        HAPI_HandleBindingInfo_Create, HAPI_HandleBindingInfo_Init => HAPI_HandleBindingInfo;
+        HAPI_HandleInfo_Create, HAPI_HandleInfo_Init => HAPI_HandleInfo;
+        HAPI_ImageFileFormat_Create, HAPI_ImageFileFormat_Init => HAPI_ImageFileFormat;
+        HAPI_ImageInfo_Create, HAPI_ImageInfo_Init => HAPI_ImageInfo;
+        HAPI_InputCurveInfo_Create, HAPI_InputCurveInfo_Init => HAPI_InputCurveInfo;
+        HAPI_Keyframe_Create, HAPI_Keyframe_Init => HAPI_Keyframe;
+        HAPI_MaterialInfo_Create, HAPI_MaterialInfo_Init => HAPI_MaterialInfo;
+        HAPI_NodeInfo_Create, HAPI_NodeInfo_Init => HAPI_NodeInfo;
+        HAPI_ObjectInfo_Create, HAPI_ObjectInfo_Init => HAPI_ObjectInfo;
+        HAPI_ParmChoiceInfo_Create, HAPI_ParmChoiceInfo_Init => HAPI_ParmChoiceInfo;
+        HAPI_ParmInfo_Create, HAPI_ParmInfo_Init => HAPI_ParmInfo;
+        HAPI_SessionInfo_Create, HAPI_SessionInfo_Init => HAPI_SessionInfo;
+        HAPI_SessionSyncInfo_Create, HAPI_SessionSyncInfo_Init => HAPI_SessionSyncInfo;
+        HAPI_Transform_Create, HAPI_Transform_Init => HAPI_Transform;
+        HAPI_TransformEuler_Create, HAPI_TransformEuler_Init => HAPI_TransformEuler;
+        HAPI_Viewport_Create, HAPI_Viewport_Init => HAPI_Viewport;
+        HAPI_VolumeInfo_Create, HAPI_VolumeInfo_Init => HAPI_VolumeInfo;
+        HAPI_VolumeTileInfo_Create, HAPI_VolumeTileInfo_Init => HAPI_VolumeTileInfo;
+    }
+
+    unsafe fn HAPI_CookOptions_Create(&self) -> HAPI_CookOptions {
+        HAPI_CookOptions {
+            splitGeosByGroup: 0,
+            splitGroupSH: 0,
+            splitGeosByAttribute: 0,
+            splitAttrSH: 0,
+            maxVerticesPerPrimitive: -1,
+            refineCurveToLinear: 0,
+            curveRefineLOD: 8.0,
+            clearErrorsAndWarnings: 0,
+            cookTemplatedGeos: 0,
+            splitPointsByVertexAttributes: 0,
+            packedPrimInstancingMode: PackedPrimInstancingMode::Flat,
+            handleBoxPartTypes: 0,
+            handleSpherePartTypes: 0,
+            checkPartChanges: 0,
+            cacheMeshTopology: 0,
+            preferOutputNodes: 0,
+            extraFlags: 0,
+        }
+    }
+
+    unsafe fn HAPI_CookOptions_Init(&self, in_: *mut HAPI_CookOptions) {
+        if !in_.is_null() {
+            unsafe { in_.write(self.HAPI_CookOptions_Create()) };
+        }
+    }
+
+    unsafe fn HAPI_TimelineOptions_Create(&self) -> HAPI_TimelineOptions {
+        default_timeline()
+    }
+
+    unsafe fn HAPI_TimelineOptions_Init(&self, in_: *mut HAPI_TimelineOptions) {
+        if !in_.is_null() {
+            unsafe { in_.write(default_timeline()) };
+        }
+    }
+
+    unsafe fn HAPI_ThriftServerOptions_Create(&self) -> HAPI_ThriftServerOptions {
+        HAPI_ThriftServerOptions {
+            autoClose: 1,
+            timeoutMs: 10_000.0,
+            verbosity: StatusVerbosity::Statusverbosity0,
+            sharedMemoryBufferType: ThriftSharedMemoryBufferType::Buffer,
+            sharedMemoryBufferSize: 100,
+        }
+    }
+
+    unsafe fn HAPI_ThriftServerOptions_Init(&self, in_: *mut HAPI_ThriftServerOptions) {
+        if !in_.is_null() {
+            unsafe { in_.write(self.HAPI_ThriftServerOptions_Create()) };
+        }
+    }
+
+    unsafe fn HAPI_PartInfo_Create(&self) -> HAPI_PartInfo {
+        Part::new("").info(0, &mut Strings::default())
+    }
+
+    unsafe fn HAPI_PartInfo_Init(&self, in_: *mut HAPI_PartInfo) {
+        if !in_.is_null() {
+            unsafe { in_.write(self.HAPI_PartInfo_Create()) };
+        }
+    }
+
+    unsafe fn HAPI_AttributeInfo_Create(&self) -> HAPI_AttributeInfo {
+        HAPI_AttributeInfo {
+            exists: 0,
+            owner: AttributeOwner::Invalid,
+            storage: StorageType::Invalid,
+            originalOwner: AttributeOwner::Invalid,
+            count: 0,
+            tupleSize: 0,
+            totalArrayElements: 0,
+            typeInfo: AttributeTypeInfo::Invalid,
+        }
+    }
+
+    unsafe fn HAPI_AttributeInfo_Init(&self, in_: *mut HAPI_AttributeInfo) {
+        if !in_.is_null() {
+            unsafe { in_.write(self.HAPI_AttributeInfo_Create()) };
+        }
+    }
+
+    unsafe fn HAPI_PartInfo_GetElementCountByAttributeOwner(
+        &self,
+        in_: *mut HAPI_PartInfo,
+        owner: AttributeOwner,
+    ) -> c_int {
+        let Some(info) = (unsafe { in_.as_ref() }) else {
+            return 0;
+        };
+        match owner {
+            AttributeOwner::Vertex => info.vertexCount,
+            AttributeOwner::Point => info.pointCount,
+            AttributeOwner::Prim => info.faceCount,
+            AttributeOwner::Detail => 1,
+            _ => 0,
+        }
+    }
+
+    unsafe fn HAPI_PartInfo_GetElementCountByGroupType(
+        &self,
+        in_: *mut HAPI_PartInfo,
+        type_: GroupType,
+    ) -> c_int {
+        let Some(info) = (unsafe { in_.as_ref() }) else {
+            return 0;
+        };
+        match type_ {
+            GroupType::Point => info.pointCount,
+            GroupType::Prim => info.faceCount,
+            _ => 0,
+        }
+    }
+
+    unsafe fn HAPI_PartInfo_GetAttributeCountByOwner(
+        &self,
+        in_: *mut HAPI_PartInfo,
+        owner: AttributeOwner,
+    ) -> c_int {
+        match unsafe { in_.as_ref() } {
+            Some(info) if (0..4).contains(&(owner as i32)) => info.attributeCounts[owner as usize],
+            _ => 0,
+        }
+    }
+
+    unsafe fn HAPI_GeoInfo_GetGroupCountByType(
+        &self,
+        _in_: *mut HAPI_GeoInfo,
+        _type_: GroupType,
+    ) -> c_int {
+        0
+    }
+
+    unsafe fn HAPI_GetEnvInt(&self, int_type: EnvIntType, value: *mut c_int) -> HapiResult {
+        let (major, minor, build) = {
+            let mut parts = env!("CARGO_PKG_VERSION")
+                .split('.')
+                .map(|p| p.parse().unwrap_or(0));
+            (parts.next().unwrap_or(0), parts.next().unwrap_or(0), 0)
+        };
+        let v = match int_type {
+            EnvIntType::HoudiniMajor | EnvIntType::EngineMajor => major,
+            EnvIntType::HoudiniMinor | EnvIntType::EngineMinor => minor,
+            EnvIntType::HoudiniBuild | EnvIntType::HoudiniPatch | EnvIntType::EngineApi => build,
+            _ => return HapiResult::InvalidArgument,
+        };
+        match unsafe { out(value, v) } {
+            Ok(()) => HapiResult::Success,
+            Err(Fail(code, _)) => code,
+        }
+    }
+
+    // Sessions
+
+    unsafe fn HAPI_CreateInProcessSession(
+        &self,
+        session: *mut HAPI_Session,
+        _session_info: *const HAPI_SessionInfo,
+    ) -> HapiResult {
+        self.new_session(session, SessionType::Inprocess)
+    }
+
+    unsafe fn HAPI_BindCustomImplementation(
+        &self,
+        session_type: SessionType,
+        dll_path: *const c_char,
+    ) -> HapiResult {
+        let path = match unsafe { read_str(dll_path) } {
+            Ok(path) if !path.is_empty() => path.to_string(),
+            _ => return HapiResult::InvalidArgument,
+        };
+        self.state
+            .lock()
+            .custom_implementations
+            .insert(session_type as i32, path);
+        HapiResult::Success
+    }
+
+    unsafe fn HAPI_CreateCustomSession(
+        &self,
+        session_type: SessionType,
+        _session_info: *mut std::ffi::c_void,
+        session: *mut HAPI_Session,
+    ) -> HapiResult {
+        let mut state = self.state.lock();
+        if !state
+            .custom_implementations
+            .contains_key(&(session_type as i32))
+        {
+            state.connection_error = format!("No implementation bound to {session_type:?}");
+            return HapiResult::Failure;
+        }
+        drop(state);
+        self.new_session(session, session_type)
+    }
+
+    unsafe fn HAPI_StartThriftSocketServer(
+        &self,
+        _options: *const HAPI_ThriftServerOptions,
+        port: c_int,
+        process_id: *mut HAPI_ProcessId,
+        _log_file: *const c_char,
+    ) -> HapiResult {
+        self.start_server(format!("localhost:{port}"), process_id)
+    }
+
+    unsafe fn HAPI_CreateThriftSocketSession(
+        &self,
+        session: *mut HAPI_Session,
+        host_name: *const c_char,
+        port: c_int,
+        _session_info: *const HAPI_SessionInfo,
+    ) -> HapiResult {
+        let host = match unsafe { read_str(host_name) } {
+            Ok("127.0.0.1") => "localhost",
+            Ok(host) => host,
+            Err(Fail(code, _)) => return code,
+        };
+        self.connect(format!("{host}:{port}"), session)
+    }
+
+    unsafe fn HAPI_StartThriftNamedPipeServer(
+        &self,
+        _options: *const HAPI_ThriftServerOptions,
+        pipe_name: *const c_char,
+        process_id: *mut HAPI_ProcessId,
+        _log_file: *const c_char,
+    ) -> HapiResult {
+        match unsafe { read_str(pipe_name) } {
+            Ok(name) => self.start_server(name.to_string(), process_id),
+            Err(Fail(code, _)) => code,
+        }
+    }
+
+    unsafe fn HAPI_CreateThriftNamedPipeSession(
+        &self,
+        session: *mut HAPI_Session,
+        pipe_name: *const c_char,
+        _session_info: *const HAPI_SessionInfo,
+    ) -> HapiResult {
+        match unsafe { read_str(pipe_name) } {
+            Ok(name) => self.connect(name.to_string(), session),
+            Err(Fail(code, _)) => code,
+        }
+    }
+
+    unsafe fn HAPI_StartThriftSharedMemoryServer(
+        &self,
+        _options: *const HAPI_ThriftServerOptions,
+        shared_mem_name: *const c_char,
+        process_id: *mut HAPI_ProcessId,
+        _log_file: *const c_char,
+    ) -> HapiResult {
+        match unsafe { read_str(shared_mem_name) } {
+            Ok(name) => self.start_server(name.to_string(), process_id),
+            Err(Fail(code, _)) => code,
+        }
+    }
+
+    unsafe fn HAPI_CreateThriftSharedMemorySession(
+        &self,
+        session: *mut HAPI_Session,
+        shared_mem_name: *const c_char,
+        _session_info: *const HAPI_SessionInfo,
+    ) -> HapiResult {
+        match unsafe { read_str(shared_mem_name) } {
+            Ok(name) => self.connect(name.to_string(), session),
+            Err(Fail(code, _)) => code,
+        }
+    }
+
+    unsafe fn HAPI_IsSessionValid(&self, session: *const HAPI_Session) -> HapiResult {
+        self.call_with(session, false, |_| Ok(()))
+    }
+
+    unsafe fn HAPI_CloseSession(&self, session: *const HAPI_Session) -> HapiResult {
+        let Some(id) = (unsafe { session.as_ref() }).map(|s| s.id) else {
+            return HapiResult::InvalidArgument;
+        };
+        match self.state.lock().sessions.remove(&id) {
+            Some(_) => HapiResult::Success,
+            None => HapiResult::InvalidSession,
+        }
+    }
+
+    unsafe fn HAPI_IsInitialized(&self, session: *const HAPI_Session) -> HapiResult {
+        self.call_with(session, false, |s| match s.initialized {
+            true => Ok(()),
+            false => Err(Fail(HapiResult::NotInitialized, String::new())),
+        })
+    }
+
+    unsafe fn HAPI_Initialize(
+        &self,
+        session: *const HAPI_Session,
+        _cook_options: *const HAPI_CookOptions,
+        use_cooking_thread: HAPI_Bool,
+        _cooking_thread_stack_size: c_int,
+        _houdini_environment_files: *const c_char,
+        _otl_search_path: *const c_char,
+        _dso_search_path: *const c_char,
+        _image_dso_search_path: *const c_char,
+        _audio_dso_search_path: *const c_char,
+    ) -> HapiResult {
+        let licenses = self.state.lock().licenses.clone();
+        self.call_with(session, false, |s| {
+            if s.initialized {
+                return Err(Fail(
+                    HapiResult::AlreadyInitialized,
+                    "Session is already initialized".to_string(),
+                ));
+            }
+            s.license = check_out_license(s.license_options.as_deref(), licenses.as_deref())?;
+            s.initialize(use_cooking_thread != 0);
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_Cleanup(&self, session: *const HAPI_Session) -> HapiResult {
+        self.call(session, |s| {
+            s.cleanup();
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_Shutdown(&self, session: *const HAPI_Session) -> HapiResult {
+        self.call_with(session, false, |_| Ok(()))
+    }
+
+    unsafe fn HAPI_GetSessionEnvInt(
+        &self,
+        session: *const HAPI_Session,
+        int_type: SessionEnvIntType,
+        value: *mut c_int,
+    ) -> HapiResult {
+        self.call(session, |s| match int_type {
+            SessionEnvIntType::License => unsafe { out(value, s.license as i32) },
+            _ => Err(invalid("Invalid session env int type")),
+        })
+    }
+
+    unsafe fn HAPI_GetServerEnvInt(
+        &self,
+        session: *const HAPI_Session,
+        variable_name: *const c_char,
+        value: *mut c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let name = read_str(variable_name)?;
+            match s.env.get(name) {
+                Some(EnvValue::Int(v)) => out(value, *v),
+                Some(EnvValue::String(v)) => out(value, v.parse().unwrap_or(0)),
+                None => Err(invalid(format!("Variable {name} is not set"))),
+            }
+        })
+    }
+
+    unsafe fn HAPI_GetServerEnvString(
+        &self,
+        session: *const HAPI_Session,
+        variable_name: *const c_char,
+        value: *mut HAPI_StringHandle,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let name = read_str(variable_name)?;
+            let text = match s.env.get(name) {
+                Some(EnvValue::Int(v)) => v.to_string(),
+                Some(EnvValue::String(v)) => v.clone(),
+                None => return Err(invalid(format!("Variable {name} is not set"))),
+            };
+            out(value, s.strings.intern(&text))
+        })
+    }
+
+    unsafe fn HAPI_GetServerEnvVarCount(
+        &self,
+        session: *const HAPI_Session,
+        env_count: *mut c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe { out(env_count, s.env.len() as i32) })
+    }
+
+    unsafe fn HAPI_GetServerEnvVarList(
+        &self,
+        session: *const HAPI_Session,
+        values_array: *mut HAPI_StringHandle,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let vars: Vec<String> = s
+                .env
+                .iter()
+                .map(|(k, v)| match v {
+                    EnvValue::Int(v) => format!("{k}={v}"),
+                    EnvValue::String(v) => format!("{k}={v}"),
+                })
+                .collect();
+            let range = range(start, length, 1, vars.len())?;
+            let out = slice_mut(values_array, length)?;
+            for (handle, var) in out.iter_mut().zip(&vars[range]) {
+                *handle = s.strings.intern(var);
+            }
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_SetServerEnvInt(
+        &self,
+        session: *const HAPI_Session,
+        variable_name: *const c_char,
+        value: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            s.env
+                .insert(read_str(variable_name)?.to_string(), EnvValue::Int(value));
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_SetServerEnvString(
+        &self,
+        session: *const HAPI_Session,
+        variable_name: *const c_char,
+        value: *const c_char,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            s.env.insert(
+                read_str(variable_name)?.to_string(),
+                EnvValue::String(read_str(value)?.to_string()),
+            );
+            Ok(())
+        })
+    }
+
+    // Status
+
+    unsafe fn HAPI_GetStatus(
+        &self,
+        session: *const HAPI_Session,
+        status_type: StatusType,
+        status: *mut c_int,
+    ) -> HapiResult {
+        self.call_with(session, false, |s| unsafe {
+            let value = match status_type {
+                StatusType::CallResult => (!s.last_error.is_empty()) as i32,
+                StatusType::CookResult => (!s.cook_result.is_empty()) as i32,
+                StatusType::CookState if s.pending_cook_polls > 0 => {
+                    s.pending_cook_polls -= 1;
+                    State::Cooking as i32
+                }
+                StatusType::CookState if s.threaded && !s.cook_result.is_empty() => {
+                    State::ReadyWithCookErrors as i32
+                }
+                StatusType::CookState => State::Ready as i32,
+                _ => return Err(invalid("Invalid status type")),
+            };
+            out(status, value)
+        })
+    }
+
+    unsafe fn HAPI_GetStatusStringBufLength(
+        &self,
+        session: *const HAPI_Session,
+        status_type: StatusType,
+        _verbosity: StatusVerbosity,
+        buffer_length: *mut c_int,
+    ) -> HapiResult {
+        self.call_with(session, false, |s| unsafe {
+            let len = match status_type {
+                StatusType::CallResult => s.last_error.len(),
+                StatusType::CookResult => s.cook_result.len(),
+                StatusType::CookState => s.cook_state().len(),
+                _ => 0,
+            };
+            out(buffer_length, len as i32 + 1)
+        })
+    }
+
+    unsafe fn HAPI_GetStatusString(
+        &self,
+        session: *const HAPI_Session,
+        status_type: StatusType,
+        string_value: *mut c_char,
+        length: c_int,
+    ) -> HapiResult {
+        // Reading the status must not overwrite the last error.
+        let Some(id) = (unsafe { session.as_ref() }).map(|s| s.id) else {
+            return HapiResult::InvalidArgument;
+        };
+        let state = self.state.lock();
+        let Some(s) = state.sessions.get(&id) else {
+            return HapiResult::InvalidSession;
+        };
+        let value = match status_type {
+            StatusType::CallResult => s.last_error.as_bytes(),
+            StatusType::CookResult => s.cook_result.as_bytes(),
+            StatusType::CookState => s.cook_state().as_bytes(),
+            _ => &[],
+        };
+        match unsafe { copy_string(string_value, length, value) } {
+            Ok(()) => HapiResult::Success,
+            Err(Fail(code, _)) => code,
+        }
+    }
+
+    unsafe fn HAPI_ClearConnectionError(&self) -> HapiResult {
+        self.state.lock().connection_error.clear();
+        HapiResult::Success
+    }
+
+    unsafe fn HAPI_GetConnectionErrorLength(&self, buffer_length: *mut c_int) -> HapiResult {
+        let len = self.state.lock().connection_error.len();
+        match unsafe { out(buffer_length, if len > 0 { len as i32 + 1 } else { 0 }) } {
+            Ok(()) => HapiResult::Success,
+            Err(Fail(code, _)) => code,
+        }
+    }
+
+    unsafe fn HAPI_GetConnectionError(
+        &self,
+        string_value: *mut c_char,
+        length: c_int,
+        clear: HAPI_Bool,
+    ) -> HapiResult {
+        let mut state = self.state.lock();
+        let result =
+            unsafe { copy_string(string_value, length, state.connection_error.as_bytes()) };
+        if clear != 0 {
+            state.connection_error.clear();
+        }
+        match result {
+            Ok(()) => HapiResult::Success,
+            Err(Fail(code, _)) => code,
+        }
+    }
+
+    unsafe fn HAPI_GetCookingTotalCount(
+        &self,
+        session: *const HAPI_Session,
+        count: *mut c_int,
+    ) -> HapiResult {
+        let total = self.cook_polls as i32;
+        self.call(session, |s| unsafe {
+            out(count, if s.threaded { total } else { 0 })
+        })
+    }
+
+    unsafe fn HAPI_GetCookingCurrentCount(
+        &self,
+        session: *const HAPI_Session,
+        count: *mut c_int,
+    ) -> HapiResult {
+        let total = self.cook_polls;
+        self.call(session, |s| unsafe {
+            let current = if s.threaded {
+                total.saturating_sub(s.pending_cook_polls)
+            } else {
+                0
+            };
+            out(count, current as i32)
+        })
+    }
+
+    unsafe fn HAPI_Interrupt(&self, session: *const HAPI_Session) -> HapiResult {
+        self.call(session, |s| {
+            if s.pending_cook_polls > 0 {
+                s.pending_cook_polls = 0;
+                s.cook_result = "Cook interrupted".to_string();
+            }
+            Ok(())
+        })
+    }
+
+    // Strings
+
+    unsafe fn HAPI_GetStringBufLength(
+        &self,
+        session: *const HAPI_Session,
+        string_handle: HAPI_StringHandle,
+        buffer_length: *mut c_int,
+    ) -> HapiResult {
+        self.call_with(session, false, |s| unsafe {
+            let len = s.strings.get(string_handle)?.len();
+            out(buffer_length, len as i32 + 1)
+        })
+    }
+
+    unsafe fn HAPI_GetString(
+        &self,
+        session: *const HAPI_Session,
+        string_handle: HAPI_StringHandle,
+        string_value: *mut c_char,
+        length: c_int,
+    ) -> HapiResult {
+        self.call_with(session, false, |s| unsafe {
+            copy_string(
+                string_value,
+                length,
+                s.strings.get(string_handle)?.as_bytes(),
+            )
+        })
+    }
+
+    unsafe fn HAPI_SetCustomString(
+        &self,
+        session: *const HAPI_Session,
+        string_value: *const c_char,
+        handle_value: *mut HAPI_StringHandle,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let handle = s.strings.intern(read_str(string_value)?);
+            out(handle_value, handle)
+        })
+    }
+
+    unsafe fn HAPI_RemoveCustomString(
+        &self,
+        session: *const HAPI_Session,
+        string_handle: HAPI_StringHandle,
+    ) -> HapiResult {
+        self.call(session, |s| s.strings.get(string_handle).map(|_| ()))
+    }
+
+    unsafe fn HAPI_GetStringBatchSize(
+        &self,
+        session: *const HAPI_Session,
+        string_handle_array: *const c_int,
+        string_handle_count: c_int,
+        string_buffer_size: *mut c_int,
+    ) -> HapiResult {
+        self.call_with(session, false, |s| unsafe {
+            let handles = slice(string_handle_array, string_handle_count)?;
+            let size = s.batch(handles)?;
+            out(string_buffer_size, size)
+        })
+    }
+
+    unsafe fn HAPI_GetStringBatch(
+        &self,
+        session: *const HAPI_Session,
+        char_buffer: *mut c_char,
+        char_array_length: c_int,
+    ) -> HapiResult {
+        self.call_with(session, false, |s| unsafe {
+            let buffer = slice_mut(char_buffer as *mut u8, char_array_length)?;
+            let n = buffer.len().min(s.string_batch.len());
+            buffer[..n].copy_from_slice(&s.string_batch[..n]);
+            Ok(())
+        })
+    }
+
+    // Caches
+
+    unsafe fn HAPI_GetActiveCacheCount(
+        &self,
+        session: *const HAPI_Session,
+        active_cache_count: *mut c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            out(active_cache_count, s.caches.len() as c_int)
+        })
+    }
+
+    unsafe fn HAPI_GetActiveCacheNames(
+        &self,
+        session: *const HAPI_Session,
+        cache_names_array: *mut HAPI_StringHandle,
+        active_cache_count: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let names: Vec<String> = s.caches.keys().cloned().collect();
+            let range = range(0, active_cache_count, 1, names.len())?;
+            let out = slice_mut(cache_names_array, active_cache_count)?;
+            for (handle, name) in out.iter_mut().zip(&names[range]) {
+                *handle = s.strings.intern(name);
+            }
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_GetCacheProperty(
+        &self,
+        session: *const HAPI_Session,
+        cache_name: *const c_char,
+        cache_property: CacheProperty,
+        property_value: *mut c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let name = read_str(cache_name)?;
+            let cache = s
+                .caches
+                .get(name)
+                .ok_or_else(|| invalid(format!("Unknown cache: {name}")))?;
+            let value = match cache_property {
+                CacheProperty::CachepropCurrent => cache.current,
+                CacheProperty::HasMin => cache.min.is_some() as c_int,
+                CacheProperty::CachepropMin => cache.min.unwrap_or(0),
+                CacheProperty::HasMax => cache.max.is_some() as c_int,
+                CacheProperty::CachepropMax => cache.max.unwrap_or(0),
+                CacheProperty::CullLevel => cache.cull_level,
+            };
+            out(property_value, value)
+        })
+    }
+
+    unsafe fn HAPI_SetCacheProperty(
+        &self,
+        session: *const HAPI_Session,
+        cache_name: *const c_char,
+        cache_property: CacheProperty,
+        property_value: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| {
+            let name = unsafe { read_str(cache_name)? };
+            let cache = s
+                .caches
+                .get_mut(name)
+                .ok_or_else(|| invalid(format!("Unknown cache: {name}")))?;
+            match cache_property {
+                // Setting the current size reduces the cache to that size, 0 clears it.
+                CacheProperty::CachepropCurrent => {
+                    cache.current = cache.current.min(property_value.max(0))
+                }
+                CacheProperty::CachepropMin => cache.min = Some(property_value),
+                CacheProperty::CachepropMax => {
+                    cache.max = Some(property_value);
+                    cache.current = cache.current.min(property_value);
+                }
+                CacheProperty::CullLevel => cache.cull_level = property_value,
+                CacheProperty::HasMin | CacheProperty::HasMax => {
+                    return Err(invalid("Cache property is read-only"));
+                }
+            }
+            Ok(())
+        })
+    }
+
+    // Time
+
+    unsafe fn HAPI_GetTime(&self, session: *const HAPI_Session, time: *mut f64) -> HapiResult {
+        self.call(session, |s| unsafe { out(time, s.time) })
+    }
+
+    unsafe fn HAPI_SetTime(&self, session: *const HAPI_Session, time: f64) -> HapiResult {
+        self.call(session, |s| {
+            s.time = time;
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_GetTimelineOptions(
+        &self,
+        session: *const HAPI_Session,
+        timeline_options: *mut HAPI_TimelineOptions,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe { out(timeline_options, s.timeline) })
+    }
+
+    unsafe fn HAPI_SetTimelineOptions(
+        &self,
+        session: *const HAPI_Session,
+        timeline_options: *const HAPI_TimelineOptions,
+    ) -> HapiResult {
+        self.call(session, |s| {
+            s.timeline = *unsafe { timeline_options.as_ref() }
+                .ok_or_else(|| invalid("Timeline options are null"))?;
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_GetCompositorOptions(
+        &self,
+        session: *const HAPI_Session,
+        compositor_options: *mut HAPI_CompositorOptions,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            out(compositor_options, s.compositor)
+        })
+    }
+
+    unsafe fn HAPI_SetCompositorOptions(
+        &self,
+        session: *const HAPI_Session,
+        compositor_options: *const HAPI_CompositorOptions,
+    ) -> HapiResult {
+        self.call(session, |s| {
+            s.compositor = *unsafe { compositor_options.as_ref() }
+                .ok_or_else(|| invalid("Compositor options are null"))?;
+            Ok(())
+        })
+    }
+
+    // Hip files and asset libraries
+
+    unsafe fn HAPI_SaveHIPFile(
+        &self,
+        session: *const HAPI_Session,
+        file_path: *const c_char,
+        _lock_nodes: HAPI_Bool,
+    ) -> HapiResult {
+        self.call_with_scenes(session, true, |s, scenes| {
+            let path = unsafe { read_str(file_path)? };
+            scenes.push(Scene {
+                nodes: s.nodes.clone(),
+                next_node_id: s.next_node_id,
+            });
+            std::fs::write(path, format!("{HIP_HEADER} {}", scenes.len() - 1))
+                .map_err(|e| Fail(HapiResult::Failure, format!("Could not save {path}: {e}")))
+        })
+    }
+
+    unsafe fn HAPI_LoadHIPFile(
+        &self,
+        session: *const HAPI_Session,
+        file_name: *const c_char,
+        _cook_on_load: HAPI_Bool,
+    ) -> HapiResult {
+        self.call_with_scenes(session, true, |s, scenes| {
+            let path = unsafe { read_str(file_name)? };
+            let cant_load = || Fail(HapiResult::CantLoadfile, format!("Could not load {path}"));
+            let scene = std::fs::read_to_string(path)
+                .ok()
+                .and_then(|text| {
+                    let index = text
+                        .strip_prefix(HIP_HEADER)?
+                        .trim()
+                        .parse::<usize>()
+                        .ok()?;
+                    scenes.get(index)
+                })
+                .ok_or_else(cant_load)?;
+            s.nodes = scene.nodes.clone();
+            s.next_node_id = scene.next_node_id;
+            s.composed_children.clear();
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_LoadAssetLibraryFromFile(
+        &self,
+        session: *const HAPI_Session,
+        file_path: *const c_char,
+        _allow_overwrite: HAPI_Bool,
+        library_id: *mut HAPI_AssetLibraryId,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let path = read_str(file_path)?;
+            let id = match s.libraries.iter().position(|library| library == path) {
+                Some(id) => id,
+                None => {
+                    s.libraries.push(path.to_string());
+                    s.libraries.len() - 1
+                }
+            };
+            out(library_id, id as HAPI_AssetLibraryId)
+        })
+    }
+
+    unsafe fn HAPI_GetLoadedAssetLibraryCount(
+        &self,
+        session: *const HAPI_Session,
+        count: *mut c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe { out(count, s.libraries.len() as i32) })
+    }
+
+    unsafe fn HAPI_GetAssetLibraryIds(
+        &self,
+        session: *const HAPI_Session,
+        asset_library_ids_array: *mut HAPI_AssetLibraryId,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let range = range(start, length, 1, s.libraries.len())?;
+            let ids = slice_mut(asset_library_ids_array, length)?;
+            for (id, library) in ids.iter_mut().zip(range) {
+                *id = library as HAPI_AssetLibraryId;
+            }
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_GetAssetLibraryFilePath(
+        &self,
+        session: *const HAPI_Session,
+        asset_library_id: HAPI_AssetLibraryId,
+        file_path_sh: *mut HAPI_StringHandle,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let path = usize::try_from(asset_library_id)
+                .ok()
+                .and_then(|id| s.libraries.get(id))
+                .ok_or_else(|| invalid(format!("Invalid library id: {asset_library_id}")))?;
+            out(file_path_sh, s.strings.intern(path))
+        })
+    }
+
+    // Nodes
+
+    unsafe fn HAPI_IsNodeValid(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        unique_node_id: c_int,
+        answer: *mut HAPI_Bool,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let valid = s.nodes.contains_key(&node_id) && node_id == unique_node_id;
+            out(answer, valid as HAPI_Bool)
+        })
+    }
+
+    unsafe fn HAPI_GetAssetInfo(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        asset_info: *mut HAPI_AssetInfo,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            out(asset_info, s.asset_info(node_id)?)
+        })
+    }
+
+    unsafe fn HAPI_GetNodeInfo(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        node_info: *mut HAPI_NodeInfo,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            out(node_info, s.node_info(node_id)?)
+        })
+    }
+
+    unsafe fn HAPI_GetNodePath(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        relative_to_node_id: HAPI_NodeId,
+        path: *mut HAPI_StringHandle,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let mut node_path = s.path(node_id)?;
+            if relative_to_node_id >= 0 {
+                let base = s.path(relative_to_node_id)?;
+                if let Some(rel) = node_path.strip_prefix(&format!("{base}/")) {
+                    node_path = rel.to_string();
+                }
+            }
+            out(path, s.strings.intern(&node_path))
+        })
+    }
+
+    unsafe fn HAPI_GetManagerNodeId(
+        &self,
+        session: *const HAPI_Session,
+        node_type: NodeType,
+        node_id: *mut HAPI_NodeId,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            match s.manager(node_type) {
+                -1 => Err(invalid(format!("No manager node for {node_type:?}"))),
+                id => out(node_id, id),
+            }
+        })
+    }
+
+    unsafe fn HAPI_ComposeChildNodeList(
+        &self,
+        session: *const HAPI_Session,
+        parent_node_id: HAPI_NodeId,
+        node_type_filter: HAPI_NodeTypeBits,
+        node_flags_filter: HAPI_NodeFlagsBits,
+        recursive: HAPI_Bool,
+        count: *mut c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            s.node(parent_node_id)?;
+            let children: Vec<_> = s
+                .descendants(parent_node_id, recursive != 0)
+                .into_iter()
+                .filter(|id| s.matches_filter(*id, node_type_filter, node_flags_filter))
+                .collect();
+            out(count, children.len() as i32)?;
+            s.composed_children.insert(parent_node_id, children);
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_GetComposedChildNodeList(
+        &self,
+        session: *const HAPI_Session,
+        parent_node_id: HAPI_NodeId,
+        child_node_ids_array: *mut HAPI_NodeId,
+        count: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let children = s
+                .composed_children
+                .remove(&parent_node_id)
+                .ok_or_else(|| invalid("Child node list was not composed"))?;
+            let out = slice_mut(child_node_ids_array, count)?;
+            let n = out.len().min(children.len());
+            out[..n].copy_from_slice(&children[..n]);
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_CreateNode(
+        &self,
+        session: *const HAPI_Session,
+        parent_node_id: HAPI_NodeId,
+        operator_name: *const c_char,
+        node_label: *const c_char,
+        cook_on_creation: HAPI_Bool,
+        new_node_id: *mut HAPI_NodeId,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let operator = read_str(operator_name)?;
+            let label = opt_str(node_label)?;
+            let id = self.create_node(s, parent_node_id, operator, label)?;
+            if cook_on_creation != 0 {
+                s.cook(id, &mut HashSet::new())?;
+            }
+            out(new_node_id, id)
+        })
+    }
+
+    unsafe fn HAPI_CreateInputNode(
+        &self,
+        session: *const HAPI_Session,
+        parent_node_id: HAPI_NodeId,
+        node_id: *mut HAPI_NodeId,
+        name: *const c_char,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let name = opt_str(name)?.unwrap_or("input");
+            let id = self.create_input_node(s, parent_node_id, name)?;
+            out(node_id, id)
+        })
+    }
+
+    unsafe fn HAPI_CookNode(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        _cook_options: *const HAPI_CookOptions,
+    ) -> HapiResult {
+        self.call(session, |s| {
+            s.cook_result.clear();
+            if s.threaded {
+                s.pending_cook_polls = self.cook_polls;
+            }
+            s.cook(node_id, &mut HashSet::new())
+        })
+    }
+
+    unsafe fn HAPI_DeleteNode(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+    ) -> HapiResult {
+        self.call(session, |s| {
+            s.node(node_id)?;
+            if s.is_manager(node_id) {
+                return Err(invalid("Manager nodes can't be deleted"));
+            }
+            s.delete(node_id);
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_RenameNode(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        new_name: *const c_char,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let name = read_str(new_name)?;
+            let parent = s.node(node_id)?.parent;
+            let name = s.unique_name(parent, name);
+            s.node_mut(node_id)?.name = name;
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_ConnectNodeInput(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        input_index: c_int,
+        node_id_to_connect: HAPI_NodeId,
+        _output_index: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| {
+            s.node(node_id_to_connect)?;
+            if node_id == node_id_to_connect {
+                return Err(invalid("Can't connect a node to itself"));
+            }
+            let node = s.node_mut(node_id)?;
+            let input = node
+                .inputs
+                .get_mut(input_index as usize)
+                .ok_or_else(|| invalid(format!("Invalid input index: {input_index}")))?;
+            *input = node_id_to_connect;
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_DisconnectNodeInput(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        input_index: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| {
+            let node = s.node_mut(node_id)?;
+            let input = node
+                .inputs
+                .get_mut(input_index as usize)
+                .ok_or_else(|| invalid(format!("Invalid input index: {input_index}")))?;
+            *input = -1;
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_QueryNodeInput(
+        &self,
+        session: *const HAPI_Session,
+        node_to_query: HAPI_NodeId,
+        input_index: c_int,
+        connected_node_id: *mut HAPI_NodeId,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let node = s.node(node_to_query)?;
+            let input = node
+                .inputs
+                .get(input_index as usize)
+                .ok_or_else(|| invalid(format!("Invalid input index: {input_index}")))?;
+            out(connected_node_id, *input)
+        })
+    }
+
+    unsafe fn HAPI_QueryNodeOutputConnectedCount(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        output_idx: c_int,
+        _into_subnets: HAPI_Bool,
+        _through_dots: HAPI_Bool,
+        connected_count: *mut c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let connected = s.output_connections(node_id, output_idx)?;
+            out(connected_count, connected.len() as c_int)
+        })
+    }
+
+    unsafe fn HAPI_QueryNodeOutputConnectedNodes(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        output_idx: c_int,
+        _into_subnets: HAPI_Bool,
+        _through_dots: HAPI_Bool,
+        connected_node_ids_array: *mut HAPI_NodeId,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let connected = s.output_connections(node_id, output_idx)?;
+            let range = range(start, length, 1, connected.len())?;
+            slice_mut(connected_node_ids_array, length)?.copy_from_slice(&connected[range]);
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_GetNodeInputName(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        input_idx: c_int,
+        name: *mut HAPI_StringHandle,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let node = s.node(node_id)?;
+            if input_idx < 0 || input_idx as usize >= node.inputs.len() {
+                return Err(invalid(format!("Invalid input index: {input_idx}")));
+            }
+            out(name, s.strings.intern(&format!("Input {}", input_idx + 1)))
+        })
+    }
+
+    unsafe fn HAPI_GetNodeFromPath(
+        &self,
+        session: *const HAPI_Session,
+        parent_node_id: HAPI_NodeId,
+        path: *const c_char,
+        node_id: *mut HAPI_NodeId,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let path = read_str(path)?;
+            let id = s
+                .find_path(parent_node_id, path)
+                .ok_or_else(|| invalid(format!("Node not found: {path}")))?;
+            out(node_id, id)
+        })
+    }
+
+    unsafe fn HAPI_GetOutputNodeId(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        output: c_int,
+        output_node_id: *mut HAPI_NodeId,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let id = s.geometry_node(node_id)?;
+            match output {
+                0 => out(output_node_id, id),
+                _ => Err(invalid(format!("Invalid output index: {output}"))),
+            }
+        })
+    }
+
+    unsafe fn HAPI_SetNodeDisplay(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        onOff: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| {
+            let parent = s.node(node_id)?.parent;
+            let parent = s.node_mut(parent)?;
+            if onOff != 0 {
+                parent.display = Some(node_id);
+            }
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_GetTotalCookCount(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        node_type_filter: HAPI_NodeTypeBits,
+        node_flags_filter: HAPI_NodeFlagsBits,
+        recursive: HAPI_Bool,
+        count: *mut c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let mut total = s.node(node_id)?.cook_count;
+            for child in s.descendants(node_id, recursive != 0) {
+                if s.matches_filter(child, node_type_filter, node_flags_filter) {
+                    total += s.node(child)?.cook_count;
+                }
+            }
+            out(count, total)
+        })
+    }
+
+    unsafe fn HAPI_GetNodeCookResultLength(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        verbosity: StatusVerbosity,
+        buffer_length: *mut c_int,
+    ) -> HapiResult {
+        // Errors only, plus warnings, or everything.
+        let shown: &[&str] = match verbosity {
+            StatusVerbosity::Errors => &["Error"],
+            StatusVerbosity::Warnings => &["Error", "Warning"],
+            _ => &["Error", "Warning", "Message"],
+        };
+        self.call(session, |s| unsafe {
+            s.composed_cook_result = s
+                .node(node_id)?
+                .cook_errors
+                .lines()
+                .filter(|line| shown.iter().any(|prefix| line.starts_with(prefix)))
+                .collect::<Vec<_>>()
+                .join("\n");
+            out(buffer_length, s.composed_cook_result.len() as i32 + 1)
+        })
+    }
+
+    unsafe fn HAPI_GetNodeCookResult(
+        &self,
+        session: *const HAPI_Session,
+        string_value: *mut c_char,
+        length: c_int,
+    ) -> HapiResult {
+        // The caller may size its buffer without the terminator, so only the message is copied.
+        self.call(session, |s| unsafe {
+            let buffer = slice_mut(string_value as *mut u8, length - 1)?;
+            let message = s.composed_cook_result.as_bytes();
+            let n = buffer.len().min(message.len());
+            buffer[..n].copy_from_slice(&message[..n]);
+            Ok(())
+        })
+    }
+
+    // Parameters
+
+    unsafe fn HAPI_GetParameters(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        parm_infos_array: *mut HAPI_ParmInfo,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let total = s.node(node_id)?.parms.len();
+            let range = range(start, length, 1, total)?;
+            let out = slice_mut(parm_infos_array, length)?;
+            for (info, index) in out.iter_mut().zip(range) {
+                *info = s.parm_info(node_id, index)?;
+            }
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_GetParmInfo(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        parm_id: HAPI_ParmId,
+        parm_info: *mut HAPI_ParmInfo,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            if parm_id < 0 {
+                return Err(invalid(format!("Invalid parameter id: {parm_id}")));
+            }
+            out(parm_info, s.parm_info(node_id, parm_id as usize)?)
+        })
+    }
+
+    unsafe fn HAPI_GetParmIdFromName(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        parm_name: *const c_char,
+        parm_id: *mut HAPI_ParmId,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let name = read_str(parm_name)?;
+            let id = s
+                .node(node_id)?
+                .parm(name)
+                .map(|(i, _)| i as i32)
+                .unwrap_or(-1);
+            out(parm_id, id)
+        })
+    }
+
+    unsafe fn HAPI_GetParmInfoFromName(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        parm_name: *const c_char,
+        parm_info: *mut HAPI_ParmInfo,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let (index, _) = s.node(node_id)?.parm(read_str(parm_name)?)?;
+            out(parm_info, s.parm_info(node_id, index)?)
+        })
+    }
+
+    unsafe fn HAPI_GetParmWithTag(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        _tag_name: *const c_char,
+        parm_id: *mut HAPI_ParmId,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            s.node(node_id)?;
+            out(parm_id, -1)
+        })
+    }
+
+    unsafe fn HAPI_ParmHasExpression(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        parm_name: *const c_char,
+        index: c_int,
+        has_expression: *mut HAPI_Bool,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let (_, parm) = s.node(node_id)?.parm(read_str(parm_name)?)?;
+            out(
+                has_expression,
+                parm.expressions.contains_key(&index) as HAPI_Bool,
+            )
+        })
+    }
+
+    unsafe fn HAPI_GetParmExpression(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        parm_name: *const c_char,
+        index: c_int,
+        value: *mut HAPI_StringHandle,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let (_, parm) = s.node(node_id)?.parm(read_str(parm_name)?)?;
+            let expression = parm.expressions.get(&index).cloned().unwrap_or_default();
+            out(value, s.strings.intern(&expression))
+        })
+    }
+
+    unsafe fn HAPI_SetParmExpression(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        value: *const c_char,
+        parm_id: HAPI_ParmId,
+        index: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| {
+            let expression = unsafe { read_str(value)? };
+            let parm = s.parm_by_id_mut(node_id, parm_id)?;
+            if index < 0 || index >= parm.size() {
+                return Err(invalid(format!("Invalid index {index} for {}", parm.name)));
+            }
+            parm.expressions.insert(index, expression.to_string());
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_RemoveParmExpression(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        parm_id: HAPI_ParmId,
+        index: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| {
+            s.parm_by_id_mut(node_id, parm_id)?
+                .expressions
+                .remove(&index);
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_GetParmIntValue(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        parm_name: *const c_char,
+        index: c_int,
+        value: *mut c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let name = read_str(parm_name)?;
+            match &s.node(node_id)?.parm(name)?.1.value {
+                ParmValue::Int(v) => out(
+                    value,
+                    *v.get(index as usize)
+                        .ok_or_else(|| invalid(format!("Invalid index {index} for {name}")))?,
+                ),
+                _ => Err(invalid(format!("Parameter {name} is not an int parameter"))),
+            }
+        })
+    }
+
+    unsafe fn HAPI_GetParmIntValues(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        values_array: *mut c_int,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let values = s.node_mut(node_id)?.int_values();
+            let range = range(start, length, 1, values.len())?;
+            let out = slice_mut(values_array, length)?;
+            for (o, v) in out.iter_mut().zip(&values[range]) {
+                *o = **v;
+            }
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_GetParmFloatValue(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        parm_name: *const c_char,
+        index: c_int,
+        value: *mut f32,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let name = read_str(parm_name)?;
+            match &s.node(node_id)?.parm(name)?.1.value {
+                ParmValue::Float(v) => out(
+                    value,
+                    *v.get(index as usize)
+                        .ok_or_else(|| invalid(format!("Invalid index {index} for {name}")))?,
+                ),
+                _ => Err(invalid(format!(
+                    "Parameter {name} is not a float parameter"
+                ))),
+            }
+        })
+    }
+
+    unsafe fn HAPI_GetParmFloatValues(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        values_array: *mut f32,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let values = s.node_mut(node_id)?.float_values();
+            let range = range(start, length, 1, values.len())?;
+            let out = slice_mut(values_array, length)?;
+            for (o, v) in out.iter_mut().zip(&values[range]) {
+                *o = **v;
+            }
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_GetParmStringValue(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        parm_name: *const c_char,
+        index: c_int,
+        _evaluate: HAPI_Bool,
+        value: *mut HAPI_StringHandle,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let name = read_str(parm_name)?;
+            let text = match &s.node(node_id)?.parm(name)?.1.value {
+                ParmValue::String(v) => v
+                    .get(index as usize)
+                    .ok_or_else(|| invalid(format!("Invalid index {index} for {name}")))?
+                    .clone(),
+                _ => {
+                    return Err(invalid(format!(
+                        "Parameter {name} is not a string parameter"
+                    )));
+                }
+            };
+            out(value, s.strings.intern(&text))
+        })
+    }
+
+    unsafe fn HAPI_GetParmStringValues(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        _evaluate: HAPI_Bool,
+        values_array: *mut HAPI_StringHandle,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let values: Vec<String> = s
+                .node(node_id)?
+                .string_values()
+                .into_iter()
+                .map(str::to_string)
+                .collect();
+            let range = range(start, length, 1, values.len())?;
+            let out = slice_mut(values_array, length)?;
+            for (o, v) in out.iter_mut().zip(&values[range]) {
+                *o = s.strings.intern(v);
+            }
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_SetParmIntValue(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        parm_name: *const c_char,
+        index: c_int,
+        value: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let name = read_str(parm_name)?;
+            match &mut s.node_mut(node_id)?.parm_mut(name)?.value {
+                ParmValue::Int(v) => {
+                    *v.get_mut(index as usize)
+                        .ok_or_else(|| invalid(format!("Invalid index {index} for {name}")))? =
+                        value;
+                    Ok(())
+                }
+                _ => Err(Fail(
+                    HapiResult::ParmSetFailed,
+                    format!("Parameter {name} is not an int parameter"),
+                )),
+            }
+        })
+    }
+
+    unsafe fn HAPI_SetParmIntValues(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        values_array: *const c_int,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let input = slice(values_array, length)?;
+            let mut values = s.node_mut(node_id)?.int_values();
+            let range = range(start, length, 1, values.len())?;
+            for (v, i) in values[range].iter_mut().zip(input) {
+                **v = *i;
+            }
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_SetParmFloatValue(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        parm_name: *const c_char,
+        index: c_int,
+        value: f32,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let name = read_str(parm_name)?;
+            match &mut s.node_mut(node_id)?.parm_mut(name)?.value {
+                ParmValue::Float(v) => {
+                    *v.get_mut(index as usize)
+                        .ok_or_else(|| invalid(format!("Invalid index {index} for {name}")))? =
+                        value;
+                    Ok(())
+                }
+                _ => Err(Fail(
+                    HapiResult::ParmSetFailed,
+                    format!("Parameter {name} is not a float parameter"),
+                )),
+            }
+        })
+    }
+
+    unsafe fn HAPI_SetParmFloatValues(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        values_array: *const f32,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let input = slice(values_array, length)?;
+            let mut values = s.node_mut(node_id)?.float_values();
+            let range = range(start, length, 1, values.len())?;
+            for (v, i) in values[range].iter_mut().zip(input) {
+                **v = *i;
+            }
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_SetParmStringValue(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        value: *const c_char,
+        parm_id: HAPI_ParmId,
+        index: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let text = read_str(value)?.to_string();
+            let parm = s
+                .node_mut(node_id)?
+                .parms
+                .get_mut(parm_id as usize)
+                .ok_or_else(|| invalid(format!("Invalid parameter id: {parm_id}")))?;
+            match &mut parm.value {
+                ParmValue::String(v) => {
+                    *v.get_mut(index as usize)
+                        .ok_or_else(|| invalid(format!("Invalid index {index}")))? = text;
+                    Ok(())
+                }
+                _ => Err(Fail(
+                    HapiResult::ParmSetFailed,
+                    format!("Parameter {} is not a string parameter", parm.name),
+                )),
+            }
+        })
+    }
+
+    // Geometry
+
+    unsafe fn HAPI_GetDisplayGeoInfo(
+        &self,
+        session: *const HAPI_Session,
+        object_node_id: HAPI_NodeId,
+        geo_info: *mut HAPI_GeoInfo,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            out(geo_info, s.geo_info(object_node_id)?)
+        })
+    }
+
+    unsafe fn HAPI_GetGeoInfo(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        geo_info: *mut HAPI_GeoInfo,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe { out(geo_info, s.geo_info(node_id)?) })
+    }
+
+    unsafe fn HAPI_GetOutputGeoCount(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        count: *mut c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            out(count, s.output_geos(node_id)?.len() as c_int)
+        })
+    }
+
+    unsafe fn HAPI_GetOutputGeoInfos(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        geo_infos_array: *mut HAPI_GeoInfo,
+        count: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| {
+            let outputs = s.output_geos(node_id)?;
+            let infos = unsafe { slice_mut(geo_infos_array, count)? };
+            if infos.len() != outputs.len() {
+                return Err(invalid("Output geo count mismatch"));
+            }
+            for (info, id) in infos.iter_mut().zip(outputs) {
+                *info = s.geo_info(id)?;
+            }
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_SaveGeoToFile(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        file_name: *const c_char,
+    ) -> HapiResult {
+        self.call(session, |s| {
+            let path = unsafe { read_str(file_name)? };
+            std::fs::write(path, s.geo_bytes(node_id)?)
+                .map_err(|e| Fail(HapiResult::Failure, format!("Could not save {path}: {e}")))
+        })
+    }
+
+    unsafe fn HAPI_GetGeoSize(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        _format: *const c_char,
+        size: *mut c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            out(size, s.geo_bytes(node_id)?.len() as c_int)
+        })
+    }
+
+    unsafe fn HAPI_SaveGeoToMemory(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        buffer: *mut c_char,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| {
+            let bytes = s.geo_bytes(node_id)?;
+            let buffer = unsafe { slice_mut(buffer as *mut u8, length)? };
+            if buffer.len() != bytes.len() {
+                return Err(invalid("Buffer length does not match HAPI_GetGeoSize"));
+            }
+            buffer.copy_from_slice(&bytes);
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_GetPartInfo(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        part_info: *mut HAPI_PartInfo,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let part = s.node(s.geometry_node(node_id)?)?.part(part_id)?.clone();
+            out(part_info, part.info(part_id, &mut s.strings))
+        })
+    }
+
+    unsafe fn HAPI_SetPartInfo(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        part_info: *const HAPI_PartInfo,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let info = *part_info
+                .as_ref()
+                .ok_or_else(|| invalid("Part info is null"))?;
+            let name = s.strings.get(info.nameSH).unwrap_or_default().to_string();
+            let node = s.node_mut(node_id)?;
+            if !node.editable {
+                return Err(invalid(format!("Node {} is not editable", node.name)));
+            }
+            let parts = &mut node.staged.parts;
+            if part_id < 0 || part_id as usize > parts.len() {
+                return Err(invalid(format!("Invalid part id: {part_id}")));
+            }
+            if part_id as usize == parts.len() {
+                parts.push(Part::new(&name));
+            }
+            let part = &mut parts[part_id as usize];
+            part.name = name;
+            part.part_type = info.type_;
+            part.point_count = info.pointCount;
+            part.face_count = info.faceCount;
+            part.vertex_count = info.vertexCount;
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_GetFaceCounts(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        face_counts_array: *mut c_int,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let part = s.node(s.geometry_node(node_id)?)?.part(part_id)?;
+            let range = range(start, length, 1, part.face_counts.len())?;
+            slice_mut(face_counts_array, length)?.copy_from_slice(&part.face_counts[range]);
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_SetFaceCounts(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        face_counts_array: *const c_int,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let input = slice(face_counts_array, length)?;
+            let part = s.node_mut(node_id)?.staged_part(part_id)?;
+            let end = (start + length) as usize;
+            if part.face_counts.len() < end {
+                part.face_counts.resize(end, 0);
+            }
+            part.face_counts[start as usize..end].copy_from_slice(input);
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_GetVertexList(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        vertex_list_array: *mut c_int,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let part = s.node(s.geometry_node(node_id)?)?.part(part_id)?;
+            let range = range(start, length, 1, part.vertices.len())?;
+            slice_mut(vertex_list_array, length)?.copy_from_slice(&part.vertices[range]);
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_SetVertexList(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        vertex_list_array: *const c_int,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let input = slice(vertex_list_array, length)?;
+            let part = s.node_mut(node_id)?.staged_part(part_id)?;
+            let end = (start + length) as usize;
+            if part.vertices.len() < end {
+                part.vertices.resize(end, 0);
+            }
+            part.vertices[start as usize..end].copy_from_slice(input);
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_GetAttributeInfo(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        name: *const c_char,
+        owner: AttributeOwner,
+        attr_info: *mut HAPI_AttributeInfo,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let name = read_str(name)?;
+            let part = s.node(s.geometry_node(node_id)?)?.part(part_id)?;
+            let info = match part.attribute(name, owner) {
+                Some(attr) => attr.info(),
+                None => self.HAPI_AttributeInfo_Create(),
+            };
+            out(attr_info, info)
+        })
+    }
+
+    unsafe fn HAPI_GetAttributeNames(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        owner: AttributeOwner,
+        attribute_names_array: *mut HAPI_StringHandle,
+        count: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let names: Vec<String> = s
+                .node(s.geometry_node(node_id)?)?
+                .part(part_id)?
+                .attributes
+                .iter()
+                .filter(|a| a.owner == owner)
+                .map(|a| a.name.clone())
+                .collect();
+            let out = slice_mut(attribute_names_array, count)?;
+            for (handle, name) in out.iter_mut().zip(&names) {
+                *handle = s.strings.intern(name);
+            }
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_AddAttribute(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        name: *const c_char,
+        attr_info: *const HAPI_AttributeInfo,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let name = read_str(name)?;
+            let info = *attr_info
+                .as_ref()
+                .ok_or_else(|| invalid("Attribute info is null"))?;
+            let part = s.node_mut(node_id)?.staged_part(part_id)?;
+            let count = match info.count {
+                c if c > 0 => c,
+                _ => part.element_count(info.owner),
+            };
+            let tuple_size = info.tupleSize.max(1);
+            let values = AttribValues::new(info.storage, (count * tuple_size) as usize)
+                .ok_or_else(|| {
+                    invalid(format!("Unsupported attribute storage: {:?}", info.storage))
+                })?;
+            part.attributes
+                .retain(|a| !(a.name == name && a.owner == info.owner));
+            part.attributes.push(Attrib {
+                name: name.to_string(),
+                owner: info.owner,
+                tuple_size,
+                count,
+                type_info: info.typeInfo,
+                values,
+            });
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_DeleteAttribute(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        name: *const c_char,
+        attr_info: *const HAPI_AttributeInfo,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let name = read_str(name)?;
+            let owner = info_owner(attr_info)?;
+            let part = s.node_mut(node_id)?.staged_part(part_id)?;
+            part.attributes
+                .retain(|a| !(a.name == name && a.owner == owner));
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_SetAttributeIntData(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        name: *const c_char,
+        attr_info: *const HAPI_AttributeInfo,
+        data_array: *const c_int,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let (name, owner) = (read_str(name)?, info_owner(attr_info)?);
+            let tuple = (*attr_info).tupleSize.max(1);
+            let data = slice(data_array, length * tuple)?;
+            set_numeric(s, node_id, part_id, name, owner, data, start, length)
+        })
+    }
+
+    unsafe fn HAPI_SetAttributeInt64Data(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        name: *const c_char,
+        attr_info: *const HAPI_AttributeInfo,
+        data_array: *const HAPI_Int64,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let (name, owner) = (read_str(name)?, info_owner(attr_info)?);
+            let tuple = (*attr_info).tupleSize.max(1);
+            let data = slice(data_array, length * tuple)?;
+            set_numeric(s, node_id, part_id, name, owner, data, start, length)
+        })
+    }
+
+    unsafe fn HAPI_SetAttributeFloatData(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        name: *const c_char,
+        attr_info: *const HAPI_AttributeInfo,
+        data_array: *const f32,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let (name, owner) = (read_str(name)?, info_owner(attr_info)?);
+            let tuple = (*attr_info).tupleSize.max(1);
+            let data = slice(data_array, length * tuple)?;
+            set_numeric(s, node_id, part_id, name, owner, data, start, length)
+        })
+    }
+
+    unsafe fn HAPI_SetAttributeIntDataAsync(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        name: *const c_char,
+        attr_info: *const HAPI_AttributeInfo,
+        data_array: *const c_int,
+        start: c_int,
+        length: c_int,
+        job_id: *mut c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let len = length * (*attr_info).tupleSize.max(1);
+            let data = AsyncData::Int(data_array, len);
+            s.start_set_job(
+                node_id, part_id, name, attr_info, data, start, length, job_id,
+            )
+        })
+    }
+
+    unsafe fn HAPI_SetAttributeFloatDataAsync(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        name: *const c_char,
+        attr_info: *const HAPI_AttributeInfo,
+        data_array: *const f32,
+        start: c_int,
+        length: c_int,
+        job_id: *mut c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let len = length * (*attr_info).tupleSize.max(1);
+            let data = AsyncData::Float(data_array, len);
+            s.start_set_job(
+                node_id, part_id, name, attr_info, data, start, length, job_id,
+            )
+        })
+    }
+
+    unsafe fn HAPI_GetJobStatus(
+        &self,
+        session: *const HAPI_Session,
+        job_id: c_int,
+        job_status: *mut JobStatus,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let job = s
+                .jobs
+                .get_mut(job_id as usize)
+                .ok_or_else(|| invalid(format!("Invalid job id: {job_id}")))?
+                .take();
+            if let Some(job) = job {
+                job.run(s)?;
+            }
+            out(job_status, JobStatus::Idle)
+        })
+    }
+
+    unsafe fn HAPI_SetAttributeFloat64Data(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        name: *const c_char,
+        attr_info: *const HAPI_AttributeInfo,
+        data_array: *const f64,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let (name, owner) = (read_str(name)?, info_owner(attr_info)?);
+            let tuple = (*attr_info).tupleSize.max(1);
+            let data = slice(data_array, length * tuple)?;
+            set_numeric(s, node_id, part_id, name, owner, data, start, length)
+        })
+    }
+
+    unsafe fn HAPI_SetAttributeStringData(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        name: *const c_char,
+        attr_info: *const HAPI_AttributeInfo,
+        data_array: *mut *const c_char,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let (name, owner) = (read_str(name)?, info_owner(attr_info)?);
+            let input = slice(data_array as *const *const c_char, length)?
+                .iter()
+                .map(|ptr| read_str(*ptr).map(str::to_string))
+                .collect::<Outcome<Vec<_>>>()?;
+            let attr = s
+                .node_mut(node_id)?
+                .staged_part(part_id)?
+                .attribute_mut(name, owner)?;
+            let AttribValues::String(values) = &mut attr.values else {
+                return Err(invalid(format!("Attribute {name} storage mismatch")));
+            };
+            let range = range(start, length, 1, values.len())?;
+            for (v, i) in values[range].iter_mut().zip(input) {
+                *v = i;
+            }
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_GetAttributeIntData(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        name: *const c_char,
+        attr_info: *mut HAPI_AttributeInfo,
+        _stride: c_int,
+        data_array: *mut c_int,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let (name, owner) = (read_str(name)?, info_owner(attr_info)?);
+            let node = s.geometry_node(node_id)?;
+            get_numeric(s, node, part_id, name, owner, data_array, start, length)
+        })
+    }
+
+    unsafe fn HAPI_GetAttributeInt64Data(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        name: *const c_char,
+        attr_info: *mut HAPI_AttributeInfo,
+        _stride: c_int,
+        data_array: *mut HAPI_Int64,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let (name, owner) = (read_str(name)?, info_owner(attr_info)?);
+            let node = s.geometry_node(node_id)?;
+            get_numeric(s, node, part_id, name, owner, data_array, start, length)
+        })
+    }
+
+    unsafe fn HAPI_GetAttributeFloatData(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        name: *const c_char,
+        attr_info: *mut HAPI_AttributeInfo,
+        _stride: c_int,
+        data_array: *mut f32,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let (name, owner) = (read_str(name)?, info_owner(attr_info)?);
+            let node = s.geometry_node(node_id)?;
+            get_numeric(s, node, part_id, name, owner, data_array, start, length)
+        })
+    }
+
+    unsafe fn HAPI_GetAttributeFloat64Data(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        name: *const c_char,
+        attr_info: *mut HAPI_AttributeInfo,
+        _stride: c_int,
+        data_array: *mut f64,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let (name, owner) = (read_str(name)?, info_owner(attr_info)?);
+            let node = s.geometry_node(node_id)?;
+            get_numeric(s, node, part_id, name, owner, data_array, start, length)
+        })
+    }
+
+    unsafe fn HAPI_GetAttributeStringData(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+        part_id: HAPI_PartId,
+        name: *const c_char,
+        attr_info: *mut HAPI_AttributeInfo,
+        data_array: *mut HAPI_StringHandle,
+        start: c_int,
+        length: c_int,
+    ) -> HapiResult {
+        self.call(session, |s| unsafe {
+            let (name, owner) = (read_str(name)?, info_owner(attr_info)?);
+            let node = s.geometry_node(node_id)?;
+            let attr = s
+                .node(node)?
+                .part(part_id)?
+                .attribute(name, owner)
+                .ok_or_else(|| invalid(format!("Attribute {name} does not exist")))?;
+            let AttribValues::String(values) = &attr.values else {
+                return Err(invalid(format!("Attribute {name} storage mismatch")));
+            };
+            let range = range(start, length, 1, values.len())?;
+            let values = values[range].to_vec();
+            let out = slice_mut(data_array, length)?;
+            for (handle, value) in out.iter_mut().zip(&values) {
+                *handle = s.strings.intern(value);
+            }
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_CommitGeo(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+    ) -> HapiResult {
+        self.call(session, |s| {
+            let node = s.node_mut(node_id)?;
+            if !node.editable {
+                return Err(invalid(format!("Node {} is not editable", node.name)));
+            }
+            node.committed = node.staged.clone();
+            node.output = Some(node.committed.clone());
+            Ok(())
+        })
+    }
+
+    unsafe fn HAPI_RevertGeo(
+        &self,
+        session: *const HAPI_Session,
+        node_id: HAPI_NodeId,
+    ) -> HapiResult {
+        self.call(session, |s| {
+            let node = s.node_mut(node_id)?;
+            if !node.editable {
+                return Err(invalid(format!("Node {} is not editable", node.name)));
+            }
+            node.staged = GeoData::default();
+            node.committed = GeoData::default();
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,239 @@
+//! Pluggable implementation of the raw Engine API.
+//!
+//! Every call made by this crate (and every function in [`crate::raw`]) is dispatched
+//! through a process-wide [`HapiBackend`]. By default this is `LinkedBackend`, which forwards
+//! to `libHAPIL` linked at build time. With the `dynamic-load` feature (and `link` disabled), the
+//! default is `DynamicBackend`, which loads `libHAPIL` from `$HFS` when the first session is created.
+//! A different backend can be installed once, before the first API call, with [`set_backend`].
+//!
+//! The [`fake`] module provides an in-memory engine which implements enough of the API to
+//! create nodes, set parameters and build input geometry without a Houdini installation:
+//!
+//! ```no_run
+//! use hapi_rs::backend::{set_backend, fake::FakeEngine};
+//! use hapi_rs::session::new_in_process_session;
+//!
+//! set_backend(FakeEngine::new()).unwrap();
+//! let session = new_in_process_session(None).unwrap();
+//! let node = session.create_node("Object/geo").unwrap();
+//! ```
+//!
+//! Any function a backend does not implement logs an error and returns
+//! [`HapiResult::Failure`](crate::raw::HapiResult::Failure), or a zeroed value for functions
+//! which don't return a result code.
+
+use std::sync::OnceLock;
+
+use log::error;
+
+use crate::errors::{HapiError, Result};
+use crate::ffi::hapi_functions;
+use crate::ffi::raw::*;
+
+#[cfg(feature = "dynamic-load")]
+mod dynamic;
+pub mod fake;
+
+#[cfg(feature = "dynamic-load")]
+pub use dynamic::DynamicBackend;
+
+/// Value returned by a [`HapiBackend`] function the backend doesn't implement.
+pub trait Unsupported {
+    fn unsupported() -> Self;
+}
+
+impl Unsupported for () {
+    fn unsupported() -> Self {}
+}
+
+impl Unsupported for HapiResult {
+    fn unsupported() -> Self {
+        HapiResult::Failure
+    }
+}
+
+impl Unsupported for HAPI_Bool {
+    fn unsupported() -> Self {
+        0
+    }
+}
+
+impl Unsupported for std::os::raw::c_int {
+    fn unsupported() -> Self {
+        0
+    }
+}
+
+macro_rules! zeroed_unsupported {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Unsupported for $ty {
+                fn unsupported() -> Self {
+                    // SAFETY: Engine structs are plain C data, all of their enum fields have a zero variant.
+                    unsafe { std::mem::zeroed() }
+                }
+            }
+        )*
+    };
+}
+
+zeroed_unsupported!(
+    HAPI_AssetInfo,
+    HAPI_AttributeInfo,
+    HAPI_CompositorOptions,
+    HAPI_CookOptions,
+    HAPI_CurveInfo,
+    HAPI_GeoInfo,
+    HAPI_HandleBindingInfo,
+    HAPI_HandleInfo,
+    HAPI_ImageFileFormat,
+    HAPI_ImageInfo,
+    HAPI_InputCurveInfo,
+    HAPI_Keyframe,
+    HAPI_MaterialInfo,
+    HAPI_NodeInfo,
+    HAPI_ObjectInfo,
+    HAPI_ParmChoiceInfo,
+    HAPI_ParmInfo,
+    HAPI_PartInfo,
+    HAPI_SessionInfo,
+    HAPI_SessionSyncInfo,
+    HAPI_ThriftServerOptions,
+    HAPI_TimelineOptions,
+    HAPI_Transform,
+    HAPI_TransformEuler,
+    HAPI_Viewport,
+    HAPI_VolumeInfo,
+    HAPI_VolumeTileInfo,
+);
+
+macro_rules! declare_backend {
+    ($(fn $name:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)?;)*) => {
+        /// Implementation of the Engine C API.
+        ///
+        /// Every method mirrors the function of the same name in [`crate::raw`] and has the same
+        /// safety contract: pointers come straight from the caller and must be treated exactly as
+        /// `libHAPIL` would. All methods have a default implementation which calls
+        /// [`HapiBackend::unsupported`], so a backend only overrides what it supports.
+        #[allow(non_snake_case, unused_variables, clippy::too_many_arguments, clippy::missing_safety_doc)]
+        pub trait HapiBackend: Send + Sync {
+            /// Called by every function the backend doesn't implement.
+            fn unsupported(&self, function: &'static str) {
+                error!("{function} is not supported by the current HAPI backend");
+            }
+
+            /// Report an error which prevents the backend from serving any call.
+            /// Checked before a session is created.
+            fn check(&self) -> Result<()> {
+                Ok(())
+            }
+
+            /// Returns `true` if the server process `pid` started by this backend is running.
+            fn is_process_alive(&self, pid: u32) -> bool {
+                crate::server::os_is_process_alive(pid)
+            }
+
+            /// Kill the server process `pid`, returns `true` if it was signalled.
+            fn kill_process(&self, pid: u32) -> bool {
+                crate::server::os_kill_process(pid)
+            }
+
+            $(
+                unsafe fn $name(&self, $($arg: $ty),*) $(-> $ret)? {
+                    self.unsupported(stringify!($name));
+                    Unsupported::unsupported()
+                }
+            )*
+        }
+    };
+}
+
+hapi_functions!(declare_backend);
+
+/// Backend which calls into `libHAPIL` linked at build time.
+#[cfg(feature = "link")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LinkedBackend;
+
+#[cfg(feature = "link")]
+macro_rules! declare_linked_backend {
+    ($(fn $name:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)?;)*) => {
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        impl HapiBackend for LinkedBackend {
+            $(
+                #[inline]
+                unsafe fn $name(&self, $($arg: $ty),*) $(-> $ret)? {
+                    unsafe { crate::ffi::bindings::$name($($arg),*) }
+                }
+            )*
+        }
+    };
+}
+
+#[cfg(feature = "link")]
+hapi_functions!(declare_linked_backend);
+
+/// Placeholder used when no backend is available, every call fails with `reason`.
+#[cfg(not(feature = "link"))]
+struct UnavailableBackend {
+    reason: String,
+}
+
+#[cfg(not(feature = "link"))]
+impl HapiBackend for UnavailableBackend {
+    fn unsupported(&self, function: &'static str) {
+        error!("{function}: {}", self.reason);
+    }
+
+    fn check(&self) -> Result<()> {
+        Err(HapiError::Backend(self.reason.clone()))
+    }
+}
+
+static BACKEND: OnceLock<Box<dyn HapiBackend>> = OnceLock::new();
+
+/// Install the backend used by all subsequent API calls.
+///
+/// Must be called before any other function in this crate, the backend can't be replaced once set.
+pub fn set_backend(backend: impl HapiBackend + 'static) -> Result<()> {
+    BACKEND
+        .set(Box::new(backend))
+        .map_err(|_| HapiError::Internal("HAPI backend is already initialized".to_string()))
+}
+
+/// Returns `true` if a backend has been installed, explicitly or by the first API call.
+pub fn is_backend_set() -> bool {
+    BACKEND.get().is_some()
+}
+
+/// Returns an error if the current backend can't serve API calls.
+pub(crate) fn check() -> Result<()> {
+    current().check()
+}
+
+#[inline]
+pub(crate) fn current() -> &'static dyn HapiBackend {
+    BACKEND.get_or_init(default_backend).as_ref()
+}
+
+fn default_backend() -> Box<dyn HapiBackend> {
+    #[cfg(feature = "link")]
+    {
+        Box::new(LinkedBackend)
+    }
+    #[cfg(all(feature = "dynamic-load", not(feature = "link")))]
+    {
+        match DynamicBackend::discover() {
+            Ok(backend) => Box::new(backend),
+            Err(e) => Box::new(UnavailableBackend {
+                reason: e.to_string(),
+            }),
+        }
+    }
+    #[cfg(not(any(feature = "link", feature = "dynamic-load")))]
+    {
+        Box::new(UnavailableBackend {
+            reason: "no HAPI backend installed. Enable the `link` or `dynamic-load` feature or call `backend::set_backend`".to_string(),
+        })
+    }
+}
@@ -0,0 +1,419 @@
+//! Cook an asset with many parameter sets in parallel.
+//!
+//! A [`Batch`] spreads [`BatchJob`]s over the sessions of a [`SessionPool`]. Each job creates a
+//! fresh asset node, sets its parameters, cooks it and saves the output geometry to a file or
+//! memory. Jobs are independent, a failed job is reported in the [`BatchReport`] and the rest
+//! of the batch keeps running:
+//!
+//! ```no_run
+//! use hapi_rs::batch::{Batch, BatchJob, BatchOutput};
+//! # fn run() -> hapi_rs::Result<()> {
+//! let jobs = (1..=8).map(|i| BatchJob::new().with_parm("size", [i as f32; 3]));
+//! let report = Batch::new("otls/hapi_geo.hda", BatchOutput::files("out/geo_{job}.bgeo"))
+//!     .with_sessions(4)
+//!     .run(jobs)?;
+//! for job in report.failed() {
+//!     eprintln!("Job {} failed: {:?}", job.index, job.result);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::{debug, warn};
+use parking_lot::Mutex;
+
+use crate::asset::AssetLibrary;
+use crate::errors::{ErrorContext, HapiError, Result};
+use crate::geometry::GeoFormat;
+use crate::node::HoudiniNode;
+use crate::parameter::Parameter;
+use crate::server::ServerOptions;
+use crate::session::{CookResult, PooledSession, SessionOptions, SessionPool};
+
+/// Value assigned to a parameter by a [`BatchJob`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchValue {
+    /// Also accepted by float parameters
+    Int(Vec<i32>),
+    Float(Vec<f32>),
+    String(Vec<String>),
+}
+
+impl From<i32> for BatchValue {
+    fn from(value: i32) -> Self {
+        BatchValue::Int(vec![value])
+    }
+}
+
+impl From<f32> for BatchValue {
+    fn from(value: f32) -> Self {
+        BatchValue::Float(vec![value])
+    }
+}
+
+impl From<&str> for BatchValue {
+    fn from(value: &str) -> Self {
+        BatchValue::String(vec![value.to_string()])
+    }
+}
+
+impl From<String> for BatchValue {
+    fn from(value: String) -> Self {
+        BatchValue::String(vec![value])
+    }
+}
+
+impl From<Vec<i32>> for BatchValue {
+    fn from(values: Vec<i32>) -> Self {
+        BatchValue::Int(values)
+    }
+}
+
+impl From<Vec<f32>> for BatchValue {
+    fn from(values: Vec<f32>) -> Self {
+        BatchValue::Float(values)
+    }
+}
+
+impl From<Vec<String>> for BatchValue {
+    fn from(values: Vec<String>) -> Self {
+        BatchValue::String(values)
+    }
+}
+
+impl<const N: usize> From<[i32; N]> for BatchValue {
+    fn from(values: [i32; N]) -> Self {
+        BatchValue::Int(values.to_vec())
+    }
+}
+
+impl<const N: usize> From<[f32; N]> for BatchValue {
+    fn from(values: [f32; N]) -> Self {
+        BatchValue::Float(values.to_vec())
+    }
+}
+
+/// Parameter assignments of one asset cook.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchJob {
+    pub parms: Vec<(String, BatchValue)>,
+}
+
+impl BatchJob {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set parameter `name`, tuple parameters take an array or a `Vec`.
+    pub fn with_parm(mut self, name: impl Into<String>, value: impl Into<BatchValue>) -> Self {
+        self.parms.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// Where the geometry of each job is saved.
+#[derive(Debug, Clone)]
+pub enum BatchOutput {
+    /// Save with [`Geometry::save_to_file`](crate::geometry::Geometry::save_to_file). `{job}` in
+    /// the path is replaced with the job index, the extension selects the file format.
+    Files(PathBuf),
+    /// Keep the bytes from [`Geometry::save_to_memory`](crate::geometry::Geometry::save_to_memory).
+    Memory(GeoFormat),
+}
+
+impl BatchOutput {
+    pub fn files(pattern: impl Into<PathBuf>) -> Self {
+        BatchOutput::Files(pattern.into())
+    }
+
+    fn file_path(pattern: &std::path::Path, index: usize) -> PathBuf {
+        PathBuf::from(
+            pattern
+                .to_string_lossy()
+                .replace("{job}", &index.to_string()),
+        )
+    }
+}
+
+/// Saved geometry of a job.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobOutput {
+    File(PathBuf),
+    Memory(Vec<i8>),
+}
+
+/// Outcome of a [`BatchJob`].
+#[derive(Debug)]
+pub struct JobReport {
+    /// Position of the job in the list passed to [`Batch::run`]
+    pub index: usize,
+    /// Index of the pool session which ran the job, see [`PooledSession::index`]
+    pub session: Option<usize>,
+    pub result: Result<JobOutput>,
+    /// `None` if the job failed before cooking
+    pub cook_result: Option<CookResult>,
+    pub cook_time: Duration,
+    /// Time from creating the node to saving the output
+    pub total_time: Duration,
+}
+
+impl JobReport {
+    /// The output was saved and the asset cooked without errors.
+    pub fn succeeded(&self) -> bool {
+        self.result.is_ok() && self.cook_result == Some(CookResult::Succeeded)
+    }
+}
+
+/// Reports of all jobs of a batch, see [`Batch::run`].
+#[derive(Debug)]
+pub struct BatchReport {
+    /// Sorted by job index
+    pub jobs: Vec<JobReport>,
+    pub elapsed: Duration,
+}
+
+impl BatchReport {
+    pub fn succeeded(&self) -> impl Iterator<Item = &JobReport> {
+        self.jobs.iter().filter(|job| job.succeeded())
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &JobReport> {
+        self.jobs.iter().filter(|job| !job.succeeded())
+    }
+
+    pub fn all_succeeded(&self) -> bool {
+        self.jobs.iter().all(JobReport::succeeded)
+    }
+}
+
+/// Runs [`BatchJob`]s for an asset library, see the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct Batch {
+    library: PathBuf,
+    asset: Option<String>,
+    output: BatchOutput,
+    sessions: usize,
+    session_options: SessionOptions,
+    server_options: ServerOptions,
+}
+
+impl Batch {
+    /// Batch for the first asset of `library`. By default, one shared memory server is started
+    /// per CPU, mind that each server takes a license.
+    pub fn new(library: impl Into<PathBuf>, output: BatchOutput) -> Self {
+        Batch {
+            library: library.into(),
+            asset: None,
+            output,
+            sessions: thread::available_parallelism().map_or(1, |n| n.get()),
+            session_options: SessionOptions::default(),
+            server_options: ServerOptions::shared_memory_with_defaults(),
+        }
+    }
+
+    /// Fully qualified asset name, e.g `Sop/my_asset`, instead of the first asset in the library.
+    pub fn with_asset(mut self, name: impl Into<String>) -> Self {
+        self.asset = Some(name.into());
+        self
+    }
+
+    /// Number of servers started by [`Batch::run`].
+    pub fn with_sessions(mut self, sessions: usize) -> Self {
+        self.sessions = sessions.max(1);
+        self
+    }
+
+    pub fn with_session_options(mut self, options: SessionOptions) -> Self {
+        self.session_options = options;
+        self
+    }
+
+    pub fn with_server_options(mut self, options: ServerOptions) -> Self {
+        self.server_options = options;
+        self
+    }
+
+    /// Start a [`SessionPool`] and run `jobs` on it. The servers are shut down afterwards.
+    pub fn run(&self, jobs: impl IntoIterator<Item = BatchJob>) -> Result<BatchReport> {
+        let jobs: Vec<BatchJob> = jobs.into_iter().collect();
+        let sessions = self.sessions.min(jobs.len()).max(1);
+        let pool = SessionPool::new(
+            sessions,
+            self.session_options.clone(),
+            self.server_options.clone(),
+        )
+        .context("Starting batch sessions")?;
+        self.run_with_pool(&pool, jobs)
+    }
+
+    /// Run `jobs` on the sessions of an existing pool, one worker thread per session.
+    pub fn run_with_pool(
+        &self,
+        pool: &SessionPool,
+        jobs: impl IntoIterator<Item = BatchJob>,
+    ) -> Result<BatchReport> {
+        let jobs: Vec<BatchJob> = jobs.into_iter().collect();
+        let workers = pool.size().min(jobs.len());
+        debug!("Running {} batch jobs on {workers} sessions", jobs.len());
+        let start = Instant::now();
+        let next = AtomicUsize::new(0);
+        let reports = Mutex::new(Vec::with_capacity(jobs.len()));
+        thread::scope(|scope| {
+            for worker in 0..workers {
+                thread::Builder::new()
+                    .name(format!("hapi-rs-batch-{worker}"))
+                    .spawn_scoped(scope, || self.worker(pool, &jobs, &next, &reports))?;
+            }
+            Ok::<_, HapiError>(())
+        })?;
+        let mut jobs = reports.into_inner();
+        jobs.sort_by_key(|job| job.index);
+        Ok(BatchReport {
+            jobs,
+            elapsed: start.elapsed(),
+        })
+    }
+
+    fn worker(
+        &self,
+        pool: &SessionPool,
+        jobs: &[BatchJob],
+        next: &AtomicUsize,
+        reports: &Mutex<Vec<JobReport>>,
+    ) {
+        let mut checked_out: Option<(PooledSession, AssetLibrary)> = None;
+        loop {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            let Some(job) = jobs.get(index) else {
+                break;
+            };
+            // A session broken by a previous job goes back to the pool, which reconnects it.
+            if checked_out
+                .as_ref()
+                .is_some_and(|(session, _)| !session.is_valid())
+            {
+                checked_out = None;
+            }
+            if checked_out.is_none() {
+                match self.checkout(pool) {
+                    Ok(session) => checked_out = Some(session),
+                    Err(e) => {
+                        warn!("Batch job {index} has no session: {e}");
+                        reports.lock().push(JobReport {
+                            index,
+                            session: None,
+                            result: Err(e),
+                            cook_result: None,
+                            cook_time: Duration::ZERO,
+                            total_time: Duration::ZERO,
+                        });
+                        continue;
+                    }
+                }
+            }
+            let (session, library) = checked_out.as_ref().expect("session is checked out");
+            let report = self.run_job(index, session.index(), library, job);
+            if let Err(e) = &report.result {
+                warn!("Batch job {index} failed: {e}");
+            }
+            reports.lock().push(report);
+        }
+    }
+
+    fn checkout(&self, pool: &SessionPool) -> Result<(PooledSession, AssetLibrary)> {
+        let session = pool.checkout()?;
+        let library = session
+            .load_asset_file(&self.library)
+            .with_context(|| format!("Loading asset library {:?}", self.library))?;
+        Ok((session, library))
+    }
+
+    fn run_job(
+        &self,
+        index: usize,
+        session: usize,
+        library: &AssetLibrary,
+        job: &BatchJob,
+    ) -> JobReport {
+        let start = Instant::now();
+        let mut report = JobReport {
+            index,
+            session: Some(session),
+            result: Err(HapiError::Internal("Job did not run".to_string())),
+            cook_result: None,
+            cook_time: Duration::ZERO,
+            total_time: Duration::ZERO,
+        };
+        let node = match &self.asset {
+            Some(asset) => library.create_asset_for_node(asset.as_str(), None),
+            None => library.try_create_first(),
+        };
+        report.result = node.and_then(|node| {
+            let result = self.cook_and_save(index, &node, job, &mut report);
+            if let Err(e) = node.delete() {
+                warn!("Could not delete batch job {index} node: {e}");
+            }
+            result
+        });
+        report.total_time = start.elapsed();
+        report
+    }
+
+    fn cook_and_save(
+        &self,
+        index: usize,
+        node: &HoudiniNode,
+        job: &BatchJob,
+        report: &mut JobReport,
+    ) -> Result<JobOutput> {
+        for (name, value) in &job.parms {
+            set_parm(node, name, value).with_context(|| format!("Setting parameter {name}"))?;
+        }
+        let cook_start = Instant::now();
+        let cook_result = node.cook_blocking();
+        report.cook_time = cook_start.elapsed();
+        let cook_result = cook_result?;
+        report.cook_result = Some(cook_result.clone());
+        // Geometry of a cook with errors is still saved, it may be partial.
+        match cook_result {
+            CookResult::FatalErrors(message) => {
+                return Err(HapiError::Internal(format!("Cook failed: {message}")));
+            }
+            CookResult::Interrupted => {
+                return Err(HapiError::Internal("Cook was interrupted".to_string()));
+            }
+            CookResult::Succeeded | CookResult::CookErrors(_) => {}
+        }
+        let geometry = node
+            .geometry()?
+            .ok_or_else(|| HapiError::Internal("Asset has no geometry output".to_string()))?;
+        match &self.output {
+            BatchOutput::Files(pattern) => {
+                let path = BatchOutput::file_path(pattern, index);
+                geometry
+                    .save_to_file(&path.to_string_lossy())
+                    .with_context(|| format!("Saving geometry to {path:?}"))?;
+                Ok(JobOutput::File(path))
+            }
+            BatchOutput::Memory(format) => geometry.save_to_memory(*format).map(JobOutput::Memory),
+        }
+    }
+}
+
+fn set_parm(node: &HoudiniNode, name: &str, value: &BatchValue) -> Result<()> {
+    match (node.parameter(name)?, value) {
+        (Parameter::Float(parm), BatchValue::Float(values)) => parm.set_array(values),
+        (Parameter::Float(parm), BatchValue::Int(values)) => {
+            parm.set_array(values.iter().map(|v| *v as f32).collect::<Vec<_>>())
+        }
+        (Parameter::Int(parm), BatchValue::Int(values)) => parm.set_array(values),
+        (Parameter::String(parm), BatchValue::String(values)) => parm.set_array(values),
+        (_, value) => Err(HapiError::Internal(format!(
+            "Parameter {name} does not accept {value:?}"
+        ))),
+    }
+}
@@ -0,0 +1,216 @@
+//! Memory caches of a session.
+//!
+//! [`Session::cache_manager`] wraps [`Session::get_active_cache_names`] and the cache property
+//! functions with sizes in bytes. The Engine API works in whole megabytes, limits are rounded up.
+//!
+//! [`CacheManager::report`] takes a snapshot of all caches, comparing reports over time shows
+//! which caches grow in a long-running session:
+//!
+//! ```no_run
+//! # fn run(session: hapi_rs::session::Session) -> hapi_rs::Result<()> {
+//! let caches = session.cache_manager();
+//! caches.set_max_bytes("SOP Cache", 2 << 30)?;
+//! let before = caches.report()?;
+//! // ... cook for a while
+//! let after = caches.report()?;
+//! for (name, bytes) in after.growth_since(&before) {
+//!     println!("{name} grew by {bytes} bytes");
+//! }
+//! println!("{after}");
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt;
+use std::time::Instant;
+
+use crate::errors::Result;
+use crate::ffi::raw::CacheProperty;
+use crate::session::Session;
+
+const MB: u64 = 1024 * 1024;
+
+fn to_bytes(mb: i32) -> u64 {
+    mb.max(0) as u64 * MB
+}
+
+fn to_mb(bytes: u64) -> i32 {
+    bytes.div_ceil(MB).min(i32::MAX as u64) as i32
+}
+
+/// Memory usage and limits of a cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheInfo {
+    pub name: String,
+    /// Current memory usage in bytes
+    pub current: u64,
+    /// Lower memory limit in bytes, `None` if the cache has none
+    pub min: Option<u64>,
+    /// Upper memory limit in bytes, `None` if the cache has none
+    pub max: Option<u64>,
+}
+
+impl CacheInfo {
+    /// Fraction of the upper limit in use.
+    pub fn usage(&self) -> Option<f64> {
+        self.max
+            .filter(|max| *max > 0)
+            .map(|max| self.current as f64 / max as f64)
+    }
+}
+
+/// Caches of a session at a point in time, see [`CacheManager::report`].
+#[derive(Debug, Clone)]
+pub struct CacheReport {
+    /// Sorted by name
+    pub caches: Vec<CacheInfo>,
+    pub taken_at: Instant,
+}
+
+impl CacheReport {
+    pub fn get(&self, name: &str) -> Option<&CacheInfo> {
+        self.caches.iter().find(|cache| cache.name == name)
+    }
+
+    /// Memory used by all caches in bytes.
+    pub fn total_bytes(&self) -> u64 {
+        self.caches.iter().map(|cache| cache.current).sum()
+    }
+
+    /// Caches using at least `threshold` of their upper limit, e.g `0.9`.
+    pub fn near_limit(&self, threshold: f64) -> impl Iterator<Item = &CacheInfo> {
+        self.caches
+            .iter()
+            .filter(move |cache| cache.usage().is_some_and(|usage| usage >= threshold))
+    }
+
+    /// Change of memory usage in bytes per cache compared to an `earlier` report, largest growth
+    /// first. Unchanged caches are left out, caches missing from `earlier` count from zero.
+    pub fn growth_since(&self, earlier: &CacheReport) -> Vec<(String, i64)> {
+        let mut growth: Vec<(String, i64)> = self
+            .caches
+            .iter()
+            .map(|cache| {
+                let before = earlier.get(&cache.name).map_or(0, |cache| cache.current);
+                (cache.name.clone(), cache.current as i64 - before as i64)
+            })
+            .filter(|(_, bytes)| *bytes != 0)
+            .collect();
+        growth.sort_by_key(|(_, bytes)| std::cmp::Reverse(*bytes));
+        growth
+    }
+}
+
+impl fmt::Display for CacheReport {
+    /// One line per cache with the current usage and limit in MB.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .caches
+            .iter()
+            .map(|cache| cache.name.len())
+            .max()
+            .unwrap_or(0);
+        for cache in &self.caches {
+            let current = cache.current as f64 / MB as f64;
+            write!(f, "{:width$}  {current:>10.1} MB", cache.name)?;
+            match cache.max {
+                Some(max) => writeln!(f, " / {:.1} MB", max as f64 / MB as f64)?,
+                None => writeln!(f)?,
+            }
+        }
+        write!(
+            f,
+            "{:width$}  {:>10.1} MB",
+            "Total",
+            self.total_bytes() as f64 / MB as f64
+        )
+    }
+}
+
+/// Typed access to the memory caches of a session, see [`Session::cache_manager`].
+#[derive(Debug, Clone)]
+pub struct CacheManager {
+    session: Session,
+}
+
+impl CacheManager {
+    pub(crate) fn new(session: Session) -> Self {
+        CacheManager { session }
+    }
+
+    fn get(&self, name: &str, property: CacheProperty) -> Result<i32> {
+        self.session.get_cache_property_value(name, property)
+    }
+
+    fn set(&self, name: &str, property: CacheProperty, value: i32) -> Result<()> {
+        self.session.set_cache_property_value(name, property, value)
+    }
+
+    /// Names of the active caches.
+    pub fn names(&self) -> Result<Vec<String>> {
+        Ok(self.session.get_active_cache_names()?.into_iter().collect())
+    }
+
+    pub fn info(&self, name: &str) -> Result<CacheInfo> {
+        let limit = |has: CacheProperty, value: CacheProperty| -> Result<Option<u64>> {
+            Ok(match self.get(name, has)? {
+                0 => None,
+                _ => Some(to_bytes(self.get(name, value)?)),
+            })
+        };
+        Ok(CacheInfo {
+            name: name.to_string(),
+            current: to_bytes(self.get(name, CacheProperty::CachepropCurrent)?),
+            min: limit(CacheProperty::HasMin, CacheProperty::CachepropMin)?,
+            max: limit(CacheProperty::HasMax, CacheProperty::CachepropMax)?,
+        })
+    }
+
+    /// Info of all active caches, sorted by name.
+    pub fn list(&self) -> Result<Vec<CacheInfo>> {
+        let mut names = self.names()?;
+        names.sort();
+        names.iter().map(|name| self.info(name)).collect()
+    }
+
+    /// Set the upper memory limit, rounded up to whole megabytes.
+    pub fn set_max_bytes(&self, name: &str, bytes: u64) -> Result<()> {
+        self.set(name, CacheProperty::CachepropMax, to_mb(bytes))
+    }
+
+    /// Set the lower memory limit, rounded up to whole megabytes.
+    pub fn set_min_bytes(&self, name: &str, bytes: u64) -> Result<()> {
+        self.set(name, CacheProperty::CachepropMin, to_mb(bytes))
+    }
+
+    /// How aggressively memory is culled, from 0 (least) to 10 (most). Only supported by the
+    /// COP and object caches.
+    pub fn set_cull_level(&self, name: &str, level: u8) -> Result<()> {
+        self.set(name, CacheProperty::CullLevel, i32::from(level.min(10)))
+    }
+
+    /// Reduce the memory usage of the cache down to `bytes`.
+    pub fn trim(&self, name: &str, bytes: u64) -> Result<()> {
+        self.set(name, CacheProperty::CachepropCurrent, to_mb(bytes))
+    }
+
+    /// Empty the cache.
+    pub fn clear(&self, name: &str) -> Result<()> {
+        self.set(name, CacheProperty::CachepropCurrent, 0)
+    }
+
+    /// Empty all active caches.
+    pub fn clear_all(&self) -> Result<()> {
+        for name in self.names()? {
+            self.clear(&name)?;
+        }
+        Ok(())
+    }
+
+    /// Snapshot of all caches for monitoring, see [`CacheReport::growth_since`].
+    pub fn report(&self) -> Result<CacheReport> {
+        Ok(CacheReport {
+            caches: self.list()?,
+            taken_at: Instant::now(),
+        })
+    }
+}
@@ -0,0 +1,319 @@
+//! Cook progress reporting, cancellation and runtime-agnostic cook futures.
+//!
+//! [`HoudiniNode::cook_with_progress`](crate::node::HoudiniNode::cook_with_progress) cooks a node on
+//! a background thread, reports [`CookProgress`] updates and returns a [`CookHandle`] which can
+//! cancel the cook:
+//!
+//! ```no_run
+//! use hapi_rs::session::{SessionOptions, new_thrift_session};
+//! use hapi_rs::server::ServerOptions;
+//! # fn run() -> hapi_rs::Result<()> {
+//! let session = new_thrift_session(
+//!     SessionOptions::default().threaded(true),
+//!     ServerOptions::shared_memory_with_defaults(),
+//! )?;
+//! let node = session.create_node("Object/geo")?;
+//! let (tx, rx) = std::sync::mpsc::channel();
+//! let handle = node.cook_with_progress(move |progress| {
+//!     let _ = tx.send(progress);
+//! })?;
+//! for progress in rx.iter().take(10) {
+//!     println!("{}/{} {}", progress.current, progress.total, progress.status);
+//! }
+//! handle.cancel()?;
+//! let result = handle.wait()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! With the `async-cooking` feature, `CookFuture` waits for a threaded cook without blocking.
+//! In threaded mode the Engine cooks in the background and the cook state has to be polled with
+//! `HAPI_GetStatus`. `CookFuture` does this polling between [`CookBackoff`] delays, which are
+//! driven by a single background timer thread, so it works with any executor and doesn't block
+//! an executor thread while the session is cooking.
+//!
+//! ```no_run
+//! # #[cfg(feature = "async-cooking")]
+//! # async fn run() -> hapi_rs::Result<()> {
+//! use hapi_rs::session::simple_session;
+//! let session = simple_session()?;
+//! let node = session.create_node("Object/geo")?;
+//! let result = node.cook_async().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`CookBackoff`]: crate::session::CookBackoff
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use log::debug;
+
+use crate::errors::{HapiError, Result};
+use crate::node::HoudiniNode;
+use crate::session::{CookResult, Session, StatusType, StatusVerbosity};
+
+#[cfg(feature = "async-cooking")]
+pub use self::future::CookFuture;
+
+/// Progress of a running cook reported by [`HoudiniNode::cook_with_progress`].
+#[derive(Debug, Clone)]
+pub struct CookProgress {
+    /// Number of nodes already cooked, see [`Session::cooking_current_count`]
+    pub current: i32,
+    /// Number of nodes to cook, see [`Session::cooking_total_count`]
+    pub total: i32,
+    /// Cook state message from the server
+    pub status: String,
+    /// Time since the cook started
+    pub elapsed: Duration,
+}
+
+// States of a cook started with `cook_with_progress`.
+const RUNNING: u8 = 0;
+const CANCELLED: u8 = 1;
+const FINISHED: u8 = 2;
+
+/// Handle to a cook started with [`HoudiniNode::cook_with_progress`].
+#[derive(Debug)]
+pub struct CookHandle {
+    session: Session,
+    state: Arc<AtomicU8>,
+    thread: JoinHandle<Result<CookResult>>,
+}
+
+impl CookHandle {
+    /// Interrupt the cook if it's still running, it then resolves to [`CookResult::Interrupted`].
+    /// A cook which already completed keeps its result. In single-threaded sessions the cook
+    /// runs inside `HAPI_CookNode` and always completes.
+    pub fn cancel(&self) -> Result<()> {
+        match self
+            .state
+            .compare_exchange(RUNNING, CANCELLED, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => self.session.interrupt(),
+            Err(_) => Ok(()),
+        }
+    }
+
+    /// Returns `true` if the cook has completed, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    /// Block until the cook completes.
+    pub fn wait(self) -> Result<CookResult> {
+        self.thread
+            .join()
+            .map_err(|_| HapiError::Internal("Cook progress thread panicked".to_string()))?
+    }
+}
+
+pub(crate) fn cook_with_progress<F>(node: &HoudiniNode, mut on_progress: F) -> Result<CookHandle>
+where
+    F: FnMut(CookProgress) + Send + 'static,
+{
+    let session = node.session.clone();
+    let state = Arc::new(AtomicU8::new(RUNNING));
+    let thread = std::thread::Builder::new()
+        .name("hapi-rs-cook-progress".to_string())
+        .spawn({
+            let node = node.clone();
+            let state = Arc::clone(&state);
+            move || {
+                let session = &node.session;
+                let start = Instant::now();
+                crate::ffi::cook_node(&node, None)?;
+                let mut delays = session.inner.options.cook_backoff.delays();
+                loop {
+                    let done = session.poll_cook()?;
+                    // Once the cook is done, `cancel` no longer interrupts it.
+                    let cancelled =
+                        done.is_some() && state.swap(FINISHED, Ordering::AcqRel) == CANCELLED;
+                    on_progress(CookProgress {
+                        current: session.cooking_current_count()?,
+                        total: session.cooking_total_count()?,
+                        status: session
+                            .get_status_string(StatusType::CookState, StatusVerbosity::All)?,
+                        elapsed: start.elapsed(),
+                    });
+                    if let Some(result) = done {
+                        // The cook may have completed before the interrupt reached the server,
+                        // in which case the server reports it as succeeded.
+                        if cancelled && result != CookResult::Succeeded {
+                            debug!("Cook interrupted after {:?}", start.elapsed());
+                            break Ok(CookResult::Interrupted);
+                        }
+                        break Ok(result);
+                    }
+                    std::thread::sleep(delays.next().unwrap_or_default());
+                }
+            }
+        })?;
+    Ok(CookHandle {
+        session,
+        state,
+        thread,
+    })
+}
+
+#[cfg(feature = "async-cooking")]
+mod future {
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::OnceLock;
+    use std::task::{Context, Poll, Waker};
+    use std::time::{Duration, Instant};
+
+    use parking_lot::{Condvar, Mutex};
+
+    use crate::errors::{HapiError, Result};
+    use crate::session::{CookResult, Session};
+
+    /// Future returned by [`Session::wait_cook`] and [`HoudiniNode::cook_async`](crate::node::HoudiniNode::cook_async).
+    ///
+    /// Resolves when the session is done cooking. In single-threaded sessions the cook
+    /// already happened when the future is created and it resolves immediately.
+    #[must_use = "futures do nothing unless polled"]
+    pub struct CookFuture {
+        session: Session,
+        error: Option<HapiError>,
+        delays: Box<dyn Iterator<Item = Duration> + Send>,
+        deadline: Option<Instant>,
+    }
+
+    impl CookFuture {
+        pub(crate) fn new(session: Session) -> Self {
+            let delays = Box::new(session.inner.options.cook_backoff.delays());
+            CookFuture {
+                session,
+                error: None,
+                delays,
+                deadline: None,
+            }
+        }
+
+        /// A future which resolves to `error` on the first poll.
+        pub(crate) fn failed(session: Session, error: HapiError) -> Self {
+            let mut future = Self::new(session);
+            future.error = Some(error);
+            future
+        }
+    }
+
+    impl std::fmt::Debug for CookFuture {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("CookFuture")
+                .field("error", &self.error)
+                .field("deadline", &self.deadline)
+                .finish()
+        }
+    }
+
+    impl Future for CookFuture {
+        type Output = Result<CookResult>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let this = self.get_mut();
+            if let Some(error) = this.error.take() {
+                return Poll::Ready(Err(error));
+            }
+            if let Some(deadline) = this.deadline {
+                if Instant::now() < deadline {
+                    timer().wake_at(deadline, cx.waker().clone());
+                    return Poll::Pending;
+                }
+                this.deadline = None;
+            }
+            match this.session.poll_cook() {
+                Ok(Some(result)) => Poll::Ready(Ok(result)),
+                Err(e) => Poll::Ready(Err(e)),
+                Ok(None) => {
+                    let deadline = Instant::now() + this.delays.next().unwrap_or_default();
+                    this.deadline = Some(deadline);
+                    timer().wake_at(deadline, cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }
+    }
+
+    struct TimerEntry {
+        deadline: Instant,
+        waker: Waker,
+    }
+
+    impl PartialEq for TimerEntry {
+        fn eq(&self, other: &Self) -> bool {
+            self.deadline == other.deadline
+        }
+    }
+
+    impl Eq for TimerEntry {}
+
+    impl PartialOrd for TimerEntry {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for TimerEntry {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.deadline.cmp(&other.deadline)
+        }
+    }
+
+    /// Wakes registered wakers once their deadline has passed.
+    struct Timer {
+        queue: Mutex<BinaryHeap<Reverse<TimerEntry>>>,
+        condvar: Condvar,
+    }
+
+    impl Timer {
+        fn wake_at(&self, deadline: Instant, waker: Waker) {
+            let mut queue = self.queue.lock();
+            let earliest = queue.peek().is_none_or(|Reverse(e)| deadline < e.deadline);
+            queue.push(Reverse(TimerEntry { deadline, waker }));
+            if earliest {
+                self.condvar.notify_one();
+            }
+        }
+
+        fn run(&self) {
+            let mut queue = self.queue.lock();
+            loop {
+                let now = Instant::now();
+                while queue.peek().is_some_and(|Reverse(e)| e.deadline <= now) {
+                    let Reverse(entry) = queue.pop().expect("peeked entry");
+                    entry.waker.wake();
+                }
+                match queue.peek() {
+                    Some(Reverse(entry)) => {
+                        let deadline = entry.deadline;
+                        self.condvar.wait_until(&mut queue, deadline);
+                    }
+                    None => self.condvar.wait(&mut queue),
+                }
+            }
+        }
+    }
+
+    fn timer() -> &'static Timer {
+        static TIMER: OnceLock<&'static Timer> = OnceLock::new();
+        TIMER.get_or_init(|| {
+            let timer: &'static Timer = Box::leak(Box::new(Timer {
+                queue: Mutex::new(BinaryHeap::new()),
+                condvar: Condvar::new(),
+            }));
+            std::thread::Builder::new()
+                .name("hapi-rs-cook-timer".to_string())
+                .spawn(move || timer.run())
+                .expect("Could not spawn cook timer thread");
+            timer
+        })
+    }
+}
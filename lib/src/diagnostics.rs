@@ -0,0 +1,174 @@
+//! Cook errors, warnings and messages of a whole network.
+//!
+//! [`HoudiniNode::get_composed_cook_result_string`] returns the results of all nodes as a single
+//! text. [`HoudiniNode::cook_diagnostics`] instead walks the node and all of its descendants and
+//! parses the cook result of each node with [`CookMessage::parse`] into a [`CookDiagnostic`],
+//! which makes it easy to fail a build on specific warnings:
+//!
+//! ```no_run
+//! use hapi_rs::diagnostics::Severity;
+//! # fn run(asset: hapi_rs::node::HoudiniNode) -> hapi_rs::Result<()> {
+//! asset.cook_blocking()?;
+//! let report = asset.cook_diagnostics()?.at_least(Severity::Warning);
+//! if report.warnings().any(|w| w.message.contains("deprecated")) {
+//!     eprintln!("{report}");
+//!     std::process::exit(1);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! With the `serde` feature the report can be written out as e.g. JSON.
+use std::fmt;
+
+use crate::errors::{CookMessage, Result};
+use crate::node::{HoudiniNode, StatusVerbosity};
+
+pub use crate::errors::Severity;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A single error, warning or message reported by a node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CookDiagnostic {
+    pub node_path: String,
+    pub severity: Severity,
+    /// Error code of the operator type, if the message has one
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub code: Option<i32>,
+    /// Without the severity prefix, multi-line messages are joined with `\n`
+    pub message: String,
+}
+
+impl fmt::Display for CookDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.node_path, self.severity)?;
+        if let Some(code) = self.code {
+            write!(f, " {code}")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl CookDiagnostic {
+    fn from_message(node_path: &str, message: CookMessage) -> Self {
+        CookDiagnostic {
+            node_path: message.node_path.unwrap_or_else(|| node_path.to_string()),
+            severity: message.severity,
+            code: message.code,
+            message: message.message,
+        }
+    }
+}
+
+/// Cook results of a network, see [`HoudiniNode::cook_diagnostics`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CookDiagnostics {
+    /// In network traversal order, the node itself first
+    pub entries: Vec<CookDiagnostic>,
+}
+
+impl CookDiagnostics {
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CookDiagnostic> {
+        self.entries.iter()
+    }
+
+    /// Entries of exactly this severity.
+    pub fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &CookDiagnostic> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.severity == severity)
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &CookDiagnostic> {
+        self.with_severity(Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &CookDiagnostic> {
+        self.with_severity(Severity::Warning)
+    }
+
+    pub fn messages(&self) -> impl Iterator<Item = &CookDiagnostic> {
+        self.with_severity(Severity::Message)
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// Report with only the entries of `severity` or above, e.g warnings and errors.
+    pub fn at_least(&self, severity: Severity) -> CookDiagnostics {
+        self.filter(|entry| entry.severity >= severity)
+    }
+
+    /// Report with only the entries matching `predicate`.
+    pub fn filter(&self, predicate: impl Fn(&CookDiagnostic) -> bool) -> CookDiagnostics {
+        CookDiagnostics {
+            entries: self
+                .entries
+                .iter()
+                .filter(|entry| predicate(entry))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for CookDiagnostics {
+    /// One entry per line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{entry}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> IntoIterator for &'a CookDiagnostics {
+    type Item = &'a CookDiagnostic;
+    type IntoIter = std::slice::Iter<'a, CookDiagnostic>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+impl HoudiniNode {
+    /// Collect the cook errors, warnings and messages of this node and all of its descendants,
+    /// see [`crate::diagnostics`]. The nodes are not cooked.
+    pub fn cook_diagnostics(&self) -> Result<CookDiagnostics> {
+        debug_assert!(self.is_valid()?, "Invalid node: {}", self.path()?);
+        let mut entries = Vec::new();
+        let mut collect = |node: &HoudiniNode| -> Result<()> {
+            let text = node.get_cook_result_string(StatusVerbosity::All)?;
+            if !text.trim().is_empty() {
+                let path = node.path()?;
+                entries.extend(
+                    CookMessage::parse(&text)
+                        .into_iter()
+                        .map(|message| CookDiagnostic::from_message(&path, message)),
+                );
+            }
+            Ok(())
+        };
+        collect(self)?;
+        for node in self.query().recursive(true).iter()? {
+            collect(&node?)?;
+        }
+        Ok(CookDiagnostics { entries })
+    }
+}
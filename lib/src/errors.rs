@@ -3,6 +3,9 @@ use crate::session::Session;
 pub use crate::ffi::raw::{HapiResult, StatusType, StatusVerbosity};
 use thiserror::Error;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 pub type Result<T> = std::result::Result<T, HapiError>;
 
 /// Error type returned by all APIs
@@ -34,6 +37,225 @@ pub enum HapiError {
 
     /// Internal library error
     Internal(String),
+
+    /// The Engine API backend is unavailable, e.g libHAPIL could not be loaded
+    Backend(String),
+
+    /// The server crashed and the session was recovered with a new server,
+    /// see [`SessionOptions::auto_recover`](crate::session::SessionOptions::auto_recover).
+    /// Node, asset and geometry handles created before the crash are stale.
+    SessionRecovered { server_pid: Option<u32> },
+
+    /// A call didn't finish within its [`CallTimeout`](crate::session::CallTimeout) and the session
+    /// was interrupted. If it didn't respond to the interrupt either, the server was killed.
+    Timeout {
+        operation: String,
+        timeout: std::time::Duration,
+        server_killed: bool,
+    },
+
+    /// The session checked out a license below the minimum set with
+    /// [`ServerOptions::with_minimum_license`](crate::server::ServerOptions::with_minimum_license).
+    LicenseRejected {
+        license: crate::session::LicenseType,
+        minimum: crate::server::LicenseTier,
+    },
+}
+
+impl HapiError {
+    /// Returns `true` if the error, or the error it adds context to, is [`HapiError::SessionRecovered`].
+    pub fn is_session_recovered(&self) -> bool {
+        match self {
+            HapiError::SessionRecovered { .. } => true,
+            HapiError::Context { source, .. } => source.is_session_recovered(),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the error, or the error it adds context to, is [`HapiError::Timeout`].
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            HapiError::Timeout { .. } => true,
+            HapiError::Context { source, .. } => source.is_timeout(),
+            _ => false,
+        }
+    }
+
+    /// Result code of a failed HAPI call, looking through added context.
+    pub fn result_code(&self) -> Option<HapiResult> {
+        match self {
+            HapiError::Hapi { result_code, .. } => Some(result_code.0),
+            HapiError::Context { source, .. } => source.result_code(),
+            _ => None,
+        }
+    }
+
+    /// Errors, warnings and messages parsed from the engine message, looking through added context.
+    pub fn diagnostics(&self) -> Vec<CookMessage> {
+        match self {
+            HapiError::Hapi {
+                server_message: Some(message),
+                ..
+            } => CookMessage::parse(message),
+            HapiError::Context { source, .. } => source.diagnostics(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns `true` if the session could not check out a license it's allowed to use.
+    pub fn is_license_error(&self) -> bool {
+        match self {
+            HapiError::LicenseRejected { .. } => true,
+            HapiError::Context { source, .. } => source.is_license_error(),
+            _ => matches!(
+                self.result_code(),
+                Some(HapiResult::NoLicenseFound | HapiResult::DisallowedNcLicenseFound)
+            ),
+        }
+    }
+}
+
+/// Severity of a [`CookMessage`], ordered from least to most severe.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Severity {
+    Message,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn from_word(word: &str) -> Option<Severity> {
+        match word.to_ascii_lowercase().as_str() {
+            "message" => Some(Severity::Message),
+            "warning" => Some(Severity::Warning),
+            "error" | "fatal" => Some(Severity::Error),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Severity::Message => "Message",
+            Severity::Warning => "Warning",
+            Severity::Error => "Error",
+        })
+    }
+}
+
+/// An entry of a status or cook result string, see [`CookMessage::parse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CookMessage {
+    pub severity: Severity,
+    /// Node which reported the message, if the text names one
+    pub node_path: Option<String>,
+    /// Error code of the operator type, e.g SOP error codes
+    pub code: Option<i32>,
+    /// Without the prefixes, multi-line messages are joined with `\n`
+    pub message: String,
+}
+
+// "Error:", "Warning 11:" or "SOP Error 11:" followed by the message.
+fn parse_severity(line: &str) -> Option<(Severity, Option<i32>, &str)> {
+    let (head, message) = line.split_once(':')?;
+    let mut words: Vec<&str> = head.split_whitespace().collect();
+    let code = match words.last().map(|word| word.parse::<i32>()) {
+        Some(Ok(code)) => {
+            words.pop();
+            Some(code)
+        }
+        _ => None,
+    };
+    let severity = match words.as_slice() {
+        [severity] => Severity::from_word(severity)?,
+        [kind, severity] if kind.chars().all(|c| c.is_ascii_alphabetic()) => {
+            Severity::from_word(severity)?
+        }
+        _ => return None,
+    };
+    Some((severity, code, message.trim()))
+}
+
+impl CookMessage {
+    /// Parse the text of [`Session::get_cook_result_string`](crate::session::Session::get_cook_result_string),
+    /// [`HoudiniNode::get_cook_result_string`](crate::node::HoudiniNode::get_cook_result_string)
+    /// or an engine error message.
+    ///
+    /// An entry starts with a severity, optionally with the operator type and error code,
+    /// and may be prefixed with the path of the node: `/obj/geo1/file1: SOP Error 11: ...`.
+    /// A line with only a node path followed by `:` applies to the entries below it.
+    /// Headers like `Cook Errors:` are skipped, other lines continue the previous entry.
+    /// Text without any severity is returned as a single error.
+    pub fn parse(text: &str) -> Vec<CookMessage> {
+        let mut entries: Vec<CookMessage> = Vec::new();
+        let mut current_node: Option<String> = None;
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let (node_path, rest) = match line.split_once(':') {
+                Some((path, rest)) if path.starts_with('/') && !path.contains(' ') => {
+                    (Some(path.to_string()), rest.trim())
+                }
+                _ => (None, line),
+            };
+            if rest.is_empty() {
+                if node_path.is_some() {
+                    current_node = node_path;
+                    continue;
+                }
+            } else if node_path.is_none()
+                && rest.ends_with(':')
+                && rest.split_whitespace().count() <= 3
+                && rest[..rest.len() - 1]
+                    .chars()
+                    .all(|c| c.is_ascii_alphabetic() || c == ' ')
+                && parse_severity(rest).is_none()
+            {
+                // Section header
+                continue;
+            }
+            let inline_path = node_path.is_some();
+            let node_path = node_path.or_else(|| current_node.clone());
+            match (parse_severity(rest), entries.last_mut()) {
+                (Some((severity, code, message)), _) => entries.push(CookMessage {
+                    severity,
+                    node_path,
+                    code,
+                    message: message.to_string(),
+                }),
+                (None, Some(last)) if !inline_path => {
+                    if !last.message.is_empty() {
+                        last.message.push('\n');
+                    }
+                    last.message.push_str(rest);
+                }
+                (None, _) => entries.push(CookMessage {
+                    severity: Severity::Error,
+                    node_path,
+                    code: None,
+                    message: rest.to_string(),
+                }),
+            }
+        }
+        entries
+    }
+}
+
+impl std::fmt::Display for CookMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(path) = &self.node_path {
+            write!(f, "{}: ", path)?;
+        }
+        write!(f, "{}", self.severity)?;
+        if let Some(code) = self.code {
+            write!(f, " {}", code)?;
+        }
+        write!(f, ": {}", self.message)
+    }
 }
 
 // Wrapper for HapiResult to provide Display for error messages
@@ -191,6 +413,28 @@ impl std::fmt::Display for HapiError {
                 }
                 HapiError::Io(e) => write!(f, "IO error: {}", e),
                 HapiError::Internal(e) => write!(f, "Internal error: {}", e),
+                HapiError::Backend(e) => write!(f, "Backend error: {}", e),
+                HapiError::SessionRecovered { server_pid } => write!(
+                    f,
+                    "Session was recovered after a server crash, new server pid: {:?}. Handles created before the crash are stale",
+                    server_pid
+                ),
+                HapiError::Timeout {
+                    operation,
+                    timeout,
+                    server_killed,
+                } => {
+                    write!(f, "{} timed out after {:?}", operation, timeout)?;
+                    if *server_killed {
+                        write!(f, ", the server was killed")?;
+                    }
+                    Ok(())
+                }
+                HapiError::LicenseRejected { license, minimum } => write!(
+                    f,
+                    "Session checked out a {:?} license, {:?} or better is required",
+                    license, minimum
+                ),
             }
         }
 
@@ -231,6 +475,11 @@ impl HapiResult {
         match self {
             HapiResult::Success => Ok(()),
             _err => {
+                if !session.is_valid()
+                    && let Some(error) = session.recover_after_error()
+                {
+                    return Err(error);
+                }
                 let server_message = if session.is_valid() {
                     session
                         .get_status_string(StatusType::CallResult, StatusVerbosity::All)
@@ -323,6 +572,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cook_messages_are_parsed() {
+        let text = "Cook Errors:\n\
+            /obj/geo1/file1:\n\
+            \tSOP Error 11: Unable to read file \"a.bgeo\".\n\
+            \t(No such file or directory)\n\
+            /obj/geo1/wrangle1: Warning: Deprecated function\n\
+            /obj/geo1/OUT:\n\
+            Message: Done\n";
+        let messages = CookMessage::parse(text);
+        assert_eq!(
+            messages,
+            vec![
+                CookMessage {
+                    severity: Severity::Error,
+                    node_path: Some("/obj/geo1/file1".to_string()),
+                    code: Some(11),
+                    message: "Unable to read file \"a.bgeo\".\n(No such file or directory)"
+                        .to_string(),
+                },
+                CookMessage {
+                    severity: Severity::Warning,
+                    node_path: Some("/obj/geo1/wrangle1".to_string()),
+                    code: None,
+                    message: "Deprecated function".to_string(),
+                },
+                CookMessage {
+                    severity: Severity::Message,
+                    node_path: Some("/obj/geo1/OUT".to_string()),
+                    code: None,
+                    message: "Done".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            messages[0].to_string(),
+            "/obj/geo1/file1: Error 11: Unable to read file \"a.bgeo\".\n(No such file or directory)"
+        );
+    }
+
+    #[test]
+    fn diagnostics_of_hapi_errors() {
+        let err = (Err::<(), HapiError>(HapiError::Hapi {
+            result_code: HapiResultCode(HapiResult::Failure),
+            server_message: Some("Invalid node id".to_string()),
+            contexts: vec![],
+        }))
+        .context("outer")
+        .unwrap_err();
+        let diagnostics = err.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].message, "Invalid node id");
+        assert!(
+            HapiError::Internal("x".to_string())
+                .diagnostics()
+                .is_empty()
+        );
+    }
+
     #[test]
     fn context_added_outside_hapi_error_is_rendered_after_inner_contexts() {
         // Create a HAPI error with one context, then add an outer wrapper context.
@@ -788,6 +788,29 @@ pub fn create_inprocess_session(info: &raw::HAPI_SessionInfo) -> Result<raw::HAP
     }
 }
 
+pub fn bind_custom_implementation(session_type: raw::SessionType, dll_path: &CStr) -> Result<()> {
+    unsafe {
+        raw::HAPI_BindCustomImplementation(session_type, dll_path.as_ptr())
+            .with_context(|| format!("Could not bind custom implementation {dll_path:?}"))
+    }
+}
+
+pub fn create_custom_session(
+    session_type: raw::SessionType,
+    session_info: *mut std::ffi::c_void,
+) -> Result<raw::HAPI_Session> {
+    let mut ses = uninit!();
+    unsafe {
+        raw::HAPI_CreateCustomSession(session_type, session_info, ses.as_mut_ptr()).with_context(
+            || {
+                get_connection_error(true)
+                    .unwrap_or("Could not retrieve server connection error".to_string())
+            },
+        )?;
+        Ok(ses.assume_init())
+    }
+}
+
 pub fn set_server_env_str(session: &Session, key: &CStr, value: &CStr) -> Result<()> {
     unsafe {
         raw::HAPI_SetServerEnvString(session.ptr(), key.as_ptr(), value.as_ptr())
@@ -1022,6 +1045,11 @@ pub fn close_session(session: &Session) -> Result<()> {
     }
 }
 
+/// Close a session which failed to initialize.
+pub fn close_session_handle(session: raw::HAPI_Session) -> Result<()> {
+    unsafe { raw::HAPI_CloseSession(&session).with_context(|| "Calling HAPI_CloseSession") }
+}
+
 pub fn is_session_initialized(session: &Session) -> bool {
     unsafe {
         match raw::HAPI_IsInitialized(session.ptr()) {
@@ -0,0 +1,452 @@
+// This file is generated by hapi-bindgen from bindings.rs. Do not edit by hand.
+
+/// Invokes `$callback!` with the signature of every function declared in `bindings.rs`.
+///
+/// Each entry has the form `fn HAPI_Name(arg: Type, ...) -> Ret;` where the return type
+/// is omitted for functions returning nothing.
+macro_rules! hapi_functions {
+    ($callback:ident) => {
+        $callback! {
+            fn HAPI_SessionInfo_Init(in_: *mut HAPI_SessionInfo);
+            fn HAPI_SessionInfo_Create() -> HAPI_SessionInfo;
+            fn HAPI_ThriftServerOptions_Init(in_: *mut HAPI_ThriftServerOptions);
+            fn HAPI_ThriftServerOptions_Create() -> HAPI_ThriftServerOptions;
+            fn HAPI_TimelineOptions_Init(in_: *mut HAPI_TimelineOptions);
+            fn HAPI_TimelineOptions_Create() -> HAPI_TimelineOptions;
+            fn HAPI_CompositorOptions_Init(in_: *mut HAPI_CompositorOptions);
+            fn HAPI_CompositorOptions_Create() -> HAPI_CompositorOptions;
+            fn HAPI_AssetInfo_Init(in_: *mut HAPI_AssetInfo);
+            fn HAPI_AssetInfo_Create() -> HAPI_AssetInfo;
+            fn HAPI_CookOptions_Init(in_: *mut HAPI_CookOptions);
+            fn HAPI_CookOptions_Create() -> HAPI_CookOptions;
+            fn HAPI_CookOptions_AreEqual(left: *const HAPI_CookOptions, right: *const HAPI_CookOptions) -> HAPI_Bool;
+            fn HAPI_NodeInfo_Init(in_: *mut HAPI_NodeInfo);
+            fn HAPI_NodeInfo_Create() -> HAPI_NodeInfo;
+            fn HAPI_ParmInfo_Init(in_: *mut HAPI_ParmInfo);
+            fn HAPI_ParmInfo_Create() -> HAPI_ParmInfo;
+            fn HAPI_ParmInfo_IsInt(in_: *const HAPI_ParmInfo) -> HAPI_Bool;
+            fn HAPI_ParmInfo_IsFloat(in_: *const HAPI_ParmInfo) -> HAPI_Bool;
+            fn HAPI_ParmInfo_IsString(in_: *const HAPI_ParmInfo) -> HAPI_Bool;
+            fn HAPI_ParmInfo_IsPath(in_: *const HAPI_ParmInfo) -> HAPI_Bool;
+            fn HAPI_ParmInfo_IsNode(in_: *const HAPI_ParmInfo) -> HAPI_Bool;
+            fn HAPI_ParmInfo_IsNonValue(in_: *const HAPI_ParmInfo) -> HAPI_Bool;
+            fn HAPI_ParmInfo_GetIntValueCount(in_: *const HAPI_ParmInfo) -> ::std::os::raw::c_int;
+            fn HAPI_ParmInfo_GetFloatValueCount(in_: *const HAPI_ParmInfo) -> ::std::os::raw::c_int;
+            fn HAPI_ParmInfo_GetStringValueCount(in_: *const HAPI_ParmInfo) -> ::std::os::raw::c_int;
+            fn HAPI_ParmChoiceInfo_Init(in_: *mut HAPI_ParmChoiceInfo);
+            fn HAPI_ParmChoiceInfo_Create() -> HAPI_ParmChoiceInfo;
+            fn HAPI_HandleInfo_Init(in_: *mut HAPI_HandleInfo);
+            fn HAPI_HandleInfo_Create() -> HAPI_HandleInfo;
+            fn HAPI_HandleBindingInfo_Init(in_: *mut HAPI_HandleBindingInfo);
+            fn HAPI_HandleBindingInfo_Create() -> HAPI_HandleBindingInfo;
+            fn HAPI_ObjectInfo_Init(in_: *mut HAPI_ObjectInfo);
+            fn HAPI_ObjectInfo_Create() -> HAPI_ObjectInfo;
+            fn HAPI_GeoInfo_Init(in_: *mut HAPI_GeoInfo);
+            fn HAPI_GeoInfo_Create() -> HAPI_GeoInfo;
+            fn HAPI_GeoInfo_GetGroupCountByType(in_: *mut HAPI_GeoInfo, type_: GroupType) -> ::std::os::raw::c_int;
+            fn HAPI_PartInfo_Init(in_: *mut HAPI_PartInfo);
+            fn HAPI_PartInfo_Create() -> HAPI_PartInfo;
+            fn HAPI_PartInfo_GetElementCountByAttributeOwner(in_: *mut HAPI_PartInfo, owner: AttributeOwner) -> ::std::os::raw::c_int;
+            fn HAPI_PartInfo_GetElementCountByGroupType(in_: *mut HAPI_PartInfo, type_: GroupType) -> ::std::os::raw::c_int;
+            fn HAPI_PartInfo_GetAttributeCountByOwner(in_: *mut HAPI_PartInfo, owner: AttributeOwner) -> ::std::os::raw::c_int;
+            fn HAPI_AttributeInfo_Init(in_: *mut HAPI_AttributeInfo);
+            fn HAPI_AttributeInfo_Create() -> HAPI_AttributeInfo;
+            fn HAPI_MaterialInfo_Init(in_: *mut HAPI_MaterialInfo);
+            fn HAPI_MaterialInfo_Create() -> HAPI_MaterialInfo;
+            fn HAPI_ImageFileFormat_Init(in_: *mut HAPI_ImageFileFormat);
+            fn HAPI_ImageFileFormat_Create() -> HAPI_ImageFileFormat;
+            fn HAPI_ImageInfo_Init(in_: *mut HAPI_ImageInfo);
+            fn HAPI_ImageInfo_Create() -> HAPI_ImageInfo;
+            fn HAPI_Keyframe_Init(in_: *mut HAPI_Keyframe);
+            fn HAPI_Keyframe_Create() -> HAPI_Keyframe;
+            fn HAPI_VolumeInfo_Init(in_: *mut HAPI_VolumeInfo);
+            fn HAPI_VolumeInfo_Create() -> HAPI_VolumeInfo;
+            fn HAPI_VolumeTileInfo_Init(in_: *mut HAPI_VolumeTileInfo);
+            fn HAPI_VolumeTileInfo_Create() -> HAPI_VolumeTileInfo;
+            fn HAPI_CurveInfo_Init(in_: *mut HAPI_CurveInfo);
+            fn HAPI_CurveInfo_Create() -> HAPI_CurveInfo;
+            fn HAPI_InputCurveInfo_Init(in_: *mut HAPI_InputCurveInfo);
+            fn HAPI_InputCurveInfo_Create() -> HAPI_InputCurveInfo;
+            fn HAPI_Transform_Init(in_: *mut HAPI_Transform);
+            fn HAPI_Transform_Create() -> HAPI_Transform;
+            fn HAPI_TransformEuler_Init(in_: *mut HAPI_TransformEuler);
+            fn HAPI_TransformEuler_Create() -> HAPI_TransformEuler;
+            fn HAPI_Viewport_Init(in_: *mut HAPI_Viewport);
+            fn HAPI_Viewport_Create() -> HAPI_Viewport;
+            fn HAPI_SessionSyncInfo_Init(in_: *mut HAPI_SessionSyncInfo);
+            fn HAPI_SessionSyncInfo_Create() -> HAPI_SessionSyncInfo;
+            fn HAPI_CreateInProcessSession(session: *mut HAPI_Session, session_info: *const HAPI_SessionInfo) -> HapiResult;
+            fn HAPI_StartThriftSocketServer(options: *const HAPI_ThriftServerOptions, port: ::std::os::raw::c_int, process_id: *mut HAPI_ProcessId, log_file: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_CreateThriftSocketSession(session: *mut HAPI_Session, host_name: *const ::std::os::raw::c_char, port: ::std::os::raw::c_int, session_info: *const HAPI_SessionInfo) -> HapiResult;
+            fn HAPI_StartThriftNamedPipeServer(options: *const HAPI_ThriftServerOptions, pipe_name: *const ::std::os::raw::c_char, process_id: *mut HAPI_ProcessId, log_file: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_CreateThriftNamedPipeSession(session: *mut HAPI_Session, pipe_name: *const ::std::os::raw::c_char, session_info: *const HAPI_SessionInfo) -> HapiResult;
+            fn HAPI_StartThriftSharedMemoryServer(options: *const HAPI_ThriftServerOptions, shared_mem_name: *const ::std::os::raw::c_char, process_id: *mut HAPI_ProcessId, log_file: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_CreateThriftSharedMemorySession(session: *mut HAPI_Session, shared_mem_name: *const ::std::os::raw::c_char, session_info: *const HAPI_SessionInfo) -> HapiResult;
+            fn HAPI_BindCustomImplementation(session_type: SessionType, dll_path: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_CreateCustomSession(session_type: SessionType, session_info: *mut ::std::os::raw::c_void, session: *mut HAPI_Session) -> HapiResult;
+            fn HAPI_IsSessionValid(session: *const HAPI_Session) -> HapiResult;
+            fn HAPI_CloseSession(session: *const HAPI_Session) -> HapiResult;
+            fn HAPI_IsInitialized(session: *const HAPI_Session) -> HapiResult;
+            fn HAPI_Initialize(session: *const HAPI_Session, cook_options: *const HAPI_CookOptions, use_cooking_thread: HAPI_Bool, cooking_thread_stack_size: ::std::os::raw::c_int, houdini_environment_files: *const ::std::os::raw::c_char, otl_search_path: *const ::std::os::raw::c_char, dso_search_path: *const ::std::os::raw::c_char, image_dso_search_path: *const ::std::os::raw::c_char, audio_dso_search_path: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_Cleanup(session: *const HAPI_Session) -> HapiResult;
+            fn HAPI_Shutdown(session: *const HAPI_Session) -> HapiResult;
+            fn HAPI_StartPerformanceMonitorProfile(session: *const HAPI_Session, title: *const ::std::os::raw::c_char, profile_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_StopPerformanceMonitorProfile(session: *const HAPI_Session, profile_id: ::std::os::raw::c_int, file_path: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_GetEnvInt(int_type: EnvIntType, value: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetSessionEnvInt(session: *const HAPI_Session, int_type: SessionEnvIntType, value: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetServerEnvInt(session: *const HAPI_Session, variable_name: *const ::std::os::raw::c_char, value: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetServerEnvString(session: *const HAPI_Session, variable_name: *const ::std::os::raw::c_char, value: *mut HAPI_StringHandle) -> HapiResult;
+            fn HAPI_GetServerEnvVarCount(session: *const HAPI_Session, env_count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetServerEnvVarList(session: *const HAPI_Session, values_array: *mut HAPI_StringHandle, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetServerEnvInt(session: *const HAPI_Session, variable_name: *const ::std::os::raw::c_char, value: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetServerEnvString(session: *const HAPI_Session, variable_name: *const ::std::os::raw::c_char, value: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_GetStatus(session: *const HAPI_Session, status_type: StatusType, status: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetStatusStringBufLength(session: *const HAPI_Session, status_type: StatusType, verbosity: StatusVerbosity, buffer_length: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetStatusString(session: *const HAPI_Session, status_type: StatusType, string_value: *mut ::std::os::raw::c_char, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_ComposeNodeCookResult(session: *const HAPI_Session, node_id: HAPI_NodeId, verbosity: StatusVerbosity, buffer_length: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetComposedNodeCookResult(session: *const HAPI_Session, string_value: *mut ::std::os::raw::c_char, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetNodeCookResultLength(session: *const HAPI_Session, node_id: HAPI_NodeId, verbosity: StatusVerbosity, buffer_length: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetNodeCookResult(session: *const HAPI_Session, string_value: *mut ::std::os::raw::c_char, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetMessageNodeCount(session: *const HAPI_Session, node_id: HAPI_NodeId, count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetMessageNodeIds(session: *const HAPI_Session, node_id: HAPI_NodeId, message_node_ids_array: *mut HAPI_NodeId, count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_CheckForSpecificErrors(session: *const HAPI_Session, node_id: HAPI_NodeId, errors_to_look_for: HAPI_ErrorCodeBits, errors_found: *mut HAPI_ErrorCodeBits) -> HapiResult;
+            fn HAPI_ClearConnectionError() -> HapiResult;
+            fn HAPI_GetConnectionErrorLength(buffer_length: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetConnectionError(string_value: *mut ::std::os::raw::c_char, length: ::std::os::raw::c_int, clear: HAPI_Bool) -> HapiResult;
+            fn HAPI_GetCookingTotalCount(session: *const HAPI_Session, count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetCookingCurrentCount(session: *const HAPI_Session, count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_Interrupt(session: *const HAPI_Session) -> HapiResult;
+            fn HAPI_ConvertTransform(session: *const HAPI_Session, transform_in: *const HAPI_TransformEuler, rst_order: RSTOrder, rot_order: XYZOrder, transform_out: *mut HAPI_TransformEuler) -> HapiResult;
+            fn HAPI_ConvertMatrixToQuat(session: *const HAPI_Session, matrix: *const f32, rst_order: RSTOrder, transform_out: *mut HAPI_Transform) -> HapiResult;
+            fn HAPI_ConvertMatrixToEuler(session: *const HAPI_Session, matrix: *const f32, rst_order: RSTOrder, rot_order: XYZOrder, transform_out: *mut HAPI_TransformEuler) -> HapiResult;
+            fn HAPI_ConvertTransformQuatToMatrix(session: *const HAPI_Session, transform: *const HAPI_Transform, matrix: *mut f32) -> HapiResult;
+            fn HAPI_ConvertTransformEulerToMatrix(session: *const HAPI_Session, transform: *const HAPI_TransformEuler, matrix: *mut f32) -> HapiResult;
+            fn HAPI_PythonThreadInterpreterLock(session: *const HAPI_Session, locked: HAPI_Bool) -> HapiResult;
+            fn HAPI_GetStringBufLength(session: *const HAPI_Session, string_handle: HAPI_StringHandle, buffer_length: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetString(session: *const HAPI_Session, string_handle: HAPI_StringHandle, string_value: *mut ::std::os::raw::c_char, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetCustomString(session: *const HAPI_Session, string_value: *const ::std::os::raw::c_char, handle_value: *mut HAPI_StringHandle) -> HapiResult;
+            fn HAPI_RemoveCustomString(session: *const HAPI_Session, string_handle: HAPI_StringHandle) -> HapiResult;
+            fn HAPI_GetStringBatchSize(session: *const HAPI_Session, string_handle_array: *const ::std::os::raw::c_int, string_handle_count: ::std::os::raw::c_int, string_buffer_size: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetStringBatch(session: *const HAPI_Session, char_buffer: *mut ::std::os::raw::c_char, char_array_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetTime(session: *const HAPI_Session, time: *mut f64) -> HapiResult;
+            fn HAPI_SetTime(session: *const HAPI_Session, time: f64) -> HapiResult;
+            fn HAPI_GetUseHoudiniTime(session: *const HAPI_Session, enabled: *mut HAPI_Bool) -> HapiResult;
+            fn HAPI_SetUseHoudiniTime(session: *const HAPI_Session, enabled: HAPI_Bool) -> HapiResult;
+            fn HAPI_GetTimelineOptions(session: *const HAPI_Session, timeline_options: *mut HAPI_TimelineOptions) -> HapiResult;
+            fn HAPI_SetTimelineOptions(session: *const HAPI_Session, timeline_options: *const HAPI_TimelineOptions) -> HapiResult;
+            fn HAPI_GetCompositorOptions(session: *const HAPI_Session, compositor_options: *mut HAPI_CompositorOptions) -> HapiResult;
+            fn HAPI_SetCompositorOptions(session: *const HAPI_Session, compositor_options: *const HAPI_CompositorOptions) -> HapiResult;
+            fn HAPI_LoadAssetLibraryFromFile(session: *const HAPI_Session, file_path: *const ::std::os::raw::c_char, allow_overwrite: HAPI_Bool, library_id: *mut HAPI_AssetLibraryId) -> HapiResult;
+            fn HAPI_LoadAssetLibraryFromMemory(session: *const HAPI_Session, library_buffer: *const ::std::os::raw::c_char, library_buffer_length: ::std::os::raw::c_int, allow_overwrite: HAPI_Bool, library_id: *mut HAPI_AssetLibraryId) -> HapiResult;
+            fn HAPI_GetAvailableAssetCount(session: *const HAPI_Session, library_id: HAPI_AssetLibraryId, asset_count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAvailableAssets(session: *const HAPI_Session, library_id: HAPI_AssetLibraryId, asset_names_array: *mut HAPI_StringHandle, asset_count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAssetInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, asset_info: *mut HAPI_AssetInfo) -> HapiResult;
+            fn HAPI_GetAssetDefinitionParmCounts(session: *const HAPI_Session, library_id: HAPI_AssetLibraryId, asset_name: *const ::std::os::raw::c_char, parm_count: *mut ::std::os::raw::c_int, int_value_count: *mut ::std::os::raw::c_int, float_value_count: *mut ::std::os::raw::c_int, string_value_count: *mut ::std::os::raw::c_int, choice_value_count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAssetDefinitionParmInfos(session: *const HAPI_Session, library_id: HAPI_AssetLibraryId, asset_name: *const ::std::os::raw::c_char, parm_infos_array: *mut HAPI_ParmInfo, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAssetDefinitionParmValues(session: *const HAPI_Session, library_id: HAPI_AssetLibraryId, asset_name: *const ::std::os::raw::c_char, int_values_array: *mut ::std::os::raw::c_int, int_start: ::std::os::raw::c_int, int_length: ::std::os::raw::c_int, float_values_array: *mut f32, float_start: ::std::os::raw::c_int, float_length: ::std::os::raw::c_int, string_evaluate: HAPI_Bool, string_values_array: *mut HAPI_StringHandle, string_start: ::std::os::raw::c_int, string_length: ::std::os::raw::c_int, choice_values_array: *mut HAPI_ParmChoiceInfo, choice_start: ::std::os::raw::c_int, choice_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAssetDefinitionParmTagName(session: *const HAPI_Session, library_id: HAPI_AssetLibraryId, asset_name: *const ::std::os::raw::c_char, parm_id: HAPI_ParmId, tag_index: ::std::os::raw::c_int, tag_name: *mut HAPI_StringHandle) -> HapiResult;
+            fn HAPI_GetAssetDefinitionParmTagValue(session: *const HAPI_Session, library_id: HAPI_AssetLibraryId, asset_name: *const ::std::os::raw::c_char, parm_id: HAPI_ParmId, tag_name: *const ::std::os::raw::c_char, tag_value: *mut HAPI_StringHandle) -> HapiResult;
+            fn HAPI_GetLoadedAssetLibraryCount(session: *const HAPI_Session, count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAssetLibraryIds(session: *const HAPI_Session, asset_library_ids_array: *mut HAPI_AssetLibraryId, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAssetLibraryFilePath(session: *const HAPI_Session, asset_library_id: HAPI_AssetLibraryId, file_path_sh: *mut HAPI_StringHandle) -> HapiResult;
+            fn HAPI_LoadHIPFile(session: *const HAPI_Session, file_name: *const ::std::os::raw::c_char, cook_on_load: HAPI_Bool) -> HapiResult;
+            fn HAPI_MergeHIPFile(session: *const HAPI_Session, file_name: *const ::std::os::raw::c_char, cook_on_load: HAPI_Bool, file_id: *mut HAPI_HIPFileId) -> HapiResult;
+            fn HAPI_SaveHIPFile(session: *const HAPI_Session, file_path: *const ::std::os::raw::c_char, lock_nodes: HAPI_Bool) -> HapiResult;
+            fn HAPI_GetHIPFileNodeCount(session: *const HAPI_Session, id: HAPI_HIPFileId, count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetHIPFileNodeIds(session: *const HAPI_Session, id: HAPI_HIPFileId, node_ids: *mut HAPI_NodeId, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_IsNodeValid(session: *const HAPI_Session, node_id: HAPI_NodeId, unique_node_id: ::std::os::raw::c_int, answer: *mut HAPI_Bool) -> HapiResult;
+            fn HAPI_GetNodeInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, node_info: *mut HAPI_NodeInfo) -> HapiResult;
+            fn HAPI_GetNodePath(session: *const HAPI_Session, node_id: HAPI_NodeId, relative_to_node_id: HAPI_NodeId, path: *mut HAPI_StringHandle) -> HapiResult;
+            fn HAPI_GetManagerNodeId(session: *const HAPI_Session, node_type: NodeType, node_id: *mut HAPI_NodeId) -> HapiResult;
+            fn HAPI_ComposeChildNodeList(session: *const HAPI_Session, parent_node_id: HAPI_NodeId, node_type_filter: HAPI_NodeTypeBits, node_flags_filter: HAPI_NodeFlagsBits, recursive: HAPI_Bool, count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetComposedChildNodeList(session: *const HAPI_Session, parent_node_id: HAPI_NodeId, child_node_ids_array: *mut HAPI_NodeId, count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_CreateNode(session: *const HAPI_Session, parent_node_id: HAPI_NodeId, operator_name: *const ::std::os::raw::c_char, node_label: *const ::std::os::raw::c_char, cook_on_creation: HAPI_Bool, new_node_id: *mut HAPI_NodeId) -> HapiResult;
+            fn HAPI_CreateInputNode(session: *const HAPI_Session, parent_node_id: HAPI_NodeId, node_id: *mut HAPI_NodeId, name: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_CreateInputCurveNode(session: *const HAPI_Session, parent_node_id: HAPI_NodeId, node_id: *mut HAPI_NodeId, name: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_CreateHeightFieldInput(session: *const HAPI_Session, parent_node_id: HAPI_NodeId, name: *const ::std::os::raw::c_char, xsize: ::std::os::raw::c_int, ysize: ::std::os::raw::c_int, voxelsize: f32, sampling: HeightFieldSampling, heightfield_node_id: *mut HAPI_NodeId, height_node_id: *mut HAPI_NodeId, mask_node_id: *mut HAPI_NodeId, merge_node_id: *mut HAPI_NodeId) -> HapiResult;
+            fn HAPI_CreateHeightfieldInputVolumeNode(session: *const HAPI_Session, parent_node_id: HAPI_NodeId, new_node_id: *mut HAPI_NodeId, name: *const ::std::os::raw::c_char, xsize: ::std::os::raw::c_int, ysize: ::std::os::raw::c_int, voxelsize: f32) -> HapiResult;
+            fn HAPI_CookNode(session: *const HAPI_Session, node_id: HAPI_NodeId, cook_options: *const HAPI_CookOptions) -> HapiResult;
+            fn HAPI_DeleteNode(session: *const HAPI_Session, node_id: HAPI_NodeId) -> HapiResult;
+            fn HAPI_RenameNode(session: *const HAPI_Session, node_id: HAPI_NodeId, new_name: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_ConnectNodeInput(session: *const HAPI_Session, node_id: HAPI_NodeId, input_index: ::std::os::raw::c_int, node_id_to_connect: HAPI_NodeId, output_index: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_DisconnectNodeInput(session: *const HAPI_Session, node_id: HAPI_NodeId, input_index: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_QueryNodeInput(session: *const HAPI_Session, node_to_query: HAPI_NodeId, input_index: ::std::os::raw::c_int, connected_node_id: *mut HAPI_NodeId) -> HapiResult;
+            fn HAPI_GetNodeInputName(session: *const HAPI_Session, node_id: HAPI_NodeId, input_idx: ::std::os::raw::c_int, name: *mut HAPI_StringHandle) -> HapiResult;
+            fn HAPI_DisconnectNodeOutputsAt(session: *const HAPI_Session, node_id: HAPI_NodeId, output_index: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_QueryNodeOutputConnectedCount(session: *const HAPI_Session, node_id: HAPI_NodeId, output_idx: ::std::os::raw::c_int, into_subnets: HAPI_Bool, through_dots: HAPI_Bool, connected_count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_QueryNodeOutputConnectedNodes(session: *const HAPI_Session, node_id: HAPI_NodeId, output_idx: ::std::os::raw::c_int, into_subnets: HAPI_Bool, through_dots: HAPI_Bool, connected_node_ids_array: *mut HAPI_NodeId, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetNodeOutputName(session: *const HAPI_Session, node_id: HAPI_NodeId, output_idx: ::std::os::raw::c_int, name: *mut HAPI_StringHandle) -> HapiResult;
+            fn HAPI_GetNodeFromPath(session: *const HAPI_Session, parent_node_id: HAPI_NodeId, path: *const ::std::os::raw::c_char, node_id: *mut HAPI_NodeId) -> HapiResult;
+            fn HAPI_GetOutputNodeId(session: *const HAPI_Session, node_id: HAPI_NodeId, output: ::std::os::raw::c_int, output_node_id: *mut HAPI_NodeId) -> HapiResult;
+            fn HAPI_GetParameters(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_infos_array: *mut HAPI_ParmInfo, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetParmInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_id: HAPI_ParmId, parm_info: *mut HAPI_ParmInfo) -> HapiResult;
+            fn HAPI_GetParmIdFromName(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_name: *const ::std::os::raw::c_char, parm_id: *mut HAPI_ParmId) -> HapiResult;
+            fn HAPI_GetParmInfoFromName(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_name: *const ::std::os::raw::c_char, parm_info: *mut HAPI_ParmInfo) -> HapiResult;
+            fn HAPI_GetParmTagName(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_id: HAPI_ParmId, tag_index: ::std::os::raw::c_int, tag_name: *mut HAPI_StringHandle) -> HapiResult;
+            fn HAPI_GetParmTagValue(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_id: HAPI_ParmId, tag_name: *const ::std::os::raw::c_char, tag_value: *mut HAPI_StringHandle) -> HapiResult;
+            fn HAPI_ParmHasTag(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_id: HAPI_ParmId, tag_name: *const ::std::os::raw::c_char, has_tag: *mut HAPI_Bool) -> HapiResult;
+            fn HAPI_ParmHasExpression(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_name: *const ::std::os::raw::c_char, index: ::std::os::raw::c_int, has_expression: *mut HAPI_Bool) -> HapiResult;
+            fn HAPI_GetParmWithTag(session: *const HAPI_Session, node_id: HAPI_NodeId, tag_name: *const ::std::os::raw::c_char, parm_id: *mut HAPI_ParmId) -> HapiResult;
+            fn HAPI_GetParmExpression(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_name: *const ::std::os::raw::c_char, index: ::std::os::raw::c_int, value: *mut HAPI_StringHandle) -> HapiResult;
+            fn HAPI_RevertParmToDefault(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_name: *const ::std::os::raw::c_char, index: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_RevertParmToDefaults(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_name: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_SetParmExpression(session: *const HAPI_Session, node_id: HAPI_NodeId, value: *const ::std::os::raw::c_char, parm_id: HAPI_ParmId, index: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_RemoveParmExpression(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_id: HAPI_ParmId, index: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetParmIntValue(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_name: *const ::std::os::raw::c_char, index: ::std::os::raw::c_int, value: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetParmIntValues(session: *const HAPI_Session, node_id: HAPI_NodeId, values_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetParmFloatValue(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_name: *const ::std::os::raw::c_char, index: ::std::os::raw::c_int, value: *mut f32) -> HapiResult;
+            fn HAPI_GetParmFloatValues(session: *const HAPI_Session, node_id: HAPI_NodeId, values_array: *mut f32, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetParmStringValue(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_name: *const ::std::os::raw::c_char, index: ::std::os::raw::c_int, evaluate: HAPI_Bool, value: *mut HAPI_StringHandle) -> HapiResult;
+            fn HAPI_GetParmStringValues(session: *const HAPI_Session, node_id: HAPI_NodeId, evaluate: HAPI_Bool, values_array: *mut HAPI_StringHandle, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetParmNodeValue(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_name: *const ::std::os::raw::c_char, value: *mut HAPI_NodeId) -> HapiResult;
+            fn HAPI_GetParmFile(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_name: *const ::std::os::raw::c_char, destination_directory: *const ::std::os::raw::c_char, destination_file_name: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_GetParmChoiceLists(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_choices_array: *mut HAPI_ParmChoiceInfo, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetParmIntValue(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_name: *const ::std::os::raw::c_char, index: ::std::os::raw::c_int, value: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetParmIntValues(session: *const HAPI_Session, node_id: HAPI_NodeId, values_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetParmFloatValue(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_name: *const ::std::os::raw::c_char, index: ::std::os::raw::c_int, value: f32) -> HapiResult;
+            fn HAPI_SetParmFloatValues(session: *const HAPI_Session, node_id: HAPI_NodeId, values_array: *const f32, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetParmStringValue(session: *const HAPI_Session, node_id: HAPI_NodeId, value: *const ::std::os::raw::c_char, parm_id: HAPI_ParmId, index: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetParmNodeValue(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_name: *const ::std::os::raw::c_char, value: HAPI_NodeId) -> HapiResult;
+            fn HAPI_InsertMultiparmInstance(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_id: HAPI_ParmId, instance_position: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_RemoveMultiparmInstance(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_id: HAPI_ParmId, instance_position: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetHandleInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, handle_infos_array: *mut HAPI_HandleInfo, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetHandleBindingInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, handle_index: ::std::os::raw::c_int, handle_binding_infos_array: *mut HAPI_HandleBindingInfo, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetPresetBufLength(session: *const HAPI_Session, node_id: HAPI_NodeId, preset_type: PresetType, preset_name: *const ::std::os::raw::c_char, buffer_length: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetPreset(session: *const HAPI_Session, node_id: HAPI_NodeId, buffer: *mut ::std::os::raw::c_char, buffer_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetPreset(session: *const HAPI_Session, node_id: HAPI_NodeId, preset_type: PresetType, preset_name: *const ::std::os::raw::c_char, buffer: *const ::std::os::raw::c_char, buffer_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetPresetCount(session: *const HAPI_Session, buffer: *const ::std::os::raw::c_char, buffer_length: ::std::os::raw::c_int, count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetPresetNames(session: *const HAPI_Session, buffer: *const ::std::os::raw::c_char, buffer_length: ::std::os::raw::c_int, preset_names_array: *mut HAPI_StringHandle, preset_names_count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetObjectInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, object_info: *mut HAPI_ObjectInfo) -> HapiResult;
+            fn HAPI_GetObjectTransform(session: *const HAPI_Session, node_id: HAPI_NodeId, relative_to_node_id: HAPI_NodeId, rst_order: RSTOrder, transform: *mut HAPI_Transform) -> HapiResult;
+            fn HAPI_ComposeObjectList(session: *const HAPI_Session, parent_node_id: HAPI_NodeId, categories: *const ::std::os::raw::c_char, object_count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetComposedObjectList(session: *const HAPI_Session, parent_node_id: HAPI_NodeId, object_infos_array: *mut HAPI_ObjectInfo, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetComposedObjectTransforms(session: *const HAPI_Session, parent_node_id: HAPI_NodeId, rst_order: RSTOrder, transform_array: *mut HAPI_Transform, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetInstancedObjectIds(session: *const HAPI_Session, object_node_id: HAPI_NodeId, instanced_node_id_array: *mut HAPI_NodeId, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetInstanceTransformsOnPart(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, rst_order: RSTOrder, transforms_array: *mut HAPI_Transform, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetObjectTransform(session: *const HAPI_Session, node_id: HAPI_NodeId, trans: *const HAPI_TransformEuler) -> HapiResult;
+            fn HAPI_GetDisplayGeoInfo(session: *const HAPI_Session, object_node_id: HAPI_NodeId, geo_info: *mut HAPI_GeoInfo) -> HapiResult;
+            fn HAPI_GetOutputGeoCount(session: *const HAPI_Session, node_id: HAPI_NodeId, count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetOutputGeoInfos(session: *const HAPI_Session, node_id: HAPI_NodeId, geo_infos_array: *mut HAPI_GeoInfo, count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetGeoInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, geo_info: *mut HAPI_GeoInfo) -> HapiResult;
+            fn HAPI_GetPartInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, part_info: *mut HAPI_PartInfo) -> HapiResult;
+            fn HAPI_GetEdgeCountOfEdgeGroup(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, group_name: *const ::std::os::raw::c_char, edge_count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetFaceCounts(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, face_counts_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetVertexList(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, vertex_list_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, owner: AttributeOwner, attr_info: *mut HAPI_AttributeInfo) -> HapiResult;
+            fn HAPI_GetAttributeNames(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, owner: AttributeOwner, attribute_names_array: *mut HAPI_StringHandle, count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeIntData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, stride: ::std::os::raw::c_int, data_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeIntArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut ::std::os::raw::c_int, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeUInt8Data(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, stride: ::std::os::raw::c_int, data_array: *mut HAPI_UInt8, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeUInt8ArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut HAPI_UInt8, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeInt8Data(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, stride: ::std::os::raw::c_int, data_array: *mut HAPI_Int8, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeInt8ArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut HAPI_Int8, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeInt16Data(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, stride: ::std::os::raw::c_int, data_array: *mut HAPI_Int16, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeInt16ArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut HAPI_Int16, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeInt64Data(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, stride: ::std::os::raw::c_int, data_array: *mut HAPI_Int64, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeInt64ArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut HAPI_Int64, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeFloatData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, stride: ::std::os::raw::c_int, data_array: *mut f32, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeFloatArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut f32, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeFloat64Data(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, stride: ::std::os::raw::c_int, data_array: *mut f64, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeFloat64ArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut f64, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeStringData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_array: *mut HAPI_StringHandle, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeStringArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut HAPI_StringHandle, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeDictionaryData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_array: *mut HAPI_StringHandle, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeDictionaryArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut HAPI_StringHandle, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeIntDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, stride: ::std::os::raw::c_int, data_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeUInt8DataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, stride: ::std::os::raw::c_int, data_array: *mut HAPI_UInt8, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeInt8DataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, stride: ::std::os::raw::c_int, data_array: *mut HAPI_Int8, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeInt16DataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, stride: ::std::os::raw::c_int, data_array: *mut HAPI_Int16, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeInt64DataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, stride: ::std::os::raw::c_int, data_array: *mut HAPI_Int64, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeFloatDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, stride: ::std::os::raw::c_int, data_array: *mut f32, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeFloat64DataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, stride: ::std::os::raw::c_int, data_array: *mut f64, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeStringDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_array: *mut HAPI_StringHandle, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeDictionaryDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_array: *mut HAPI_StringHandle, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeIntArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, attr_name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut ::std::os::raw::c_int, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeUInt8ArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, attr_name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut HAPI_UInt8, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeInt8ArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, attr_name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut HAPI_Int8, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeInt16ArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, attr_name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut HAPI_Int16, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeInt64ArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, attr_name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut HAPI_Int64, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeFloatArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, attr_name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut f32, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeFloat64ArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, attr_name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut f64, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeStringArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, attr_name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut HAPI_StringHandle, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetAttributeDictionaryArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, attr_name: *const ::std::os::raw::c_char, attr_info: *mut HAPI_AttributeInfo, data_fixed_array: *mut HAPI_StringHandle, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetJobStatus(session: *const HAPI_Session, job_id: ::std::os::raw::c_int, job_status: *mut JobStatus) -> HapiResult;
+            fn HAPI_GetGroupNames(session: *const HAPI_Session, node_id: HAPI_NodeId, group_type: GroupType, group_names_array: *mut HAPI_StringHandle, group_count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetGroupMembership(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, group_type: GroupType, group_name: *const ::std::os::raw::c_char, membership_array_all_equal: *mut HAPI_Bool, membership_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetGroupCountOnPackedInstancePart(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, pointGroupCount: *mut ::std::os::raw::c_int, primitiveGroupCount: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetGroupNamesOnPackedInstancePart(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, group_type: GroupType, group_names_array: *mut HAPI_StringHandle, group_count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetGroupMembershipOnPackedInstancePart(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, group_type: GroupType, group_name: *const ::std::os::raw::c_char, membership_array_all_equal: *mut HAPI_Bool, membership_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetInstancedPartIds(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, instanced_parts_array: *mut HAPI_PartId, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetInstancerPartTransforms(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, rst_order: RSTOrder, transforms_array: *mut HAPI_Transform, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetPartInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, part_info: *const HAPI_PartInfo) -> HapiResult;
+            fn HAPI_SetFaceCounts(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, face_counts_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetVertexList(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, vertex_list_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_AddAttribute(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo) -> HapiResult;
+            fn HAPI_DeleteAttribute(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo) -> HapiResult;
+            fn HAPI_SetAttributeIntData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeUInt8Data(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const HAPI_UInt8, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt8Data(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const HAPI_Int8, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt16Data(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const HAPI_Int16, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt64Data(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const HAPI_Int64, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeFloatData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const f32, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeFloat64Data(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const f64, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeStringData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *mut *const ::std::os::raw::c_char, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeIndexedStringData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, string_array: *mut *const ::std::os::raw::c_char, string_count: ::std::os::raw::c_int, indices_array: *const ::std::os::raw::c_int, indices_start: ::std::os::raw::c_int, indices_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeStringUniqueData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const ::std::os::raw::c_char, data_length: ::std::os::raw::c_int, start_index: ::std::os::raw::c_int, num_indices: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeIntUniqueData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const ::std::os::raw::c_int, data_length: ::std::os::raw::c_int, start_index: ::std::os::raw::c_int, num_indices: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeUInt8UniqueData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const HAPI_UInt8, data_length: ::std::os::raw::c_int, start_index: ::std::os::raw::c_int, num_indices: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt8UniqueData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const HAPI_Int8, data_length: ::std::os::raw::c_int, start_index: ::std::os::raw::c_int, num_indices: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt16UniqueData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const HAPI_Int16, data_length: ::std::os::raw::c_int, start_index: ::std::os::raw::c_int, num_indices: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt64UniqueData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const HAPI_Int64, data_length: ::std::os::raw::c_int, start_index: ::std::os::raw::c_int, num_indices: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeFloatUniqueData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const f32, data_length: ::std::os::raw::c_int, start_index: ::std::os::raw::c_int, num_indices: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeFloat64UniqueData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const f64, data_length: ::std::os::raw::c_int, start_index: ::std::os::raw::c_int, num_indices: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeDictionaryData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *mut *const ::std::os::raw::c_char, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeIntArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *const ::std::os::raw::c_int, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeUInt8ArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *const HAPI_UInt8, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt8ArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *const HAPI_Int8, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt16ArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *const HAPI_Int16, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt64ArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *const HAPI_Int64, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeFloatArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *const f32, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeFloat64ArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *const f64, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeStringArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *mut *const ::std::os::raw::c_char, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeDictionaryArrayData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *mut *const ::std::os::raw::c_char, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeIntDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeUInt8DataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const HAPI_UInt8, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt8DataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const HAPI_Int8, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt16DataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const HAPI_Int16, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt64DataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const HAPI_Int64, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeFloatDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const f32, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeFloat64DataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const f64, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeStringDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *mut *const ::std::os::raw::c_char, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeIndexedStringDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, string_array: *mut *const ::std::os::raw::c_char, string_count: ::std::os::raw::c_int, indices_array: *const ::std::os::raw::c_int, indices_start: ::std::os::raw::c_int, indices_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeStringUniqueDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const ::std::os::raw::c_char, data_length: ::std::os::raw::c_int, start_index: ::std::os::raw::c_int, num_indices: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeIntUniqueDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const ::std::os::raw::c_int, data_length: ::std::os::raw::c_int, start_index: ::std::os::raw::c_int, num_indices: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeUInt8UniqueDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const HAPI_UInt8, data_length: ::std::os::raw::c_int, start_index: ::std::os::raw::c_int, num_indices: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt8UniqueDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const HAPI_Int8, data_length: ::std::os::raw::c_int, start_index: ::std::os::raw::c_int, num_indices: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt16UniqueDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const HAPI_Int16, data_length: ::std::os::raw::c_int, start_index: ::std::os::raw::c_int, num_indices: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt64UniqueDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const HAPI_Int64, data_length: ::std::os::raw::c_int, start_index: ::std::os::raw::c_int, num_indices: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeFloatUniqueDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const f32, data_length: ::std::os::raw::c_int, start_index: ::std::os::raw::c_int, num_indices: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeFloat64UniqueDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *const f64, data_length: ::std::os::raw::c_int, start_index: ::std::os::raw::c_int, num_indices: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeDictionaryDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_array: *mut *const ::std::os::raw::c_char, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeIntArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *const ::std::os::raw::c_int, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeUInt8ArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *const HAPI_UInt8, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt8ArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *const HAPI_Int8, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt16ArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *const HAPI_Int16, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeInt64ArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *const HAPI_Int64, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeFloatArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *const f32, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeFloat64ArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *const f64, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeStringArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *mut *const ::std::os::raw::c_char, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAttributeDictionaryArrayDataAsync(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, attr_info: *const HAPI_AttributeInfo, data_fixed_array: *mut *const ::std::os::raw::c_char, data_fixed_length: ::std::os::raw::c_int, sizes_fixed_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, sizes_fixed_length: ::std::os::raw::c_int, job_id: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_AddGroup(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, group_type: GroupType, group_name: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_DeleteGroup(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, group_type: GroupType, group_name: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_SetGroupMembership(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, group_type: GroupType, group_name: *const ::std::os::raw::c_char, membership_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_CommitGeo(session: *const HAPI_Session, node_id: HAPI_NodeId) -> HapiResult;
+            fn HAPI_RevertGeo(session: *const HAPI_Session, node_id: HAPI_NodeId) -> HapiResult;
+            fn HAPI_GetMaterialNodeIdsOnFaces(session: *const HAPI_Session, geometry_node_id: HAPI_NodeId, part_id: HAPI_PartId, are_all_the_same: *mut HAPI_Bool, material_ids_array: *mut HAPI_NodeId, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetMaterialInfo(session: *const HAPI_Session, material_node_id: HAPI_NodeId, material_info: *mut HAPI_MaterialInfo) -> HapiResult;
+            fn HAPI_RenderCOPToImage(session: *const HAPI_Session, cop_node_id: HAPI_NodeId) -> HapiResult;
+            fn HAPI_RenderCOPOutputToImage(session: *const HAPI_Session, cop_node_id: HAPI_NodeId, cop_output_name: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_RenderTextureToImage(session: *const HAPI_Session, material_node_id: HAPI_NodeId, parm_id: HAPI_ParmId) -> HapiResult;
+            fn HAPI_GetImageInfo(session: *const HAPI_Session, material_node_id: HAPI_NodeId, image_info: *mut HAPI_ImageInfo) -> HapiResult;
+            fn HAPI_SetImageInfo(session: *const HAPI_Session, material_node_id: HAPI_NodeId, image_info: *const HAPI_ImageInfo) -> HapiResult;
+            fn HAPI_GetImagePlaneCount(session: *const HAPI_Session, material_node_id: HAPI_NodeId, image_plane_count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetImagePlanes(session: *const HAPI_Session, material_node_id: HAPI_NodeId, image_planes_array: *mut HAPI_StringHandle, image_plane_count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_ExtractImageToFile(session: *const HAPI_Session, material_node_id: HAPI_NodeId, image_file_format_name: *const ::std::os::raw::c_char, image_planes: *const ::std::os::raw::c_char, destination_folder_path: *const ::std::os::raw::c_char, destination_file_name: *const ::std::os::raw::c_char, destination_file_path: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetImageFilePath(session: *const HAPI_Session, material_node_id: HAPI_NodeId, image_file_format_name: *const ::std::os::raw::c_char, image_planes: *const ::std::os::raw::c_char, destination_folder_path: *const ::std::os::raw::c_char, destination_file_name: *const ::std::os::raw::c_char, texture_parm_id: HAPI_ParmId, destination_file_path: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_ExtractImageToMemory(session: *const HAPI_Session, material_node_id: HAPI_NodeId, image_file_format_name: *const ::std::os::raw::c_char, image_planes: *const ::std::os::raw::c_char, buffer_size: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetImageMemoryBuffer(session: *const HAPI_Session, material_node_id: HAPI_NodeId, buffer: *mut ::std::os::raw::c_char, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetSupportedImageFileFormatCount(session: *const HAPI_Session, file_format_count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetSupportedImageFileFormats(session: *const HAPI_Session, formats_array: *mut HAPI_ImageFileFormat, file_format_count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_CreateCOPImage(session: *const HAPI_Session, parent_node_id: HAPI_NodeId, width: ::std::os::raw::c_int, height: ::std::os::raw::c_int, packing: ImagePacking, flip_x: HAPI_Bool, flip_y: HAPI_Bool, data_array: *const f32, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetAnimCurve(session: *const HAPI_Session, node_id: HAPI_NodeId, parm_id: HAPI_ParmId, parm_index: ::std::os::raw::c_int, curve_keyframes_array: *const HAPI_Keyframe, keyframe_count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetTransformAnimCurve(session: *const HAPI_Session, node_id: HAPI_NodeId, trans_comp: TransformComponent, curve_keyframes_array: *const HAPI_Keyframe, keyframe_count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_ResetSimulation(session: *const HAPI_Session, node_id: HAPI_NodeId) -> HapiResult;
+            fn HAPI_GetVolumeInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, volume_info: *mut HAPI_VolumeInfo) -> HapiResult;
+            fn HAPI_GetFirstVolumeTile(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, tile: *mut HAPI_VolumeTileInfo) -> HapiResult;
+            fn HAPI_GetNextVolumeTile(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, tile: *mut HAPI_VolumeTileInfo) -> HapiResult;
+            fn HAPI_GetVolumeVoxelFloatData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, x_index: ::std::os::raw::c_int, y_index: ::std::os::raw::c_int, z_index: ::std::os::raw::c_int, values_array: *mut f32, value_count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetVolumeTileFloatData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, fill_value: f32, tile: *const HAPI_VolumeTileInfo, values_array: *mut f32, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetVolumeVoxelIntData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, x_index: ::std::os::raw::c_int, y_index: ::std::os::raw::c_int, z_index: ::std::os::raw::c_int, values_array: *mut ::std::os::raw::c_int, value_count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetVolumeTileIntData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, fill_value: ::std::os::raw::c_int, tile: *const HAPI_VolumeTileInfo, values_array: *mut ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetHeightFieldData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, values_array: *mut f32, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetVolumeInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, volume_info: *const HAPI_VolumeInfo) -> HapiResult;
+            fn HAPI_SetVolumeTileFloatData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, tile: *const HAPI_VolumeTileInfo, values_array: *const f32, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetVolumeTileIntData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, tile: *const HAPI_VolumeTileInfo, values_array: *const ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetVolumeVoxelFloatData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, x_index: ::std::os::raw::c_int, y_index: ::std::os::raw::c_int, z_index: ::std::os::raw::c_int, values_array: *const f32, value_count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetVolumeVoxelIntData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, x_index: ::std::os::raw::c_int, y_index: ::std::os::raw::c_int, z_index: ::std::os::raw::c_int, values_array: *const ::std::os::raw::c_int, value_count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetVolumeBounds(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, x_min: *mut f32, y_min: *mut f32, z_min: *mut f32, x_max: *mut f32, y_max: *mut f32, z_max: *mut f32, x_center: *mut f32, y_center: *mut f32, z_center: *mut f32) -> HapiResult;
+            fn HAPI_SetHeightFieldData(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, name: *const ::std::os::raw::c_char, values_array: *const f32, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetVolumeVisualInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, visual_info: *mut HAPI_VolumeVisualInfo) -> HapiResult;
+            fn HAPI_GetCurveInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, info: *mut HAPI_CurveInfo) -> HapiResult;
+            fn HAPI_GetCurveCounts(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, counts_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetCurveOrders(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, orders_array: *mut ::std::os::raw::c_int, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetCurveKnots(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, knots_array: *mut f32, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetCurveInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, info: *const HAPI_CurveInfo) -> HapiResult;
+            fn HAPI_SetCurveCounts(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, counts_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetCurveOrders(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, orders_array: *const ::std::os::raw::c_int, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetCurveKnots(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, knots_array: *const f32, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetInputCurveInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, info: *mut HAPI_InputCurveInfo) -> HapiResult;
+            fn HAPI_SetInputCurveInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, info: *const HAPI_InputCurveInfo) -> HapiResult;
+            fn HAPI_SetInputCurvePositions(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, positions_array: *const f32, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetInputCurvePositionsRotationsScales(session: *const HAPI_Session, node_id: HAPI_NodeId, part_id: HAPI_PartId, positions_array: *const f32, positions_start: ::std::os::raw::c_int, positions_length: ::std::os::raw::c_int, rotations_array: *const f32, rotations_start: ::std::os::raw::c_int, rotations_length: ::std::os::raw::c_int, scales_array: *const f32, scales_start: ::std::os::raw::c_int, scales_length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetBoxInfo(session: *const HAPI_Session, geo_node_id: HAPI_NodeId, part_id: HAPI_PartId, box_info: *mut HAPI_BoxInfo) -> HapiResult;
+            fn HAPI_GetSphereInfo(session: *const HAPI_Session, geo_node_id: HAPI_NodeId, part_id: HAPI_PartId, sphere_info: *mut HAPI_SphereInfo) -> HapiResult;
+            fn HAPI_GetActiveCacheCount(session: *const HAPI_Session, active_cache_count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetActiveCacheNames(session: *const HAPI_Session, cache_names_array: *mut HAPI_StringHandle, active_cache_count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetCacheProperty(session: *const HAPI_Session, cache_name: *const ::std::os::raw::c_char, cache_property: CacheProperty, property_value: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetCacheProperty(session: *const HAPI_Session, cache_name: *const ::std::os::raw::c_char, cache_property: CacheProperty, property_value: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SaveGeoToFile(session: *const HAPI_Session, node_id: HAPI_NodeId, file_name: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_LoadGeoFromFile(session: *const HAPI_Session, node_id: HAPI_NodeId, file_name: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_SaveNodeToFile(session: *const HAPI_Session, node_id: HAPI_NodeId, file_name: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_LoadNodeFromFile(session: *const HAPI_Session, file_name: *const ::std::os::raw::c_char, parent_node_id: HAPI_NodeId, node_label: *const ::std::os::raw::c_char, cook_on_load: HAPI_Bool, new_node_id: *mut HAPI_NodeId) -> HapiResult;
+            fn HAPI_GetGeoSize(session: *const HAPI_Session, node_id: HAPI_NodeId, format: *const ::std::os::raw::c_char, size: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SaveGeoToMemory(session: *const HAPI_Session, node_id: HAPI_NodeId, buffer: *mut ::std::os::raw::c_char, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_LoadGeoFromMemory(session: *const HAPI_Session, node_id: HAPI_NodeId, format: *const ::std::os::raw::c_char, buffer: *const ::std::os::raw::c_char, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetNodeDisplay(session: *const HAPI_Session, node_id: HAPI_NodeId, onOff: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetTotalCookCount(session: *const HAPI_Session, node_id: HAPI_NodeId, node_type_filter: HAPI_NodeTypeBits, node_flags_filter: HAPI_NodeFlagsBits, recursive: HAPI_Bool, count: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetSessionSync(session: *const HAPI_Session, enable: HAPI_Bool) -> HapiResult;
+            fn HAPI_GetViewport(session: *const HAPI_Session, viewport: *mut HAPI_Viewport) -> HapiResult;
+            fn HAPI_SetViewport(session: *const HAPI_Session, viewport: *const HAPI_Viewport) -> HapiResult;
+            fn HAPI_GetSessionSyncInfo(session: *const HAPI_Session, session_sync_info: *mut HAPI_SessionSyncInfo) -> HapiResult;
+            fn HAPI_SetSessionSyncInfo(session: *const HAPI_Session, session_sync_info: *const HAPI_SessionSyncInfo) -> HapiResult;
+            fn HAPI_GetPDGGraphContexts(session: *const HAPI_Session, context_names_array: *mut HAPI_StringHandle, context_id_array: *mut HAPI_PDG_GraphContextId, start: ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetPDGGraphContextsCount(session: *const HAPI_Session, num_contexts: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetPDGGraphContextId(session: *const HAPI_Session, top_node_id: HAPI_NodeId, context_id: *mut HAPI_PDG_GraphContextId) -> HapiResult;
+            fn HAPI_CookPDG(session: *const HAPI_Session, cook_node_id: HAPI_NodeId, generate_only: ::std::os::raw::c_int, blocking: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_CookPDGAllOutputs(session: *const HAPI_Session, cook_node_id: HAPI_NodeId, generate_only: ::std::os::raw::c_int, blocking: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetPDGEvents(session: *const HAPI_Session, graph_context_id: HAPI_PDG_GraphContextId, event_array: *mut HAPI_PDG_EventInfo, length: ::std::os::raw::c_int, event_count: *mut ::std::os::raw::c_int, remaining_events: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetPDGState(session: *const HAPI_Session, graph_context_id: HAPI_PDG_GraphContextId, pdg_state: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_CreateWorkitem(session: *const HAPI_Session, node_id: HAPI_NodeId, workitem_id: *mut HAPI_PDG_WorkItemId, name: *const ::std::os::raw::c_char, index: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetWorkitemInfo(session: *const HAPI_Session, graph_context_id: HAPI_PDG_GraphContextId, workitem_id: HAPI_PDG_WorkItemId, workitem_info: *mut HAPI_PDG_WorkItemInfo) -> HapiResult;
+            fn HAPI_SetWorkitemIntData(session: *const HAPI_Session, node_id: HAPI_NodeId, workitem_id: HAPI_PDG_WorkItemId, data_name: *const ::std::os::raw::c_char, values_array: *const ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetWorkitemFloatData(session: *const HAPI_Session, node_id: HAPI_NodeId, workitem_id: HAPI_PDG_WorkItemId, data_name: *const ::std::os::raw::c_char, values_array: *const f32, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetWorkitemStringData(session: *const HAPI_Session, node_id: HAPI_NodeId, workitem_id: HAPI_PDG_WorkItemId, data_name: *const ::std::os::raw::c_char, data_index: ::std::os::raw::c_int, value: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_CommitWorkitems(session: *const HAPI_Session, node_id: HAPI_NodeId) -> HapiResult;
+            fn HAPI_GetNumWorkitems(session: *const HAPI_Session, node_id: HAPI_NodeId, num: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetWorkitems(session: *const HAPI_Session, node_id: HAPI_NodeId, workitem_ids_array: *mut ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetWorkitemDataLength(session: *const HAPI_Session, node_id: HAPI_NodeId, workitem_id: HAPI_PDG_WorkItemId, data_name: *const ::std::os::raw::c_char, length: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetWorkitemIntData(session: *const HAPI_Session, node_id: HAPI_NodeId, workitem_id: HAPI_PDG_WorkItemId, data_name: *const ::std::os::raw::c_char, data_array: *mut ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetWorkitemFloatData(session: *const HAPI_Session, node_id: HAPI_NodeId, workitem_id: HAPI_PDG_WorkItemId, data_name: *const ::std::os::raw::c_char, data_array: *mut f32, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetWorkitemStringData(session: *const HAPI_Session, node_id: HAPI_NodeId, workitem_id: HAPI_PDG_WorkItemId, data_name: *const ::std::os::raw::c_char, data_array: *mut HAPI_StringHandle, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetWorkitemResultInfo(session: *const HAPI_Session, node_id: HAPI_NodeId, workitem_id: HAPI_PDG_WorkItemId, resultinfo_array: *mut HAPI_PDG_WorkItemOutputFile, resultinfo_count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_CreateWorkItem(session: *const HAPI_Session, node_id: HAPI_NodeId, work_item_id: *mut HAPI_PDG_WorkItemId, name: *const ::std::os::raw::c_char, index: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetWorkItemInfo(session: *const HAPI_Session, graph_context_id: HAPI_PDG_GraphContextId, work_item_id: HAPI_PDG_WorkItemId, work_item_info: *mut HAPI_PDG_WorkItemInfo) -> HapiResult;
+            fn HAPI_SetWorkItemIntAttribute(session: *const HAPI_Session, node_id: HAPI_NodeId, work_item_id: HAPI_PDG_WorkItemId, attribute_name: *const ::std::os::raw::c_char, values_array: *const ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetWorkItemFloatAttribute(session: *const HAPI_Session, node_id: HAPI_NodeId, work_item_id: HAPI_PDG_WorkItemId, attribute_name: *const ::std::os::raw::c_char, values_array: *const f32, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_SetWorkItemStringAttribute(session: *const HAPI_Session, node_id: HAPI_NodeId, work_item_id: HAPI_PDG_WorkItemId, attribute_name: *const ::std::os::raw::c_char, data_index: ::std::os::raw::c_int, value: *const ::std::os::raw::c_char) -> HapiResult;
+            fn HAPI_CommitWorkItems(session: *const HAPI_Session, node_id: HAPI_NodeId) -> HapiResult;
+            fn HAPI_GetNumWorkItems(session: *const HAPI_Session, node_id: HAPI_NodeId, num: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetWorkItems(session: *const HAPI_Session, node_id: HAPI_NodeId, work_item_ids_array: *mut ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetWorkItemAttributeSize(session: *const HAPI_Session, node_id: HAPI_NodeId, work_item_id: HAPI_PDG_WorkItemId, attribute_name: *const ::std::os::raw::c_char, length: *mut ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetWorkItemIntAttribute(session: *const HAPI_Session, node_id: HAPI_NodeId, work_item_id: HAPI_PDG_WorkItemId, attribute_name: *const ::std::os::raw::c_char, data_array: *mut ::std::os::raw::c_int, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetWorkItemFloatAttribute(session: *const HAPI_Session, node_id: HAPI_NodeId, work_item_id: HAPI_PDG_WorkItemId, attribute_name: *const ::std::os::raw::c_char, data_array: *mut f32, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetWorkItemStringAttribute(session: *const HAPI_Session, node_id: HAPI_NodeId, work_item_id: HAPI_PDG_WorkItemId, attribute_name: *const ::std::os::raw::c_char, data_array: *mut HAPI_StringHandle, length: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_GetWorkItemOutputFiles(session: *const HAPI_Session, node_id: HAPI_NodeId, work_item_id: HAPI_PDG_WorkItemId, resultinfo_array: *mut HAPI_PDG_WorkItemOutputFile, resultinfo_count: ::std::os::raw::c_int) -> HapiResult;
+            fn HAPI_DirtyPDGNode(session: *const HAPI_Session, node_id: HAPI_NodeId, clean_results: HAPI_Bool) -> HapiResult;
+            fn HAPI_PausePDGCook(session: *const HAPI_Session, graph_context_id: HAPI_PDG_GraphContextId) -> HapiResult;
+            fn HAPI_CancelPDGCook(session: *const HAPI_Session, graph_context_id: HAPI_PDG_GraphContextId) -> HapiResult;
+}
+    };
+}
+
+pub(crate) use hapi_functions;
@@ -3,12 +3,58 @@
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 #[allow(clippy::all)]
-mod bindings;
+pub(crate) mod bindings;
 mod functions;
+mod hapi_functions;
 pub mod structs;
 
+pub(crate) use hapi_functions::hapi_functions;
+
+macro_rules! declare_dispatch {
+    ($(fn $name:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)?;)*) => {
+        $(
+            #[inline]
+            #[allow(unused_mut, clippy::let_unit_value)]
+            pub unsafe fn $name($($arg: $ty),*) $(-> $ret)? {
+                let backend = crate::backend::current();
+                if !crate::stats::Call::enabled() && !crate::replay::Recording::enabled() {
+                    return unsafe { backend.$name($($arg),*) };
+                }
+                let mut call = crate::stats::Call::default();
+                $(crate::stats::observe_arg!(call, $arg, $arg);)*
+                let mut recording = crate::replay::Recording::default();
+                if crate::replay::Recording::enabled() {
+                    $(recording.arg(stringify!($arg), $arg);)*
+                }
+                #[cfg(feature = "tracing")]
+                let _span = {
+                    let span = tracing::trace_span!(
+                        target: "hapi_rs::ffi",
+                        stringify!($name),
+                        node_id = tracing::field::Empty,
+                        bytes = tracing::field::Empty,
+                    );
+                    call.record_span(&span);
+                    span.entered()
+                };
+                let start = std::time::Instant::now();
+                let result = unsafe { backend.$name($($arg),*) };
+                call.finish(stringify!($name), start.elapsed(), &result);
+                recording.finish(stringify!($name), &result);
+                result
+            }
+        )*
+    };
+}
+
+/// Raw Engine API types and functions.
+///
+/// Functions in this module are dispatched to the installed [`crate::backend::HapiBackend`].
+#[allow(clippy::missing_safety_doc, clippy::too_many_arguments)]
 pub mod raw {
     pub use super::bindings::*;
+
+    super::hapi_functions!(declare_dispatch);
 }
 pub(crate) use functions::*;
 pub use structs::*;
@@ -206,7 +206,7 @@ wrap! {
     [get|set|with] shared_memory_buffer_size->sharedMemoryBufferSize->[i64];
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct CompositorOptions(pub(crate) HAPI_CompositorOptions);
 
 wrap! {
@@ -22,7 +22,7 @@ pub struct Geometry {
 }
 
 /// In-memory geometry format
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GeoFormat {
     Geo,
     Bgeo,
@@ -0,0 +1,257 @@
+//! Export node networks as Graphviz DOT or Mermaid diagrams.
+//!
+//! [`HoudiniNode::export_graph`] walks the children of a network and their connections, e.g to
+//! look at the internals of an asset or a network loaded with [`Session::load_hip`](crate::session::Session::load_hip)
+//! without opening Houdini:
+//!
+//! ```no_run
+//! use hapi_rs::graph::GraphFormat;
+//! # fn run(asset: hapi_rs::node::HoudiniNode) -> hapi_rs::Result<()> {
+//! std::fs::write("asset.dot", asset.export_graph(GraphFormat::Dot)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Nodes are labeled with their name and operator type, display and output nodes are highlighted
+//! and nodes with cook errors are outlined in red with the first line of the error.
+//! Nodes outside the network which are connected to its children are drawn dashed with their path.
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write;
+
+use crate::errors::Result;
+use crate::node::{HoudiniNode, NodeHandle, StatusVerbosity};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Graphviz, render with e.g `dot -Tsvg`
+    Dot,
+    /// Mermaid flowchart, renders in Markdown on GitHub and GitLab
+    Mermaid,
+}
+
+struct GraphNode {
+    handle: NodeHandle,
+    label: String,
+    op: Option<String>,
+    display: bool,
+    output: bool,
+    error: Option<String>,
+    external: bool,
+}
+
+impl GraphNode {
+    fn id(&self) -> String {
+        format!("n{}", self.handle.0)
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let mut lines = vec![self.label.clone()];
+        lines.extend(self.op.clone());
+        let flags: Vec<&str> = [(self.display, "display"), (self.output, "output")]
+            .into_iter()
+            .filter_map(|(set, flag)| set.then_some(flag))
+            .collect();
+        if !flags.is_empty() {
+            lines.push(format!("[{}]", flags.join(", ")));
+        }
+        lines.extend(self.error.clone());
+        lines
+    }
+}
+
+// Node ids, ordered for a stable output.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Edge {
+    from: i32,
+    to: i32,
+    input: Option<i32>,
+}
+
+struct Graph {
+    name: String,
+    nodes: Vec<GraphNode>,
+    edges: BTreeSet<Edge>,
+}
+
+fn first_line(text: &str) -> Option<String> {
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(|line| match line.char_indices().nth(80) {
+            Some((end, _)) => format!("{}...", &line[..end]),
+            None => line.to_string(),
+        })
+}
+
+impl Graph {
+    fn collect(network: &HoudiniNode) -> Result<Self> {
+        let children = network.query().to_vec()?;
+        let flagged = |nodes: Vec<HoudiniNode>| -> HashSet<NodeHandle> {
+            nodes.into_iter().map(|node| node.handle).collect()
+        };
+        let display = flagged(network.query().display_only().to_vec()?);
+        let outputs = flagged(network.query().outputs_only().to_vec()?);
+        let mut graph = Graph {
+            name: network.name()?,
+            nodes: Vec::with_capacity(children.len()),
+            edges: BTreeSet::new(),
+        };
+        let mut known: HashSet<NodeHandle> = HashSet::new();
+        for child in &children {
+            let error = child.get_cook_result_string(StatusVerbosity::Errors)?;
+            known.insert(child.handle);
+            graph.nodes.push(GraphNode {
+                handle: child.handle,
+                label: child.name()?,
+                op: Some(child.asset_info()?.full_op_name()?),
+                display: display.contains(&child.handle),
+                output: outputs.contains(&child.handle),
+                error: first_line(&error),
+                external: false,
+            });
+        }
+        let mut add_external = |graph: &mut Graph, node: &HoudiniNode| -> Result<()> {
+            if known.insert(node.handle) {
+                graph.nodes.push(GraphNode {
+                    handle: node.handle,
+                    label: node.path()?,
+                    op: None,
+                    display: false,
+                    output: false,
+                    error: None,
+                    external: true,
+                });
+            }
+            Ok(())
+        };
+        for child in &children {
+            for input in 0..child.info.input_count() {
+                if let Some(source) = child.input_node(input)? {
+                    add_external(&mut graph, &source)?;
+                    graph.edges.insert(Edge {
+                        from: source.handle.0,
+                        to: child.handle.0,
+                        input: Some(input),
+                    });
+                }
+            }
+            for output in 0..child.info.output_count() {
+                for target in child.output_connected_nodes(output, false)? {
+                    // Connections within the network are already added from the inputs.
+                    if children.iter().any(|node| node.handle == target) {
+                        continue;
+                    }
+                    add_external(&mut graph, &target.to_node(&network.session)?)?;
+                    graph.edges.insert(Edge {
+                        from: child.handle.0,
+                        to: target.0,
+                        input: None,
+                    });
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    fn to_dot(&self) -> String {
+        let escape = |text: &str| text.replace('\\', "\\\\").replace('"', "\\\"");
+        let mut dot = String::new();
+        let _ = writeln!(dot, "digraph \"{}\" {{", escape(&self.name));
+        dot.push_str("    node [shape=box, style=rounded];\n");
+        for node in &self.nodes {
+            let label: Vec<String> = node.lines().iter().map(|line| escape(line)).collect();
+            let mut attrs = vec![format!("label=\"{}\"", label.join("\\n"))];
+            match (node.external, node.display) {
+                (true, _) => attrs.push("style=\"rounded,dashed\"".to_string()),
+                (false, true) => {
+                    attrs.push("style=\"rounded,filled\", fillcolor=\"#cfe8ff\"".to_string())
+                }
+                (false, false) => {}
+            }
+            if node.output {
+                attrs.push("peripheries=2".to_string());
+            }
+            if node.error.is_some() {
+                attrs.push("color=red, fontcolor=red".to_string());
+            }
+            let _ = writeln!(dot, "    {} [{}];", node.id(), attrs.join(", "));
+        }
+        for edge in &self.edges {
+            let _ = write!(dot, "    n{} -> n{}", edge.from, edge.to);
+            match edge.input {
+                Some(input) => {
+                    let _ = writeln!(dot, " [label=\"{input}\"];");
+                }
+                None => dot.push_str(";\n"),
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn to_mermaid(&self) -> String {
+        let escape = |text: &str| {
+            text.replace('&', "#amp;")
+                .replace('"', "#quot;")
+                .replace('<', "#lt;")
+                .replace('>', "#gt;")
+        };
+        let mut mermaid = String::from("flowchart TB\n");
+        let mut classes: Vec<(&str, Vec<String>)> = vec![
+            ("display", Vec::new()),
+            ("output", Vec::new()),
+            ("error", Vec::new()),
+            ("external", Vec::new()),
+        ];
+        for node in &self.nodes {
+            let label: Vec<String> = node.lines().iter().map(|line| escape(line)).collect();
+            let _ = writeln!(mermaid, "    {}[\"{}\"]", node.id(), label.join("<br/>"));
+            let flags = [
+                node.display,
+                node.output,
+                node.error.is_some(),
+                node.external,
+            ];
+            for ((_, ids), set) in classes.iter_mut().zip(flags) {
+                if set {
+                    ids.push(node.id());
+                }
+            }
+        }
+        for edge in &self.edges {
+            match edge.input {
+                Some(input) => {
+                    let _ = writeln!(mermaid, "    n{} -->|{input}| n{}", edge.from, edge.to);
+                }
+                None => {
+                    let _ = writeln!(mermaid, "    n{} --> n{}", edge.from, edge.to);
+                }
+            }
+        }
+        let styles = [
+            "fill:#cfe8ff",
+            "stroke-width:3px",
+            "stroke:#d00,color:#d00",
+            "stroke-dasharray:4",
+        ];
+        for ((class, ids), style) in classes.iter().zip(styles) {
+            if !ids.is_empty() {
+                let _ = writeln!(mermaid, "    classDef {class} {style}");
+                let _ = writeln!(mermaid, "    class {} {class}", ids.join(","));
+            }
+        }
+        mermaid
+    }
+}
+
+impl HoudiniNode {
+    /// Render the children of this node and their connections as a diagram, see [`crate::graph`].
+    pub fn export_graph(&self, format: GraphFormat) -> Result<String> {
+        debug_assert!(self.is_valid()?, "Invalid node: {}", self.path()?);
+        let graph = Graph::collect(self)?;
+        Ok(match format {
+            GraphFormat::Dot => graph.to_dot(),
+            GraphFormat::Mermaid => graph.to_mermaid(),
+        })
+    }
+}
@@ -0,0 +1,381 @@
+//! Parser for `houdini.env` files.
+//!
+//! [`SessionOptions::houdini_env_files`](crate::session::SessionOptions::houdini_env_files) passes
+//! the files to the server as is. [`HoudiniEnv`] parses them in Rust to preview and validate what
+//! they set, apply the variables to a session or server, or compare them with the server variables:
+//!
+//! ```no_run
+//! use hapi_rs::houdini_env::HoudiniEnv;
+//! use hapi_rs::server::ServerOptions;
+//! # fn run(session: hapi_rs::session::Session) -> hapi_rs::Result<()> {
+//! let env = HoudiniEnv::from_file("houdini.env")?;
+//! let server_options = env.apply_to_server_options(ServerOptions::shared_memory_with_defaults());
+//! for diff in env.diff(&session)? {
+//!     println!("{diff}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Supported syntax:
+//! - `NAME = value` assignments, the value can be wrapped in double quotes.
+//! - Lines starting with `#` are comments.
+//! - `$NAME` and `${NAME}` are expanded with variables set earlier in the file, then the process
+//!   environment. Unknown variables, e.g `$HIP`, are left for Houdini to expand. `\$` is a literal `$`.
+//! - Variables ending with `PATH` are lists of paths separated with `;`, or `:` on Unix, and are
+//!   set on the server with the same separator. The `&` entry, which Houdini replaces with the default path, is kept as is.
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fmt;
+use std::path::Path;
+
+use crate::errors::{HapiError, Result};
+use crate::server::ServerOptions;
+use crate::session::Session;
+
+/// Value of a variable in a `houdini.env` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvValue {
+    Int(i32),
+    String(String),
+    /// Entries of a `*PATH` variable, including the `&` default path token
+    Path {
+        entries: Vec<String>,
+        /// `;`, or `:` on Unix if the value had no `;`
+        separator: char,
+    },
+}
+
+impl fmt::Display for EnvValue {
+    /// Formats the value as it's set on the server, paths are joined with their separator.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvValue::Int(value) => write!(f, "{value}"),
+            EnvValue::String(value) => f.write_str(value),
+            EnvValue::Path { entries, separator } => {
+                f.write_str(&entries.join(separator.encode_utf8(&mut [0; 4])))
+            }
+        }
+    }
+}
+
+/// Difference between a [`HoudiniEnv`] and the server variables, see [`HoudiniEnv::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvDiff {
+    /// The variable is not set on the server
+    Missing { name: String, expected: String },
+    /// The server has a different value
+    Changed {
+        name: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for EnvDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvDiff::Missing { name, expected } => {
+                write!(f, "{name} is not set, expected {expected:?}")
+            }
+            EnvDiff::Changed {
+                name,
+                expected,
+                actual,
+            } => write!(f, "{name} is {actual:?}, expected {expected:?}"),
+        }
+    }
+}
+
+/// Variables set by one or more `houdini.env` files.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HoudiniEnv {
+    vars: BTreeMap<String, EnvValue>,
+}
+
+impl HoudiniEnv {
+    /// Parse a `houdini.env` file, expanding variables from the process environment.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_files([path])
+    }
+
+    /// Parse multiple files in order, later files can use and override variables of earlier ones.
+    /// Same as the list passed to [`SessionOptions::houdini_env_files`](crate::session::SessionOptions::houdini_env_files).
+    pub fn from_files<I>(files: I) -> Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<Path>,
+    {
+        let mut env = HoudiniEnv::default();
+        for file in files {
+            let file = file.as_ref();
+            let text = std::fs::read_to_string(file)?;
+            env.parse_lines(&text, |name| std::env::var(name).ok())
+                .map_err(|e| HapiError::Internal(format!("{}:{e}", file.display())))?;
+        }
+        Ok(env)
+    }
+
+    /// Parse `houdini.env` syntax, expanding variables from the process environment.
+    pub fn parse(text: &str) -> Result<Self> {
+        Self::parse_with(text, |name| std::env::var(name).ok())
+    }
+
+    /// Parse `houdini.env` syntax, expanding variables not set in `text` with `lookup`.
+    pub fn parse_with(text: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let mut env = HoudiniEnv::default();
+        env.parse_lines(text, lookup)
+            .map_err(|e| HapiError::Internal(format!("houdini.env line {e}")))?;
+        Ok(env)
+    }
+
+    // Errors are prefixed with the line number.
+    fn parse_lines(
+        &mut self,
+        text: &str,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> std::result::Result<(), String> {
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("{}: expected NAME = value, got {line:?}", index + 1))?;
+            let name = name.trim();
+            if !is_var_name(name) {
+                return Err(format!("{}: invalid variable name {name:?}", index + 1));
+            }
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(value);
+            let value = self.expand(value, &lookup);
+            self.vars.insert(name.to_string(), typed_value(name, value));
+        }
+        Ok(())
+    }
+
+    fn expand(&self, value: &str, lookup: &impl Fn(&str) -> Option<String>) -> String {
+        let mut expanded = String::with_capacity(value.len());
+        let mut rest = value;
+        while let Some(pos) = rest.find(['$', '\\']) {
+            expanded.push_str(&rest[..pos]);
+            let tail = &rest[pos..];
+            if let Some(tail) = tail.strip_prefix("\\$") {
+                expanded.push('$');
+                rest = tail;
+                continue;
+            }
+            if let Some(tail) = tail.strip_prefix('\\') {
+                expanded.push('\\');
+                rest = tail;
+                continue;
+            }
+            let (name, reference_len) = match tail[1..].strip_prefix('{') {
+                Some(braced) => match braced.find('}') {
+                    Some(end) => (&braced[..end], end + 3),
+                    None => ("", 0),
+                },
+                None => {
+                    let len = tail[1..]
+                        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                        .unwrap_or(tail.len() - 1);
+                    (&tail[1..1 + len], len + 1)
+                }
+            };
+            let value = is_var_name(name)
+                .then(|| {
+                    self.vars
+                        .get(name)
+                        .map(|value| value.to_string())
+                        .or_else(|| lookup(name))
+                })
+                .flatten();
+            match value {
+                Some(value) => {
+                    expanded.push_str(&value);
+                    rest = &tail[reference_len..];
+                }
+                // Not a variable or unknown, Houdini expands it later.
+                None => {
+                    let len = reference_len.max(1);
+                    expanded.push_str(&tail[..len]);
+                    rest = &tail[len..];
+                }
+            }
+        }
+        expanded.push_str(rest);
+        expanded
+    }
+
+    pub fn get(&self, name: &str) -> Option<&EnvValue> {
+        self.vars.get(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.vars.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+
+    /// Variables sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &EnvValue)> {
+        self.vars.iter().map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// Set the variables on a running server with [`Session::set_server_var`].
+    pub fn apply(&self, session: &Session) -> Result<()> {
+        for (name, value) in &self.vars {
+            match value {
+                EnvValue::Int(value) => session.set_server_var::<i32>(name, value)?,
+                value => session.set_server_var::<str>(name, &value.to_string())?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Add the variables to the environment the server is started with, see
+    /// [`ServerOptions::with_env_variables`]. Variables already in `options` are kept unless
+    /// overridden.
+    pub fn apply_to_server_options(&self, options: ServerOptions) -> ServerOptions {
+        let mut vars: Vec<(OsString, OsString)> = options
+            .env_variables
+            .iter()
+            .flatten()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        vars.extend(
+            self.vars
+                .iter()
+                .map(|(name, value)| (name.into(), value.to_string().into())),
+        );
+        options.with_env_variables(vars.iter())
+    }
+
+    /// Compare with [`Session::get_server_variables`]. Values are compared as the server reports
+    /// them, so entries Houdini expands itself, like `&` or `$HIP`, show up as changed.
+    pub fn diff(&self, session: &Session) -> Result<Vec<EnvDiff>> {
+        let server_vars = session.get_server_variables()?;
+        let server_vars: BTreeMap<&str, &str> = server_vars
+            .iter_str()
+            .filter_map(|var| var.split_once('='))
+            .collect();
+        Ok(self
+            .vars
+            .iter()
+            .filter_map(|(name, value)| {
+                let expected = value.to_string();
+                match server_vars.get(name.as_str()) {
+                    None => Some(EnvDiff::Missing {
+                        name: name.clone(),
+                        expected,
+                    }),
+                    Some(actual) if *actual != expected => Some(EnvDiff::Changed {
+                        name: name.clone(),
+                        expected,
+                        actual: actual.to_string(),
+                    }),
+                    Some(_) => None,
+                }
+            })
+            .collect())
+    }
+}
+
+fn is_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn typed_value(name: &str, value: String) -> EnvValue {
+    if name.ends_with("PATH") {
+        // `:` is only a separator in Unix style lists, `C:/houdini;&` has a drive letter.
+        let separator = if cfg!(windows) || value.contains(';') {
+            ';'
+        } else {
+            ':'
+        };
+        return EnvValue::Path {
+            entries: value
+                .split(separator)
+                .map(str::trim)
+                .filter(|entry| !entry.is_empty())
+                .map(str::to_string)
+                .collect(),
+            separator,
+        };
+    }
+    // Values which don't format back the same, like `0010` or `+5`, stay strings.
+    match value.parse::<i32>() {
+        Ok(int) if int.to_string() == value => EnvValue::Int(int),
+        _ => EnvValue::String(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        (name == "HOME").then(|| "/home/artist".to_string())
+    }
+
+    #[test]
+    fn parse_houdini_env() {
+        let text = r#"
+# Studio settings
+JOB = "$HOME/jobs/show"
+HOUDINI_OTLSCAN_PATH = ${JOB}/otls;&
+HOUDINI_MAXTHREADS=8
+RENDER_DIR = $JOB/render/$HIPNAME
+PRICE = \$5
+"#;
+        let env = HoudiniEnv::parse_with(text, lookup).unwrap();
+        assert_eq!(env.len(), 5);
+        assert_eq!(
+            env.get("JOB"),
+            Some(&EnvValue::String("/home/artist/jobs/show".to_string()))
+        );
+        assert_eq!(
+            env.get("HOUDINI_OTLSCAN_PATH"),
+            Some(&EnvValue::Path {
+                entries: vec!["/home/artist/jobs/show/otls".to_string(), "&".to_string()],
+                separator: ';'
+            })
+        );
+        assert_eq!(env.get("HOUDINI_MAXTHREADS"), Some(&EnvValue::Int(8)));
+        assert_eq!(
+            env.get("RENDER_DIR").unwrap().to_string(),
+            "/home/artist/jobs/show/render/$HIPNAME"
+        );
+        assert_eq!(env.get("PRICE").unwrap().to_string(), "$5");
+    }
+
+    #[test]
+    fn values_keep_their_format() {
+        let text = "SHOT = 0010\nOFFSET = +5\nZERO = -0\nFRAME = -12\nHOUDINI_PATH = /a:/b:&";
+        let env = HoudiniEnv::parse_with(text, lookup).unwrap();
+        for name in ["SHOT", "OFFSET", "ZERO"] {
+            assert!(matches!(env.get(name), Some(EnvValue::String(_))), "{name}");
+        }
+        assert_eq!(env.get("SHOT").unwrap().to_string(), "0010");
+        assert_eq!(env.get("FRAME"), Some(&EnvValue::Int(-12)));
+        #[cfg(unix)]
+        assert_eq!(env.get("HOUDINI_PATH").unwrap().to_string(), "/a:/b:&");
+    }
+
+    #[test]
+    fn parse_errors_have_line_numbers() {
+        let error = HoudiniEnv::parse_with("# comment\nJOB /jobs", lookup).unwrap_err();
+        assert!(error.to_string().contains("line 2"), "{error}");
+        assert!(HoudiniEnv::parse_with("1JOB = /jobs", lookup).is_err());
+    }
+}
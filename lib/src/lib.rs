@@ -69,7 +69,8 @@
 //! environment variables, or timeouts with [`server::ServerOptions`]—see `lib/examples/setup_server.rs` for
 //! an advanced configuration.
 //!
-//! License preference can be set with [`server::ServerOptions::with_license_preference`].
+//! License preference can be set with [`server::ServerOptions::with_license_preference`], or a list of
+//! fallbacks with [`server::ServerOptions::with_license_preferences`].
 //!
 //! Sessions expose helpers that map closely to the HAPI entry points:
 //! - [`session::Session::load_asset_file`] returns an [`asset::AssetLibrary`] so you can instantiate HDAs.
@@ -274,6 +275,9 @@
 //! threaded mode, methods return [`session::CookResult`] so you can inspect the cook-state message even if the
 //! original call succeeded. `lib/examples/node_errors.rs` demonstrates how to read verbose cook logs and status
 //! codes, while `lib/examples/materials.rs` shows how to propagate file IO errors during texture extraction.
+//! [`CookMessage::parse`] turns status and cook result strings into typed records with severity, node path
+//! and error code, [`HapiError::diagnostics`] does the same for the server message of an error and
+//! [`node::HoudiniNode::cook_diagnostics`] collects them for a whole network.
 //!
 //! String-heavy APIs such as parameter values or attribute names rely on [`stringhandle::StringArray`] to batch
 //! conversions and mirror `HAPI_StringHandle` semantics. Use [`session::Session::get_string`] or
@@ -282,21 +286,36 @@
 
 pub mod asset;
 pub mod attribute;
+pub mod backend;
+pub mod batch;
+pub mod cache;
+pub mod cooking;
+pub mod diagnostics;
 pub mod geometry;
+pub mod graph;
+pub mod houdini_env;
 pub mod material;
+pub mod network;
 pub mod node;
 pub mod cop;
 pub mod parameter;
+pub mod path;
+pub mod query;
+pub mod replay;
 pub mod server;
 pub mod session;
+pub mod snapshot;
+pub mod stats;
 pub mod stringhandle;
 pub mod volume;
+pub mod watcher;
 pub mod pdg;
 mod errors;
 mod utils;
 mod ffi;
+mod watchdog;
 
-pub use errors::{HapiError, HapiResult, HapiResultCode, Result};
+pub use errors::{CookMessage, HapiError, HapiResult, HapiResultCode, Result, Severity};
 pub use ffi::enums;
 pub use ffi::raw;
 
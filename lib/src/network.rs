@@ -0,0 +1,617 @@
+//! Declarative definitions of node networks.
+//!
+//! [`NetworkDef::export`] describes the children of a network node: their operator types and names,
+//! input connections, parameter values which differ from the defaults, expressions, multiparm
+//! instance counts and display flags. [`NetworkDef::build`] recreates the children under a node in
+//! any session.
+//!
+//! With the `serde` feature the definitions implement `Serialize` and `Deserialize`, e.g to keep
+//! procedural setups as reviewable JSON or TOML files instead of merging hip files:
+//!
+//! ```no_run
+//! use hapi_rs::network::NetworkDef;
+//! # #[cfg(feature = "serde")]
+//! # fn run(
+//! #     rig: hapi_rs::node::HoudiniNode,
+//! #     target: hapi_rs::node::HoudiniNode,
+//! # ) -> Result<(), Box<dyn std::error::Error>> {
+//! let def = NetworkDef::export(&rig)?;
+//! std::fs::write("rig.toml", toml::to_string(&def)?)?;
+//!
+//! let def: NetworkDef = toml::from_str(&std::fs::read_to_string("rig.toml")?)?;
+//! def.build(&target)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`NetworkBuilder`] declares a network in code. Nodes are wired by name and everything is
+//! validated before the first node is created, the parent is cooked once at the end:
+//!
+//! ```no_run
+//! use hapi_rs::network::NetworkBuilder;
+//! # fn run(geo: hapi_rs::node::HoudiniNode) -> hapi_rs::Result<()> {
+//! let network = NetworkBuilder::new()
+//!     .node("base", "box", |n| n.with_parm("size", [2.0, 1.0, 2.0]))
+//!     .node("lift", "xform", |n| {
+//!         n.with_input(0, "base")
+//!             .with_parm("t", [0.0, 1.0, 0.0])
+//!             .with_display(true)
+//!     })
+//!     .create(&geo)?;
+//! let lift = network.get("lift").unwrap();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Limitations:
+//! - Only the direct children of the network are exported, not the contents of child networks.
+//! - The Engine API doesn't report which output an input is connected to, inputs are always
+//!   connected to the first output.
+//! - Default values are read from reference nodes of the same type, created in a temporary network
+//!   which is deleted after the export. For node categories which can't be created in a manager,
+//!   e.g VOPs, all parameter values are exported.
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use log::debug;
+
+use crate::errors::{ErrorContext, HapiError, Result};
+use crate::node::{HoudiniNode, NodeHandle};
+use crate::parameter::{Parameter, ParmBaseTrait, ParmType};
+use crate::session::{CookResult, Session};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// Children of a network node, see [`NetworkDef::export`].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NetworkDef {
+    /// In creation order
+    pub nodes: Vec<NodeDef>,
+}
+
+/// A node in a [`NetworkDef`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NodeDef {
+    pub name: String,
+    /// Operator name without the category, e.g `box` or `labs::trim::1.0`
+    pub node_type: String,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "is_false"))]
+    pub display: bool,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub inputs: Vec<InputDef>,
+    /// Instance counts of multiparms which differ from the default
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub multiparms: BTreeMap<String, i32>,
+    /// Values which differ from the default, parameters with expressions are left out
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub parms: BTreeMap<String, ParmValue>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub expressions: Vec<ExpressionDef>,
+}
+
+/// An input connection of a [`NodeDef`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InputDef {
+    pub index: i32,
+    /// Input name as reported by [`HoudiniNode::get_input_name`], informational only
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "String::is_empty")
+    )]
+    pub label: String,
+    /// Name of a node in the same network, or the absolute path of a node outside of it
+    pub node: String,
+}
+
+/// A parameter expression of a [`NodeDef`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExpressionDef {
+    pub parm: String,
+    /// Component of the parameter tuple
+    pub index: i32,
+    pub expression: String,
+}
+
+/// Parameter tuple value. Serialized as a plain array, integers are accepted for float parameters.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(untagged))]
+pub enum ParmValue {
+    Int(Vec<i32>),
+    Float(Vec<f32>),
+    String(Vec<String>),
+}
+
+impl NodeDef {
+    pub fn new(name: impl Into<String>, node_type: impl Into<String>) -> Self {
+        NodeDef {
+            name: name.into(),
+            node_type: node_type.into(),
+            display: false,
+            inputs: Vec::new(),
+            multiparms: BTreeMap::new(),
+            parms: BTreeMap::new(),
+            expressions: Vec::new(),
+        }
+    }
+
+    /// Connect input `index` to the node named `node`, see [`InputDef::node`].
+    pub fn with_input(mut self, index: i32, node: impl Into<String>) -> Self {
+        self.inputs.push(InputDef {
+            index,
+            label: String::new(),
+            node: node.into(),
+        });
+        self
+    }
+
+    pub fn with_parm(mut self, name: impl Into<String>, value: impl Into<ParmValue>) -> Self {
+        self.parms.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn with_expression(
+        mut self,
+        parm: impl Into<String>,
+        index: i32,
+        expression: impl Into<String>,
+    ) -> Self {
+        self.expressions.push(ExpressionDef {
+            parm: parm.into(),
+            index,
+            expression: expression.into(),
+        });
+        self
+    }
+
+    pub fn with_multiparm(mut self, name: impl Into<String>, count: i32) -> Self {
+        self.multiparms.insert(name.into(), count);
+        self
+    }
+
+    pub fn with_display(mut self, display: bool) -> Self {
+        self.display = display;
+        self
+    }
+}
+
+impl From<i32> for ParmValue {
+    fn from(value: i32) -> Self {
+        ParmValue::Int(vec![value])
+    }
+}
+
+impl From<bool> for ParmValue {
+    fn from(value: bool) -> Self {
+        ParmValue::Int(vec![value as i32])
+    }
+}
+
+impl From<f32> for ParmValue {
+    fn from(value: f32) -> Self {
+        ParmValue::Float(vec![value])
+    }
+}
+
+impl From<&str> for ParmValue {
+    fn from(value: &str) -> Self {
+        ParmValue::String(vec![value.to_string()])
+    }
+}
+
+impl From<String> for ParmValue {
+    fn from(value: String) -> Self {
+        ParmValue::String(vec![value])
+    }
+}
+
+impl From<Vec<i32>> for ParmValue {
+    fn from(values: Vec<i32>) -> Self {
+        ParmValue::Int(values)
+    }
+}
+
+impl From<Vec<f32>> for ParmValue {
+    fn from(values: Vec<f32>) -> Self {
+        ParmValue::Float(values)
+    }
+}
+
+impl From<Vec<String>> for ParmValue {
+    fn from(values: Vec<String>) -> Self {
+        ParmValue::String(values)
+    }
+}
+
+impl<const N: usize> From<[i32; N]> for ParmValue {
+    fn from(values: [i32; N]) -> Self {
+        ParmValue::Int(values.to_vec())
+    }
+}
+
+impl<const N: usize> From<[f32; N]> for ParmValue {
+    fn from(values: [f32; N]) -> Self {
+        ParmValue::Float(values.to_vec())
+    }
+}
+
+// Everything read from the parameters of a node.
+#[derive(Default)]
+struct ParmState {
+    values: BTreeMap<String, ParmValue>,
+    multiparms: BTreeMap<String, i32>,
+    expressions: BTreeMap<(String, i32), String>,
+}
+
+impl ParmState {
+    fn read(node: &HoudiniNode) -> Result<Self> {
+        let mut state = ParmState::default();
+        for parm in node.parameters()? {
+            if matches!(parm, Parameter::Button(_) | Parameter::Other(_)) {
+                continue;
+            }
+            let name = parm.name()?;
+            let mut has_expression = false;
+            for index in 0..parm.size() {
+                if let Some(expression) = parm.expression(index)? {
+                    state.expressions.insert((name.clone(), index), expression);
+                    has_expression = true;
+                }
+            }
+            let value = match &parm {
+                Parameter::Int(p) if parm.info().parm_type() == ParmType::Multiparmlist => {
+                    state.multiparms.insert(name, p.get(0)?);
+                    continue;
+                }
+                _ if has_expression => continue,
+                Parameter::Int(p) => ParmValue::Int(p.get_array()?),
+                Parameter::Float(p) => ParmValue::Float(p.get_array()?),
+                Parameter::String(p) => ParmValue::String(p.get_array()?),
+                _ => continue,
+            };
+            state.values.insert(name, value);
+        }
+        Ok(state)
+    }
+}
+
+// Network outside of the exported one to create reference nodes of `category` in, `None` for
+// categories which can't be created directly in their manager.
+fn scratch_network(session: &Session, category: &str) -> Result<Option<HoudiniNode>> {
+    let operator = match category {
+        "Sop" => "Object/geo",
+        "Object" => "Object/subnet",
+        "Driver" => "Driver/subnet",
+        "Lop" => "Lop/subnet",
+        _ => return Ok(None),
+    };
+    debug!("Creating scratch network {operator}");
+    session
+        .create_node(operator)
+        .map(Some)
+        .with_context(|| format!("Creating scratch network {operator}"))
+}
+
+impl NetworkDef {
+    /// Describe the children of `network`. The network itself is only read, default values
+    /// are read from reference nodes created in temporary networks, see the [module docs](self).
+    pub fn export(network: &HoudiniNode) -> Result<Self> {
+        let mut scratch = HashMap::new();
+        let result = Self::export_nodes(network, &mut scratch);
+        for node in scratch.into_values().flatten() {
+            if let Err(e) = node.delete() {
+                debug!("Could not delete scratch network: {e}");
+            }
+        }
+        result
+    }
+
+    fn export_nodes(
+        network: &HoudiniNode,
+        scratch: &mut HashMap<String, Option<HoudiniNode>>,
+    ) -> Result<Self> {
+        let session = &network.session;
+        let children = network.query().to_vec()?;
+        let display = network
+            .query()
+            .display_only()
+            .iter()?
+            .map(|node| node.map(|node| node.handle))
+            .collect::<Result<HashSet<NodeHandle>>>()?;
+        let mut defaults: HashMap<String, ParmState> = HashMap::new();
+        let mut nodes = Vec::with_capacity(children.len());
+        for child in &children {
+            let name = child.name()?;
+            let full_op_name = child.asset_info()?.full_op_name()?;
+            let (category, node_type) = match full_op_name.split_once('/') {
+                Some((category, op)) => (category.to_string(), op.to_string()),
+                None => (String::new(), full_op_name.clone()),
+            };
+            if !defaults.contains_key(&full_op_name) {
+                if !scratch.contains_key(&category) {
+                    scratch.insert(category.clone(), scratch_network(session, &category)?);
+                }
+                let state = match &scratch[&category] {
+                    Some(parent) => {
+                        debug!("Reading default parameters of {full_op_name}");
+                        let reference = session
+                            .create_node_with(&node_type, parent.handle, None, false)
+                            .with_context(|| format!("Creating reference node {node_type}"))?;
+                        ParmState::read(&reference)?
+                    }
+                    None => {
+                        debug!("No defaults for {full_op_name}, exporting all parameters");
+                        ParmState::default()
+                    }
+                };
+                defaults.insert(full_op_name.clone(), state);
+            }
+            let default = &defaults[&full_op_name];
+            let state = ParmState::read(child).with_context(|| format!("Reading {name}"))?;
+
+            let mut inputs = Vec::new();
+            for index in 0..child.info.input_count() {
+                let Some(source) = child.input_node(index)? else {
+                    continue;
+                };
+                let node = match source.info.parent_id() == network.handle {
+                    true => source.name()?,
+                    false => source.path()?,
+                };
+                inputs.push(InputDef {
+                    index,
+                    label: child.get_input_name(index)?,
+                    node,
+                });
+            }
+            nodes.push(NodeDef {
+                display: display.contains(&child.handle),
+                inputs,
+                multiparms: state
+                    .multiparms
+                    .into_iter()
+                    .filter(|(parm, count)| default.multiparms.get(parm) != Some(count))
+                    .collect(),
+                parms: state
+                    .values
+                    .into_iter()
+                    .filter(|(parm, value)| default.values.get(parm) != Some(value))
+                    .collect(),
+                expressions: state
+                    .expressions
+                    .into_iter()
+                    .filter(|(key, expression)| default.expressions.get(key) != Some(expression))
+                    .map(|((parm, index), expression)| ExpressionDef {
+                        parm,
+                        index,
+                        expression,
+                    })
+                    .collect(),
+                name,
+                node_type,
+            });
+        }
+        Ok(NetworkDef { nodes })
+    }
+
+    /// Check that node names are unique and inputs reference nodes of the network or absolute
+    /// paths. Called by [`NetworkDef::build`] before creating any nodes.
+    pub fn validate(&self) -> Result<()> {
+        let mut errors = Vec::new();
+        let mut names = HashSet::new();
+        for def in &self.nodes {
+            if def.name.is_empty() || def.node_type.is_empty() {
+                errors.push(format!(
+                    "node {:?} of type {:?} needs a name and a type",
+                    def.name, def.node_type
+                ));
+            }
+            if !names.insert(def.name.as_str()) {
+                errors.push(format!("duplicate node name {}", def.name));
+            }
+        }
+        for def in &self.nodes {
+            let mut connected = HashSet::new();
+            for input in &def.inputs {
+                if input.index < 0 || !connected.insert(input.index) {
+                    errors.push(format!("{}: invalid input index {}", def.name, input.index));
+                }
+                if !input.node.starts_with('/') && !names.contains(input.node.as_str()) {
+                    errors.push(format!(
+                        "{}: input {} references unknown node {}",
+                        def.name, input.index, input.node
+                    ));
+                }
+            }
+        }
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(HapiError::Internal(format!(
+                "Invalid network definition: {}",
+                errors.join(", ")
+            ))),
+        }
+    }
+
+    /// Create the nodes under `parent` and connect them, the nodes are not cooked. Returns the new
+    /// nodes in the order of [`NetworkDef::nodes`], their names can differ if `parent` already has
+    /// nodes with these names. If anything fails, the nodes created so far are deleted.
+    pub fn build(&self, parent: &HoudiniNode) -> Result<Vec<HoudiniNode>> {
+        self.validate()?;
+        let mut nodes = Vec::with_capacity(self.nodes.len());
+        match self.build_nodes(parent, &mut nodes) {
+            Ok(()) => Ok(nodes),
+            Err(e) => {
+                for node in nodes {
+                    if let Err(e) = node.delete() {
+                        debug!("Could not delete node of a failed network build: {e}");
+                    }
+                }
+                Err(e)
+            }
+        }
+    }
+
+    fn build_nodes(&self, parent: &HoudiniNode, nodes: &mut Vec<HoudiniNode>) -> Result<()> {
+        let session = &parent.session;
+        for def in &self.nodes {
+            let node = session
+                .create_node_with(&def.node_type, parent.handle, Some(&def.name), false)
+                .with_context(|| format!("Creating node {} of type {}", def.name, def.node_type))?;
+            nodes.push(node);
+            let node = &nodes[nodes.len() - 1];
+            // Instances first, so that their parameters exist.
+            for (name, count) in &def.multiparms {
+                match node.parameter(name)? {
+                    Parameter::Int(parm) => parm.set(0, *count)?,
+                    _ => {
+                        return Err(HapiError::Internal(format!(
+                            "Parameter {name} of {} is not a multiparm",
+                            def.name
+                        )));
+                    }
+                }
+            }
+            for (name, value) in &def.parms {
+                set_value(node, name, value)
+                    .with_context(|| format!("Setting parameter {name} of {}", def.name))?;
+            }
+            for expr in &def.expressions {
+                node.parameter(&expr.parm)?
+                    .set_expression(&expr.expression, expr.index)
+                    .with_context(|| {
+                        format!("Setting expression on {} of {}", expr.parm, def.name)
+                    })?;
+            }
+        }
+        let created: HashMap<&str, NodeHandle> = self
+            .nodes
+            .iter()
+            .zip(nodes.iter())
+            .map(|(def, node)| (def.name.as_str(), node.handle))
+            .collect();
+        for (def, node) in self.nodes.iter().zip(nodes.iter()) {
+            for input in &def.inputs {
+                let source = match created.get(input.node.as_str()) {
+                    Some(handle) => *handle,
+                    None => {
+                        session
+                            .get_node_from_path(&input.node, None)?
+                            .ok_or_else(|| {
+                                HapiError::Internal(format!(
+                                    "Input {} of {}: node {} not found",
+                                    input.index, def.name, input.node
+                                ))
+                            })?
+                            .handle
+                    }
+                };
+                node.connect_input(input.index, source, 0)?;
+            }
+            if def.display {
+                node.set_display_flag(true)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Declare a network in one block and create it with a single cook, see the [module docs](self).
+#[derive(Debug, Clone, Default)]
+pub struct NetworkBuilder {
+    def: NetworkDef,
+}
+
+impl NetworkBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare a node, `configure` sets its inputs, parameters and flags.
+    pub fn node(
+        mut self,
+        name: impl Into<String>,
+        node_type: impl Into<String>,
+        configure: impl FnOnce(NodeDef) -> NodeDef,
+    ) -> Self {
+        self.def
+            .nodes
+            .push(configure(NodeDef::new(name, node_type)));
+        self
+    }
+
+    /// The definition declared so far, e.g to serialize it.
+    pub fn into_def(self) -> NetworkDef {
+        self.def
+    }
+
+    /// Validate the declarations, create the nodes under `parent` and cook `parent` once.
+    pub fn create(self, parent: &HoudiniNode) -> Result<BuiltNetwork> {
+        let nodes = self.def.build(parent)?;
+        let cook_result = parent.cook_blocking()?;
+        let names = self
+            .def
+            .nodes
+            .iter()
+            .zip(&nodes)
+            .map(|(def, node)| (def.name.clone(), node.clone()))
+            .collect();
+        Ok(BuiltNetwork {
+            nodes,
+            names,
+            cook_result,
+        })
+    }
+}
+
+/// Nodes created by [`NetworkBuilder::create`].
+#[derive(Debug, Clone)]
+pub struct BuiltNetwork {
+    /// In declaration order
+    pub nodes: Vec<HoudiniNode>,
+    names: HashMap<String, HoudiniNode>,
+    /// Result of cooking the parent node
+    pub cook_result: CookResult,
+}
+
+impl BuiltNetwork {
+    /// Node by the name it was declared with.
+    pub fn get(&self, name: &str) -> Option<&HoudiniNode> {
+        self.names.get(name)
+    }
+}
+
+fn set_value(node: &HoudiniNode, name: &str, value: &ParmValue) -> Result<()> {
+    match (node.parameter(name)?, value) {
+        (Parameter::Int(parm), ParmValue::Int(values)) => parm.set_array(values),
+        (Parameter::Float(parm), ParmValue::Float(values)) => parm.set_array(values),
+        (Parameter::Float(parm), ParmValue::Int(values)) => {
+            parm.set_array(values.iter().map(|v| *v as f32).collect::<Vec<_>>())
+        }
+        (Parameter::String(parm), ParmValue::String(values)) => parm.set_array(values),
+        (parm, value) => Err(HapiError::Internal(format!(
+            "Value {value:?} does not match the parameter type {:?}",
+            parm.info().parm_type()
+        ))),
+    }
+}
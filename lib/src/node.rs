@@ -14,6 +14,7 @@ use std::{ffi::CString, ffi::OsStr, fmt::Formatter};
 
 use log::debug;
 
+use crate::path::NodePath;
 use crate::pdg::TopNode;
 pub use crate::{
     errors::Result,
@@ -310,6 +311,15 @@ impl NodeHandle {
         crate::ffi::get_node_path(session, *self, to.into())
     }
 
+    /// Returns node's path relative to another node as a [`NodePath`], absolute if `to` is `None`.
+    pub fn node_path_relative(
+        &self,
+        session: &Session,
+        to: impl Into<Option<NodeHandle>>,
+    ) -> Result<NodePath> {
+        NodePath::parse(&self.path_relative(session, to)?)
+    }
+
     /// Check if the handle is valid (node wasn't deleted)
     pub fn is_valid(&self, session: &Session) -> Result<bool> {
         let info = self.info(session)?;
@@ -446,6 +456,11 @@ impl HoudiniNode {
         self.handle.path_relative(&self.session, to)
     }
 
+    /// Returns node's path relative to another node as a [`NodePath`], absolute if `to` is `None`.
+    pub fn node_path_relative(&self, to: impl Into<Option<NodeHandle>>) -> Result<NodePath> {
+        self.handle.node_path_relative(&self.session, to)
+    }
+
     /// Start cooking the node. This is a non-blocking call if the session is async.
     #[must_use = "cook may fail or return errors, check the result"]
     pub fn cook(&self) -> Result<()> {
@@ -461,8 +476,33 @@ impl HoudiniNode {
     pub fn cook_blocking(&self) -> Result<CookResult> {
         debug!("Start cooking node: {}", self.path()?);
         debug_assert!(self.is_valid()?);
-        crate::ffi::cook_node(self, None)?;
-        self.session.cook()
+        self.session.watch("cook", || {
+            crate::ffi::cook_node(self, None)?;
+            self.session.wait_for_cook()
+        })
+    }
+
+    /// Start cooking the node and return a future which resolves when the session is done cooking.
+    /// The future doesn't depend on any particular async runtime.
+    #[cfg(feature = "async-cooking")]
+    pub fn cook_async(&self) -> crate::cooking::CookFuture {
+        debug_assert!(self.is_valid().unwrap_or(false));
+        match crate::ffi::cook_node(self, None) {
+            Ok(()) => self.session.wait_cook(),
+            Err(e) => crate::cooking::CookFuture::failed(self.session.clone(), e),
+        }
+    }
+
+    /// Start cooking the node on a background thread. `on_progress` is called with
+    /// [`CookProgress`](crate::cooking::CookProgress) after every cook state poll until the cook completes.
+    /// The returned handle can wait for the [`CookResult`] or cancel the cook.
+    pub fn cook_with_progress<F>(&self, on_progress: F) -> Result<crate::cooking::CookHandle>
+    where
+        F: FnMut(crate::cooking::CookProgress) + Send + 'static,
+    {
+        debug!("Start cooking node with progress: {}", self.path()?);
+        debug_assert!(self.is_valid()?);
+        crate::cooking::cook_with_progress(self, on_progress)
     }
 
     /// Start cooking with options and wait for result if blocking = true.
@@ -470,12 +510,26 @@ impl HoudiniNode {
     pub fn cook_with_options(&self, options: &CookOptions, blocking: bool) -> Result<CookResult> {
         debug!("Start cooking node: {}", self.path()?);
         debug_assert!(self.is_valid()?);
-        crate::ffi::cook_node(self, Some(options))?;
-        if blocking {
-            self.session.cook()
-        } else {
-            Ok(CookResult::Succeeded)
-        }
+        self.session.watch("cook", || {
+            crate::ffi::cook_node(self, Some(options))?;
+            if blocking {
+                self.session.wait_for_cook()
+            } else {
+                Ok(CookResult::Succeeded)
+            }
+        })
+    }
+
+    /// Cook with options and wait for the result, with a deadline overriding
+    /// [`SessionOptions::call_timeout`](crate::session::SessionOptions::call_timeout).
+    #[must_use = "cook may fail or return errors, check the result"]
+    pub fn cook_with_timeout(
+        &self,
+        options: &CookOptions,
+        timeout: impl Into<crate::session::CallTimeout>,
+    ) -> Result<CookResult> {
+        self.session
+            .with_timeout(timeout, || self.cook_with_options(options, true))
     }
 
     /// How many times this node has been cooked.
@@ -546,7 +600,10 @@ impl HoudiniNode {
     }
 
     /// Get a child node by path.
-    pub fn get_child_by_path(&self, relative_path: &str) -> Result<Option<HoudiniNode>> {
+    pub fn get_child_by_path(
+        &self,
+        relative_path: impl TryInto<NodePath, Error = impl Into<crate::HapiError>>,
+    ) -> Result<Option<HoudiniNode>> {
         self.session
             .get_node_from_path(relative_path, Some(self.handle))
     }
@@ -0,0 +1,408 @@
+//! Typed node paths.
+//!
+//! [`NodePath`] parses and normalizes paths like `/obj/geo1/../geo2/./box1` and is accepted by
+//! [`Session::get_node_from_path`], [`Session::find_parameter_from_path`] and
+//! [`HoudiniNode::get_child_by_path`], as well as plain strings:
+//!
+//! ```no_run
+//! use hapi_rs::path::NodePath;
+//! # fn run(session: hapi_rs::session::Session) -> hapi_rs::Result<()> {
+//! let geo = NodePath::parse("/obj/geo1")?;
+//! let box_node = session.get_node_from_path(geo.join("box1")?, None)?;
+//! assert_eq!(
+//!     NodePath::parse("/obj/geo2/box1")?.relative_to(&geo),
+//!     Some(NodePath::parse("../geo2/box1")?)
+//! );
+//! for output in session.glob_nodes("/obj/*/OUT_*")? {
+//!     println!("{}", output.path()?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! In patterns `*` matches any sequence of characters within a node name and `?` a single one.
+//! [`Session::resolve_node_paths`] and [`Session::glob_nodes`] traverse the network below the
+//! common prefix of the paths once, instead of looking up each path separately.
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::{HapiError, Result};
+use crate::node::{
+    HoudiniNode, ManagerType, NodeFlags, NodeHandle, NodeInfo, NodeType, ToNodeFlagsBits,
+    ToNodeTypeBits,
+};
+use crate::session::Session;
+use crate::stringhandle::{StringHandle, get_string_array};
+use crate::utils::glob_match;
+
+/// Normalized absolute or relative node path, see the [module docs](self).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodePath {
+    absolute: bool,
+    // Relative paths may start with "..", absolute paths never contain it.
+    components: Vec<String>,
+}
+
+impl NodePath {
+    /// The root `/`.
+    pub fn root() -> Self {
+        NodePath {
+            absolute: true,
+            components: Vec::new(),
+        }
+    }
+
+    /// Parse and normalize a path: `.` and empty components are removed and `..` is applied to the
+    /// preceding component. `..` above the root of an absolute path is ignored, like in Houdini.
+    pub fn parse(path: &str) -> Result<Self> {
+        if path.is_empty() {
+            return Err(HapiError::Internal("Node path is empty".to_string()));
+        }
+        if let Some(c) = path.chars().find(|c| c.is_whitespace() || c.is_control()) {
+            return Err(HapiError::Internal(format!(
+                "Invalid character {c:?} in node path \"{path}\""
+            )));
+        }
+        let absolute = path.starts_with('/');
+        let mut components: Vec<String> = Vec::new();
+        for component in path.split('/') {
+            match component {
+                "" | "." => {}
+                ".." => match components.last() {
+                    Some(last) if last != ".." => {
+                        components.pop();
+                    }
+                    _ if absolute => {}
+                    _ => components.push("..".to_string()),
+                },
+                name => components.push(name.to_string()),
+            }
+        }
+        Ok(NodePath {
+            absolute,
+            components,
+        })
+    }
+
+    pub fn is_absolute(&self) -> bool {
+        self.absolute
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.absolute && self.components.is_empty()
+    }
+
+    /// Node names of the path, relative paths may start with `..`.
+    pub fn components(&self) -> impl Iterator<Item = &str> {
+        self.components.iter().map(String::as_str)
+    }
+
+    /// Name of the last node in the path.
+    pub fn name(&self) -> Option<&str> {
+        self.components
+            .last()
+            .map(String::as_str)
+            .filter(|name| *name != "..")
+    }
+
+    /// Path of the parent node, `None` for the root.
+    pub fn parent(&self) -> Option<NodePath> {
+        if self.is_root() {
+            return None;
+        }
+        let mut parent = self.clone();
+        match parent.name() {
+            Some(_) => {
+                parent.components.pop();
+            }
+            None => parent.components.push("..".to_string()),
+        }
+        Some(parent)
+    }
+
+    /// Append a relative `path`, an absolute `path` replaces this one.
+    pub fn join(&self, path: impl TryInto<NodePath, Error = impl Into<HapiError>>) -> Result<Self> {
+        let path: NodePath = path.try_into().map_err(Into::into)?;
+        if path.absolute {
+            return Ok(path);
+        }
+        NodePath::parse(&format!("{self}/{path}"))
+    }
+
+    /// This path relative to `base`, e.g `/obj/geo2/box1` relative to `/obj/geo1` is
+    /// `../geo2/box1`. Both paths must be absolute.
+    pub fn relative_to(&self, base: &NodePath) -> Option<NodePath> {
+        if !self.absolute || !base.absolute {
+            return None;
+        }
+        let common = self
+            .components
+            .iter()
+            .zip(&base.components)
+            .take_while(|(a, b)| a == b)
+            .count();
+        let mut components = vec!["..".to_string(); base.components.len() - common];
+        components.extend_from_slice(&self.components[common..]);
+        Some(NodePath {
+            absolute: false,
+            components,
+        })
+    }
+
+    /// Returns `true` if the path contains `*` or `?` patterns.
+    pub fn is_glob(&self) -> bool {
+        self.components
+            .iter()
+            .any(|component| component.contains(['*', '?']))
+    }
+
+    /// Match `path` against this path as a pattern, a component only matches a single node name.
+    pub fn matches(&self, path: &NodePath) -> bool {
+        self.absolute == path.absolute
+            && self.components.len() == path.components.len()
+            && self
+                .components
+                .iter()
+                .zip(&path.components)
+                .all(|(pattern, name)| glob_match(pattern, name))
+    }
+
+    // Leading components without patterns.
+    fn literal_prefix(&self) -> &[String] {
+        let end = self
+            .components
+            .iter()
+            .position(|component| component.contains(['*', '?']))
+            .unwrap_or(self.components.len());
+        &self.components[..end]
+    }
+}
+
+impl fmt::Display for NodePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.absolute, self.components.is_empty()) {
+            (true, _) => write!(f, "/{}", self.components.join("/")),
+            (false, true) => f.write_str("."),
+            (false, false) => f.write_str(&self.components.join("/")),
+        }
+    }
+}
+
+impl FromStr for NodePath {
+    type Err = HapiError;
+
+    fn from_str(path: &str) -> Result<Self> {
+        NodePath::parse(path)
+    }
+}
+
+impl TryFrom<&str> for NodePath {
+    type Error = HapiError;
+
+    fn try_from(path: &str) -> Result<Self> {
+        NodePath::parse(path)
+    }
+}
+
+impl TryFrom<String> for NodePath {
+    type Error = HapiError;
+
+    fn try_from(path: String) -> Result<Self> {
+        NodePath::parse(&path)
+    }
+}
+
+impl TryFrom<&String> for NodePath {
+    type Error = HapiError;
+
+    fn try_from(path: &String) -> Result<Self> {
+        NodePath::parse(path)
+    }
+}
+
+impl From<&NodePath> for NodePath {
+    fn from(path: &NodePath) -> Self {
+        path.clone()
+    }
+}
+
+// Parent for the manager nodes in the tree.
+const ROOT: NodeHandle = NodeHandle(-1);
+
+const ROOT_NETWORKS: [&str; 3] = ["/stage", "/mat", "/shop"];
+
+// Names of all nodes below a common prefix, fetched once to resolve many paths.
+struct NodeTree {
+    prefix: NodePath,
+    start: Option<NodeHandle>,
+    children: HashMap<NodeHandle, Vec<(String, NodeHandle)>>,
+}
+
+impl NodeTree {
+    fn collect(session: &Session, paths: &[&NodePath]) -> Result<Self> {
+        let mut prefix: Vec<String> = paths
+            .first()
+            .map(|path| path.literal_prefix().to_vec())
+            .unwrap_or_default();
+        for path in paths {
+            let common = prefix
+                .iter()
+                .zip(path.literal_prefix())
+                .take_while(|(a, b)| a == b)
+                .count();
+            prefix.truncate(common);
+        }
+        let prefix = NodePath {
+            absolute: true,
+            components: prefix,
+        };
+        let mut tree = NodeTree {
+            prefix,
+            start: None,
+            children: HashMap::new(),
+        };
+        let _lock = session.lock();
+        let tops = if tree.prefix.is_root() {
+            tree.start = Some(ROOT);
+            let mut tops = [
+                ManagerType::Obj,
+                ManagerType::Chop,
+                ManagerType::Cop,
+                ManagerType::Rop,
+                ManagerType::Top,
+            ]
+            .into_iter()
+            .map(|manager| crate::ffi::get_manager_node(session, NodeType::from(manager)))
+            .map(|id| id.map(NodeHandle))
+            .collect::<Result<Vec<_>>>()?;
+            // Networks without a ManagerType, looked up by path since not every server has them.
+            for root in ROOT_NETWORKS {
+                if let Some(node) = session.get_node_from_path(root, None)?
+                    && !tops.contains(&node.handle)
+                {
+                    tops.push(node.handle);
+                }
+            }
+            tops
+        } else {
+            match session.get_node_from_path(&tree.prefix, None)? {
+                Some(node) => {
+                    tree.start = Some(node.handle);
+                    vec![node.handle]
+                }
+                None => return Ok(tree),
+            }
+        };
+        let mut handles = Vec::new();
+        for top in &tops {
+            if tree.start == Some(ROOT) {
+                handles.push(*top);
+            }
+            handles.extend(
+                crate::ffi::get_compose_child_node_list(
+                    session,
+                    *top,
+                    NodeType::Any.to_bits(),
+                    NodeFlags::Any.to_bits(),
+                    true,
+                )?
+                .into_iter()
+                .map(NodeHandle),
+            );
+        }
+        let infos = handles
+            .iter()
+            .map(|handle| NodeInfo::new(session, *handle))
+            .collect::<Result<Vec<_>>>()?;
+        let names: Vec<StringHandle> = infos
+            .iter()
+            .map(|info| StringHandle(info.0.nameSH))
+            .collect();
+        let names: Vec<String> = get_string_array(&names, session)?.into();
+        for (info, name) in infos.iter().zip(names) {
+            let parent = match tops.contains(&info.node_handle()) {
+                true => ROOT,
+                false => info.parent_id(),
+            };
+            tree.children
+                .entry(parent)
+                .or_default()
+                .push((name, info.node_handle()));
+        }
+        Ok(tree)
+    }
+
+    fn walk(&self, node: NodeHandle, components: &[String], found: &mut Vec<NodeHandle>) {
+        let Some((pattern, rest)) = components.split_first() else {
+            found.push(node);
+            return;
+        };
+        for (name, child) in self.children.get(&node).into_iter().flatten() {
+            if glob_match(pattern, name) {
+                self.walk(*child, rest, found);
+            }
+        }
+    }
+
+    fn find(&self, path: &NodePath) -> Vec<NodeHandle> {
+        let mut found = Vec::new();
+        if let Some(start) = self.start {
+            let rest = &path.components[self.prefix.components.len()..];
+            self.walk(start, rest, &mut found);
+        }
+        found.retain(|handle| *handle != ROOT);
+        found
+    }
+}
+
+fn check_absolute(path: &NodePath) -> Result<()> {
+    match path.absolute {
+        true => Ok(()),
+        false => Err(HapiError::Internal(format!(
+            "Node path \"{path}\" must be absolute"
+        ))),
+    }
+}
+
+impl Session {
+    /// Resolve absolute paths with a single traversal of the network below their common
+    /// prefix, `None` for paths which don't exist. Use [`Session::glob_nodes`] for patterns.
+    pub fn resolve_node_paths(&self, paths: &[NodePath]) -> Result<Vec<Option<HoudiniNode>>> {
+        debug_assert!(self.is_valid());
+        for path in paths {
+            check_absolute(path)?;
+            if path.is_glob() {
+                return Err(HapiError::Internal(format!(
+                    "Node path \"{path}\" is a pattern, use Session::glob_nodes"
+                )));
+            }
+        }
+        let tree = NodeTree::collect(self, &paths.iter().collect::<Vec<_>>())?;
+        paths
+            .iter()
+            .map(|path| {
+                tree.find(path)
+                    .first()
+                    .map(|handle| handle.to_node(self))
+                    .transpose()
+            })
+            .collect()
+    }
+
+    /// Nodes matching an absolute path pattern like `/obj/*/OUT_*`, in network order.
+    /// Only the network below the leading components without patterns is traversed.
+    pub fn glob_nodes(
+        &self,
+        pattern: impl TryInto<NodePath, Error = impl Into<HapiError>>,
+    ) -> Result<Vec<HoudiniNode>> {
+        debug_assert!(self.is_valid());
+        let pattern: NodePath = pattern.try_into().map_err(Into::into)?;
+        check_absolute(&pattern)?;
+        let tree = NodeTree::collect(self, &[&pattern])?;
+        tree.find(&pattern)
+            .iter()
+            .map(|handle| handle.to_node(self))
+            .collect()
+    }
+}
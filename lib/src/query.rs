@@ -0,0 +1,256 @@
+//! Filtered traversal of node networks.
+//!
+//! [`HoudiniNode::query`] and [`ManagerNode::query`] build a [`NodeQuery`]. Type and flag filters
+//! are passed to the composed child node list, so the engine only returns matching ids. The rest
+//! of the filters need [`NodeInfo`], which is fetched lazily in batches while iterating:
+//!
+//! ```no_run
+//! use hapi_rs::node::{NodeFlags, NodeType};
+//! # fn run(session: hapi_rs::session::Session) -> hapi_rs::Result<()> {
+//! let obj = session.get_manager_node(hapi_rs::node::ManagerType::Obj)?;
+//! for node in obj
+//!     .query()
+//!     .recursive(true)
+//!     .node_types(NodeType::Sop)
+//!     .name("*box*")
+//!     .display_only()
+//!     .iter()?
+//! {
+//!     println!("{}", node?.path()?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Name and path patterns are globs, `*` matches any sequence of characters and `?` a single one.
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::errors::Result;
+use crate::node::{
+    HoudiniNode, ManagerNode, NodeFlags, NodeFlagsBits, NodeHandle, NodeInfo, NodeType,
+    NodeTypeBits, ToNodeFlagsBits, ToNodeTypeBits,
+};
+use crate::session::Session;
+use crate::stringhandle::{StringHandle, get_string_array};
+use crate::utils::glob_match;
+
+const DEFAULT_BATCH_SIZE: usize = 64;
+
+/// Builder for a filtered traversal of a node network, see the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct NodeQuery {
+    session: Session,
+    parent: NodeHandle,
+    types: NodeTypeBits,
+    flags: NodeFlagsBits,
+    recursive: bool,
+    name: Option<String>,
+    path: Option<String>,
+    outputs_only: bool,
+    display_only: bool,
+    batch_size: usize,
+}
+
+impl NodeQuery {
+    pub(crate) fn new(session: Session, parent: NodeHandle) -> Self {
+        NodeQuery {
+            session,
+            parent,
+            types: NodeType::Any.to_bits(),
+            flags: NodeFlags::Any.to_bits(),
+            recursive: false,
+            name: None,
+            path: None,
+            outputs_only: false,
+            display_only: false,
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Traverse the whole network below the parent, not only its direct children.
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Only nodes of these types, e.g `NodeType::Sop | NodeType::Obj`.
+    pub fn node_types(mut self, types: impl ToNodeTypeBits) -> Self {
+        self.types = types.to_bits();
+        self
+    }
+
+    /// Only nodes with all of these flags set, e.g `NodeFlags::Editable | NodeFlags::Network`.
+    pub fn flags(mut self, flags: impl ToNodeFlagsBits) -> Self {
+        self.flags = flags.to_bits();
+        self
+    }
+
+    /// Only nodes with a name matching the glob `pattern`.
+    pub fn name(mut self, pattern: impl Into<String>) -> Self {
+        self.name = Some(pattern.into());
+        self
+    }
+
+    /// Only nodes with an absolute path matching the glob `pattern`, e.g `/obj/*/box*`.
+    pub fn path(mut self, pattern: impl Into<String>) -> Self {
+        self.path = Some(pattern.into());
+        self
+    }
+
+    /// Only nodes which are an output geometry of their parent network, like the display SOP of
+    /// an object or the output nodes of a SOP subnet.
+    pub fn outputs_only(mut self) -> Self {
+        self.outputs_only = true;
+        self
+    }
+
+    /// Only nodes with the display flag set.
+    pub fn display_only(mut self) -> Self {
+        self.display_only = true;
+        self
+    }
+
+    /// Number of nodes fetched from the session at a time while iterating.
+    pub fn batch_size(mut self, size: usize) -> Self {
+        self.batch_size = size.max(1);
+        self
+    }
+
+    fn flags_filter(&self) -> NodeFlagsBits {
+        let any = NodeFlags::Any.to_bits();
+        match (self.display_only, self.flags == any) {
+            (false, _) => self.flags,
+            (true, true) => NodeFlags::Display.to_bits(),
+            (true, false) => self.flags | NodeFlags::Display,
+        }
+    }
+
+    /// Run the query. The matching child ids are collected right away, the nodes are created
+    /// lazily while iterating.
+    pub fn iter(&self) -> Result<NodeQueryIter> {
+        debug_assert!(self.session.is_valid());
+        let ids = crate::ffi::get_compose_child_node_list(
+            &self.session,
+            self.parent,
+            self.types,
+            self.flags_filter(),
+            self.recursive,
+        )?;
+        Ok(NodeQueryIter {
+            query: self.clone(),
+            ids: ids.into_iter().map(NodeHandle).collect(),
+            ready: VecDeque::new(),
+            outputs: HashMap::new(),
+            failed: false,
+        })
+    }
+
+    /// First matching node.
+    pub fn first(&self) -> Result<Option<HoudiniNode>> {
+        self.iter()?.next().transpose()
+    }
+
+    /// All matching nodes.
+    pub fn to_vec(&self) -> Result<Vec<HoudiniNode>> {
+        self.iter()?.collect()
+    }
+}
+
+/// Iterator over the nodes matching a [`NodeQuery`]. Stops after the first error.
+#[derive(Debug)]
+pub struct NodeQueryIter {
+    query: NodeQuery,
+    ids: VecDeque<NodeHandle>,
+    ready: VecDeque<HoudiniNode>,
+    // Output geometry nodes per parent network
+    outputs: HashMap<NodeHandle, HashSet<NodeHandle>>,
+    failed: bool,
+}
+
+impl NodeQueryIter {
+    fn is_output(&mut self, info: &NodeInfo) -> Result<bool> {
+        let parent = info.parent_id();
+        if !self.outputs.contains_key(&parent) {
+            let parent_node = parent.to_node(&self.query.session)?;
+            let outputs = crate::ffi::get_output_geos(&parent_node)?
+                .into_iter()
+                .map(|geo| NodeHandle(geo.nodeId))
+                .collect();
+            self.outputs.insert(parent, outputs);
+        }
+        Ok(self.outputs[&parent].contains(&info.node_handle()))
+    }
+
+    fn fetch_batch(&mut self) -> Result<()> {
+        let count = self.query.batch_size.min(self.ids.len());
+        let handles: Vec<NodeHandle> = self.ids.drain(..count).collect();
+        let session = self.query.session.clone();
+        let _lock = session.lock();
+        let infos = handles
+            .iter()
+            .map(|handle| NodeInfo::new(&session, *handle))
+            .collect::<Result<Vec<_>>>()?;
+        let names: Option<Vec<String>> = match self.query.name {
+            Some(_) => {
+                let handles: Vec<StringHandle> = infos
+                    .iter()
+                    .map(|info| StringHandle(info.0.nameSH))
+                    .collect();
+                Some(get_string_array(&handles, &session)?.into())
+            }
+            None => None,
+        };
+        for (index, info) in infos.into_iter().enumerate() {
+            if let (Some(pattern), Some(names)) = (&self.query.name, &names)
+                && !glob_match(pattern, &names[index])
+            {
+                continue;
+            }
+            if self.query.outputs_only && !self.is_output(&info)? {
+                continue;
+            }
+            let handle = info.node_handle();
+            if let Some(pattern) = &self.query.path
+                && !glob_match(pattern, &handle.path(&session)?)
+            {
+                continue;
+            }
+            self.ready
+                .push_back(HoudiniNode::new(session.clone(), handle, Some(info))?);
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for NodeQueryIter {
+    type Item = Result<HoudiniNode>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(node) = self.ready.pop_front() {
+                return Some(Ok(node));
+            }
+            if self.failed || self.ids.is_empty() {
+                return None;
+            }
+            if let Err(e) = self.fetch_batch() {
+                self.failed = true;
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+impl HoudiniNode {
+    /// Query the children of this node, see [`NodeQuery`].
+    pub fn query(&self) -> NodeQuery {
+        NodeQuery::new(self.session.clone(), self.handle)
+    }
+}
+
+impl ManagerNode {
+    /// Query the nodes of this network, see [`NodeQuery`].
+    pub fn query(&self) -> NodeQuery {
+        NodeQuery::new(self.session.clone(), self.handle)
+    }
+}
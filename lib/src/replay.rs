@@ -0,0 +1,1151 @@
+//! Record Engine API calls to a file and replay them against another session.
+//!
+//! With [`SessionOptions::record_calls`](crate::session::SessionOptions::record_calls) every call
+//! made with the session is written to a file, together with its arguments and the contents of
+//! input buffers such as attribute data and parameter values. [`replay`] re-runs the calls which
+//! change the session state against a fresh session, which gives a deterministic reproduction
+//! case for a misbehaving cook:
+//!
+//! ```no_run
+//! use hapi_rs::session::{SessionOptions, new_in_process_session, simple_session};
+//! # fn run() -> hapi_rs::Result<()> {
+//! let options = SessionOptions::default().record_calls("/tmp/repro.hapirec");
+//! let session = new_in_process_session(Some(options))?;
+//! let node = session.create_node("Object/geo")?;
+//! node.cook_blocking()?;
+//! drop(session);
+//!
+//! // SAFETY: the recording was just written by this process.
+//! let report = unsafe { hapi_rs::replay::replay("/tmp/repro.hapirec", &simple_session()?)? };
+//! for mismatch in &report.mismatches {
+//!     println!("{}: {:?} -> {:?}", mismatch.function, mismatch.recorded, mismatch.replayed);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Queries (`HAPI_Get*`, `HAPI_Is*`, ...) are recorded but not replayed, neither are calls which
+//! create, save or close sessions and files. Node, asset library and work item ids returned by
+//! recorded calls are mapped to the ids created on replay, ids obtained from queries, e.g. the
+//! children of an asset, are passed unchanged.
+//!
+//! The Engine reads the input buffers of async calls, e.g. `HAPI_SetAttributeFloatDataAsync`,
+//! until their job is done. Replay keeps them until the replayed job is done, which is checked
+//! where the recording queries the job status and when the replay ends.
+//!
+//! Recordings can only be replayed with the Houdini version they were made with.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::ffi::{CStr, CString, c_char};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use log::{debug, warn};
+use parking_lot::{Mutex, RwLock};
+
+use crate::errors::{HapiError, Result};
+use crate::ffi::hapi_functions;
+use crate::ffi::raw::*;
+use crate::session::Session;
+use crate::stats::{SessionKey, session_key};
+
+const MAGIC: &[u8; 8] = b"HAPIREC1";
+
+/// Argument value of a [`RecordedCall`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Value {
+    /// The session handle, replaced with the target session on replay
+    Session,
+    /// A null pointer
+    Null,
+    /// Integer, boolean or enum argument
+    Int(i64),
+    /// Floating point argument
+    Float(f64),
+    /// Null terminated string
+    String(CString),
+    /// Contents of an input buffer or struct
+    Bytes(Vec<u8>),
+    /// Array of strings, e.g. string attribute data
+    Strings(Vec<CString>),
+    /// Output argument. Holds the returned value for node, asset library, work item and job ids
+    Output(Option<i32>),
+}
+
+/// A single Engine API call read from a recording.
+#[derive(Debug, Clone)]
+pub struct RecordedCall {
+    /// Name of the HAPI function, e.g. `"HAPI_CreateNode"`
+    pub function: String,
+    /// Result of the call, `None` for functions which don't return a [`HapiResult`]
+    pub result: Option<HapiResult>,
+    /// Arguments in declaration order
+    pub args: Vec<Value>,
+}
+
+/// A replayed call which didn't return the recorded result.
+#[derive(Debug, Clone)]
+pub struct ReplayMismatch {
+    /// Position of the call in the recording
+    pub index: usize,
+    pub function: String,
+    pub recorded: Option<HapiResult>,
+    pub replayed: Option<HapiResult>,
+}
+
+/// Outcome of [`replay`].
+#[derive(Debug, Clone, Default)]
+pub struct ReplayReport {
+    /// Number of calls in the recording
+    pub calls: usize,
+    /// Number of calls replayed, queries are skipped
+    pub replayed: usize,
+    /// Calls which returned a different result than when recorded
+    pub mismatches: Vec<ReplayMismatch>,
+}
+
+/// Read all calls from a recording made with
+/// [`SessionOptions::record_calls`](crate::session::SessionOptions::record_calls).
+///
+/// A recording cut short by a crash is read up to the last complete call.
+pub fn read_calls(path: impl AsRef<Path>) -> Result<Vec<RecordedCall>> {
+    let path = path.as_ref();
+    let mut reader = BufReader::new(File::open(path)?);
+    read_header(&mut reader)
+        .map_err(|e| HapiError::Internal(format!("{}: {e}", path.display())))?;
+    let mut calls = Vec::new();
+    while !reader.fill_buf()?.is_empty() {
+        match read_call(&mut reader) {
+            Ok(call) => calls.push(call),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                warn!(
+                    "{} is truncated after {} calls",
+                    path.display(),
+                    calls.len()
+                );
+                break;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(calls)
+}
+
+/// Re-run the calls from a recording against `session`.
+///
+/// Calls are replayed in order regardless of their results, calls which return a different result
+/// than when recorded are listed in [`ReplayReport::mismatches`]. Enum arguments and the sizes of
+/// buffers are checked against the recorded length arguments, a call which doesn't match its
+/// declaration fails the replay.
+///
+/// # Safety
+///
+/// The recording must have been written by [`SessionOptions::record_calls`](crate::session::SessionOptions::record_calls)
+/// and not modified since. Struct arguments, e.g. an [`AttributeInfo`](crate::attribute::AttributeInfo),
+/// are passed to the Engine as recorded, their fields can't be validated. Replaying a corrupted or
+/// crafted file is undefined behavior.
+pub unsafe fn replay(path: impl AsRef<Path>, session: &Session) -> Result<ReplayReport> {
+    let calls = read_calls(path)?;
+    let mut replayer = Replayer {
+        session,
+        function: String::new(),
+        ids: HashMap::new(),
+        buffers: Vec::new(),
+        jobs: HashMap::new(),
+        job: None,
+        outputs: Vec::new(),
+        ints: Vec::new(),
+        tuple_size: None,
+    };
+    let mut report = ReplayReport {
+        calls: calls.len(),
+        ..Default::default()
+    };
+    for (index, call) in calls.into_iter().enumerate() {
+        if let ("HAPI_GetJobStatus", [_, Value::Int(job), ..]) =
+            (call.function.as_str(), call.args.as_slice())
+        {
+            replayer.finish_job(*job as i32);
+        }
+        if !is_replayable(&call.function) {
+            continue;
+        }
+        let result = replayer.call(&call)?;
+        report.replayed += 1;
+        if result != call.result {
+            debug!(
+                "Replayed {} returned {result:?}, recorded {:?}",
+                call.function, call.result
+            );
+            report.mismatches.push(ReplayMismatch {
+                index,
+                function: call.function,
+                recorded: call.result,
+                replayed: result,
+            });
+        }
+    }
+    Ok(report)
+}
+
+/// Whether a call changes the session state and has to be replayed.
+fn is_replayable(function: &str) -> bool {
+    const SKIPPED: &[&str] = &[
+        "Get",
+        "Is",
+        "Compose",
+        "Query",
+        "Convert",
+        "Check",
+        "ParmHas",
+        "Extract",
+        "Render",
+        "Python",
+        "Start",
+        "Stop",
+        "Save",
+        "Initialize",
+        "Cleanup",
+        "Shutdown",
+        "CloseSession",
+        "Interrupt",
+        "ClearConnectionError",
+        "BindCustomImplementation",
+    ];
+    let Some(name) = function.strip_prefix("HAPI_") else {
+        return false;
+    };
+    let creates_session = name.starts_with("Create") && name.ends_with("Session");
+    // Struct helpers like HAPI_CookOptions_Init don't talk to the session.
+    let struct_helper = name.contains('_');
+    !(creates_session || struct_helper || SKIPPED.iter().any(|prefix| name.starts_with(prefix)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum IdKind {
+    Node,
+    Library,
+    WorkItem,
+    Job,
+}
+
+/// Which kind of id an argument holds, if any.
+fn id_kind(function: &str, name: &str) -> Option<IdKind> {
+    match name {
+        "library_id" | "asset_library_id" => Some(IdKind::Library),
+        "work_item_id" | "workitem_id" => Some(IdKind::WorkItem),
+        "job_id" => Some(IdKind::Job),
+        "node_id_to_connect" | "node_to_query" => Some(IdKind::Node),
+        "value" if function == "HAPI_SetParmNodeValue" => Some(IdKind::Node),
+        name if name.ends_with("node_id") => Some(IdKind::Node),
+        _ => None,
+    }
+}
+
+/// Number of elements in a buffer argument, taken from its length argument.
+/// Attribute data lengths are in tuples of `tuple_size` rather than elements.
+fn buffer_len(
+    buffer: &str,
+    int_arg: impl Fn(&str) -> Option<i64>,
+    tuple_size: Option<i32>,
+) -> Option<usize> {
+    let (lengths, in_tuples): (&[&str], bool) = match buffer {
+        "data_array" => (&["data_length", "length"], true),
+        "data_fixed_array" => (&["data_fixed_length"], false),
+        "sizes_fixed_array" => (&["sizes_fixed_length"], false),
+        "values_array" => (&["value_count", "length"], false),
+        "face_counts_array" | "vertex_list_array" | "membership_array" | "counts_array"
+        | "orders_array" | "knots_array" => (&["length"], false),
+        "positions_array" => (&["positions_length", "length"], false),
+        "rotations_array" => (&["rotations_length"], false),
+        "scales_array" => (&["scales_length"], false),
+        "curve_keyframes_array" => (&["keyframe_count"], false),
+        "string_array" => (&["string_count"], false),
+        "indices_array" => (&["indices_length"], false),
+        "string_handle_array" => (&["string_handle_count"], false),
+        "buffer" => (&["buffer_length", "length"], false),
+        "library_buffer" => (&["library_buffer_length"], false),
+        "matrix" => return Some(16),
+        _ => return None,
+    };
+    let (name, length) = lengths
+        .iter()
+        .find_map(|name| int_arg(name).map(|length| (*name, length)))?;
+    let tuple_size = match in_tuples && name == "length" {
+        true => i64::from(tuple_size.unwrap_or(1)),
+        false => 1,
+    };
+    usize::try_from(length.checked_mul(tuple_size)?).ok()
+}
+
+fn result_code<R: Any>(result: &R) -> Option<HapiResult> {
+    (result as &dyn Any).downcast_ref::<HapiResult>().copied()
+}
+
+/// Writes the calls of one session.
+#[derive(Debug)]
+pub(crate) struct Recorder {
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl Recorder {
+    pub(crate) fn create(path: &Path) -> Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        write_header(&mut writer)?;
+        debug!("Recording session calls to {}", path.display());
+        Ok(Recorder {
+            writer: Mutex::new(writer),
+        })
+    }
+
+    fn write(&self, call: &RecordedCall) {
+        let mut writer = self.writer.lock();
+        let mut result = write_call(&mut *writer, call);
+        // Make sure the state changing calls survive a crash.
+        if result.is_ok() && is_replayable(&call.function) {
+            result = writer.flush();
+        }
+        if let Err(e) = result {
+            warn!("Could not record {}: {e}", call.function);
+        }
+    }
+}
+
+static RECORDERS: LazyLock<RwLock<HashMap<SessionKey, Arc<Recorder>>>> =
+    LazyLock::new(Default::default);
+// Number of registered recorders, checked before recording a call.
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+/// Start recording calls made with `session`.
+pub(crate) fn register(session: &HAPI_Session, recorder: Arc<Recorder>) {
+    if RECORDERS
+        .write()
+        .insert(session_key(session), recorder)
+        .is_none()
+    {
+        ACTIVE.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub(crate) fn unregister(session: &HAPI_Session) {
+    if RECORDERS.write().remove(&session_key(session)).is_some() {
+        ACTIVE.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+enum Arg {
+    Int(i64),
+    Float(f64),
+    Session,
+    Const {
+        ptr: *const u8,
+        size: usize,
+        chars: bool,
+    },
+    Strings(*const *const c_char),
+    Output {
+        ptr: *const u8,
+        size: usize,
+    },
+}
+
+/// Arguments of a single API call, built by the functions in [`crate::raw`].
+#[derive(Default)]
+pub(crate) struct Recording {
+    session: Option<SessionKey>,
+    args: Vec<(&'static str, Arg)>,
+    tuple_size: Option<i32>,
+}
+
+impl Recording {
+    /// Whether any session records its calls.
+    #[inline]
+    pub(crate) fn enabled() -> bool {
+        ACTIVE.load(Ordering::Relaxed) > 0
+    }
+
+    pub(crate) fn arg(&mut self, name: &'static str, value: impl RecordArg) {
+        value.record(name, self);
+    }
+
+    /// Write the call if its session is recorded. Buffers are read after the call returns,
+    /// input buffers are still valid and output ids are filled in by then.
+    pub(crate) fn finish<R: Any>(self, function: &'static str, result: &R) {
+        let Some(key) = self.session else {
+            return;
+        };
+        let Some(recorder) = RECORDERS.read().get(&key).cloned() else {
+            return;
+        };
+        let result = result_code(result);
+        let succeeded = result.is_none_or(|r| r == HapiResult::Success);
+        let args = self
+            .args
+            .iter()
+            .map(|(name, arg)| self.resolve(function, name, arg, succeeded))
+            .collect();
+        recorder.write(&RecordedCall {
+            function: function.to_string(),
+            result,
+            args,
+        });
+    }
+
+    fn buffer_len(&self, buffer: &str) -> Option<usize> {
+        let length = |length: &str| {
+            self.args.iter().find_map(|(name, arg)| match arg {
+                Arg::Int(value) if *name == length => Some(*value),
+                _ => None,
+            })
+        };
+        buffer_len(buffer, length, self.tuple_size)
+    }
+
+    fn resolve(&self, function: &str, name: &str, arg: &Arg, succeeded: bool) -> Value {
+        match *arg {
+            Arg::Int(value) => Value::Int(value),
+            Arg::Float(value) => Value::Float(value),
+            Arg::Session => Value::Session,
+            Arg::Const { ptr, .. } if ptr.is_null() => Value::Null,
+            Arg::Const { ptr, size, chars } => match self.buffer_len(name) {
+                // SAFETY: the Engine reads the same number of elements from the buffer.
+                Some(len) => {
+                    Value::Bytes(unsafe { std::slice::from_raw_parts(ptr, len * size) }.to_vec())
+                }
+                // SAFETY: string arguments are null terminated.
+                None if chars => Value::String(unsafe { CStr::from_ptr(ptr.cast()) }.to_owned()),
+                // SAFETY: any other pointer points to a single struct or value.
+                None => Value::Bytes(unsafe { std::slice::from_raw_parts(ptr, size) }.to_vec()),
+            },
+            Arg::Strings(ptr) if ptr.is_null() => Value::Null,
+            Arg::Strings(ptr) => match self.buffer_len(name) {
+                // SAFETY: the Engine reads the same number of strings from the array.
+                Some(len) => Value::Strings(
+                    unsafe { std::slice::from_raw_parts(ptr, len) }
+                        .iter()
+                        .map(|s| match s.is_null() {
+                            true => CString::default(),
+                            // SAFETY: string arguments are null terminated.
+                            false => unsafe { CStr::from_ptr(*s) }.to_owned(),
+                        })
+                        .collect(),
+                ),
+                None => Value::Output(None),
+            },
+            Arg::Output { ptr, .. } if ptr.is_null() => Value::Null,
+            Arg::Output { ptr, size }
+                if succeeded && size == size_of::<i32>() && id_kind(function, name).is_some() =>
+            {
+                // SAFETY: the call succeeded and filled in the id.
+                Value::Output(Some(unsafe { ptr.cast::<i32>().read_unaligned() }))
+            }
+            Arg::Output { .. } => Value::Output(None),
+        }
+    }
+}
+
+/// Argument of a [`crate::raw`] function which can be recorded.
+pub(crate) trait RecordArg {
+    fn record(self, name: &'static str, recording: &mut Recording);
+}
+
+/// Argument of a [`crate::raw`] function which can be created from a recorded [`Value`].
+trait ReplayArg: Sized {
+    fn replay(replayer: &mut Replayer, name: &'static str, value: &Value) -> Result<Self>;
+}
+
+macro_rules! numeric_args {
+    ($variant:ident, $($ty:ty),*) => {
+        $(
+            impl RecordArg for $ty {
+                fn record(self, name: &'static str, recording: &mut Recording) {
+                    recording.args.push((name, Arg::$variant(self as _)));
+                }
+            }
+
+            impl ReplayArg for $ty {
+                fn replay(replayer: &mut Replayer, name: &'static str, value: &Value) -> Result<Self> {
+                    match value {
+                        Value::$variant(value) => Ok(*value as $ty),
+                        _ => Err(replayer.unexpected(name, value)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+numeric_args!(Int, i8, u8, i16, i64);
+numeric_args!(Float, f32, f64);
+
+impl RecordArg for i32 {
+    fn record(self, name: &'static str, recording: &mut Recording) {
+        recording.args.push((name, Arg::Int(self.into())));
+    }
+}
+
+impl ReplayArg for i32 {
+    fn replay(replayer: &mut Replayer, name: &'static str, value: &Value) -> Result<Self> {
+        match value {
+            Value::Int(value) => Ok(replayer.map_id(name, *value as i32)),
+            _ => Err(replayer.unexpected(name, value)),
+        }
+    }
+}
+
+macro_rules! enum_args {
+    ($($ty:ident { $($variant:ident),* $(,)? }),* $(,)?) => {
+        $(
+            impl TryFrom<i32> for $ty {
+                type Error = HapiError;
+
+                fn try_from(value: i32) -> Result<Self> {
+                    $(
+                        if value == $ty::$variant as i32 {
+                            return Ok($ty::$variant);
+                        }
+                    )*
+                    Err(HapiError::Internal(format!(
+                        "{value} is not a valid {}",
+                        stringify!($ty)
+                    )))
+                }
+            }
+
+            impl RecordArg for $ty {
+                fn record(self, name: &'static str, recording: &mut Recording) {
+                    recording.args.push((name, Arg::Int(self as i64)));
+                }
+            }
+
+            impl ReplayArg for $ty {
+                fn replay(replayer: &mut Replayer, name: &'static str, value: &Value) -> Result<Self> {
+                    match value {
+                        Value::Int(value) => i32::try_from(*value)
+                            .ok()
+                            .and_then(|value| $ty::try_from(value).ok())
+                            .ok_or_else(|| replayer.unexpected(name, &Value::Int(*value))),
+                        _ => Err(replayer.unexpected(name, value)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+enum_args!(
+    AttributeOwner {
+        Invalid,
+        Vertex,
+        Point,
+        Prim,
+        Detail,
+        Max
+    },
+    CacheProperty {
+        CachepropCurrent,
+        HasMin,
+        CachepropMin,
+        HasMax,
+        CachepropMax,
+        CullLevel
+    },
+    EnvIntType {
+        EnvintInvalid,
+        HoudiniMajor,
+        HoudiniMinor,
+        HoudiniBuild,
+        HoudiniPatch,
+        EngineMajor,
+        EngineMinor,
+        EngineApi,
+        EnvintMax,
+    },
+    GroupType {
+        Invalid,
+        Point,
+        Prim,
+        Edge,
+        Max
+    },
+    HeightFieldSampling { Center, Corner },
+    ImagePacking {
+        Unknown,
+        Single,
+        Dual,
+        Rgb,
+        Bgr,
+        Rgba,
+        Abgr,
+        Max
+    },
+    NodeType {
+        Any,
+        None,
+        Obj,
+        Sop,
+        Chop,
+        Rop,
+        Shop,
+        Cop2,
+        Vop,
+        Dop,
+        Top,
+        Cop,
+        Lop
+    },
+    PresetType {
+        Invalid,
+        Binary,
+        Idx,
+        Max
+    },
+    RSTOrder {
+        Trs,
+        Tsr,
+        Rts,
+        Rst,
+        Str,
+        Srt
+    },
+    SessionEnvIntType {
+        Invalid,
+        License,
+        Max
+    },
+    SessionType {
+        Inprocess,
+        Thrift,
+        Custom1,
+        Custom2,
+        Custom3,
+        Max
+    },
+    StatusType {
+        CallResult,
+        CookResult,
+        CookState,
+        StatusMax
+    },
+    StatusVerbosity {
+        Statusverbosity0,
+        Statusverbosity1,
+        Statusverbosity2
+    },
+    TransformComponent {
+        Tx,
+        Ty,
+        Tz,
+        Rx,
+        Ry,
+        Rz,
+        Qx,
+        Qy,
+        Qz,
+        Qw,
+        Sx,
+        Sy,
+        Sz
+    },
+    XYZOrder {
+        Xyz,
+        Xzy,
+        Yxz,
+        Yzx,
+        Zxy,
+        Zyx
+    },
+);
+
+impl<T: 'static> RecordArg for *const T {
+    fn record(self, name: &'static str, recording: &mut Recording) {
+        let arg = if TypeId::of::<T>() == TypeId::of::<HAPI_Session>() {
+            // SAFETY: session pointers passed to the API point to a valid handle or are null.
+            recording.session = unsafe { self.cast::<HAPI_Session>().as_ref() }.map(session_key);
+            Arg::Session
+        } else {
+            if TypeId::of::<T>() == TypeId::of::<HAPI_AttributeInfo>() {
+                // SAFETY: attribute info pointers point to a valid struct or are null.
+                recording.tuple_size =
+                    unsafe { self.cast::<HAPI_AttributeInfo>().as_ref() }.map(|i| i.tupleSize);
+            }
+            Arg::Const {
+                ptr: self.cast(),
+                size: size_of::<T>(),
+                chars: TypeId::of::<T>() == TypeId::of::<c_char>(),
+            }
+        };
+        recording.args.push((name, arg));
+    }
+}
+
+impl<T: 'static> RecordArg for *mut T {
+    fn record(self, name: &'static str, recording: &mut Recording) {
+        let arg = if TypeId::of::<T>() == TypeId::of::<*const c_char>() {
+            Arg::Strings(self.cast())
+        } else {
+            Arg::Output {
+                ptr: self.cast(),
+                size: size_of::<T>(),
+            }
+        };
+        recording.args.push((name, arg));
+    }
+}
+
+impl<T: 'static> ReplayArg for *const T {
+    fn replay(replayer: &mut Replayer, name: &'static str, value: &Value) -> Result<Self> {
+        let len = replayer.buffer_len(name);
+        match value {
+            Value::Null => Ok(std::ptr::null()),
+            Value::Session if TypeId::of::<T>() == TypeId::of::<HAPI_Session>() => {
+                Ok(replayer.session.ptr().cast())
+            }
+            Value::String(string)
+                if len.is_none() && TypeId::of::<T>() == TypeId::of::<c_char>() =>
+            {
+                Ok(replayer.keep_string(string.clone()).cast())
+            }
+            // The Engine reads `len` elements, or a single struct or value.
+            Value::Bytes(bytes)
+                if len
+                    .unwrap_or(1)
+                    .checked_mul(size_of::<T>())
+                    .is_some_and(|size| size == bytes.len()) =>
+            {
+                Ok(replayer.buffer(bytes).cast_const().cast())
+            }
+            _ => Err(replayer.unexpected(name, value)),
+        }
+    }
+}
+
+impl<T: 'static> ReplayArg for *mut T {
+    fn replay(replayer: &mut Replayer, name: &'static str, value: &Value) -> Result<Self> {
+        let len = replayer.buffer_len(name);
+        match value {
+            Value::Null => Ok(std::ptr::null_mut()),
+            Value::Strings(strings)
+                if TypeId::of::<T>() == TypeId::of::<*const c_char>()
+                    && len == Some(strings.len()) =>
+            {
+                let pointers: Vec<*const c_char> = strings
+                    .iter()
+                    .map(|s| replayer.keep_string(s.clone()))
+                    .collect();
+                let ptr = pointers.as_ptr().cast_mut();
+                replayer.buffers.push(Box::new(pointers));
+                Ok(ptr.cast())
+            }
+            Value::Output(recorded) => {
+                let size = len
+                    .unwrap_or(1)
+                    .max(1)
+                    .checked_mul(size_of::<T>())
+                    .ok_or_else(|| replayer.unexpected(name, value))?;
+                let ptr = replayer.buffer(&vec![0; size]);
+                let kind = id_kind(&replayer.function, name);
+                if kind == Some(IdKind::Job) {
+                    replayer.job = Some(ptr.cast());
+                }
+                if let (Some(id), Some(kind)) = (recorded, kind)
+                    && size_of::<T>() == size_of::<i32>()
+                {
+                    replayer.outputs.push((kind, *id, ptr.cast()));
+                }
+                Ok(ptr.cast())
+            }
+            _ => Err(replayer.unexpected(name, value)),
+        }
+    }
+}
+
+struct Replayer<'a> {
+    session: &'a Session,
+    function: String,
+    // Recorded ids to ids created on replay.
+    ids: HashMap<(IdKind, i32), i32>,
+    // Keeps the argument buffers of the current call alive.
+    buffers: Vec<Box<dyn Any>>,
+    // Argument buffers of async calls by replayed job id, kept until the job is done.
+    jobs: HashMap<i32, Vec<Box<dyn Any>>>,
+    // Job id output of the current call.
+    job: Option<*const i32>,
+    // Output ids of the current call, with their recorded value.
+    outputs: Vec<(IdKind, i32, *const i32)>,
+    // Integer arguments and the attribute tuple size of the current call, to check buffer sizes.
+    ints: Vec<(&'static str, i64)>,
+    tuple_size: Option<i32>,
+}
+
+impl Replayer<'_> {
+    fn call(&mut self, call: &RecordedCall) -> Result<Option<HapiResult>> {
+        self.function.clone_from(&call.function);
+        let result = replay_call(self, &call.function, &call.args);
+        for (kind, recorded, ptr) in self.outputs.drain(..) {
+            if result
+                .as_ref()
+                .is_ok_and(|r| r.is_none_or(|r| r == HapiResult::Success))
+            {
+                // SAFETY: points into a buffer in `self.buffers`.
+                self.ids.insert((kind, recorded), unsafe { ptr.read() });
+            }
+        }
+        let job = self.job.take().filter(|_| {
+            result
+                .as_ref()
+                .is_ok_and(|r| r.is_none_or(|r| r == HapiResult::Success))
+        });
+        match job {
+            // The Engine reads the buffers of an async call until its job is done.
+            Some(ptr) => {
+                // SAFETY: points into a buffer in `self.buffers`.
+                let job = unsafe { ptr.read() };
+                self.jobs.insert(job, std::mem::take(&mut self.buffers));
+            }
+            None => self.buffers.clear(),
+        }
+        let result = result?;
+        // Threaded sessions cook in the background, wait for the cook like the recorded session did.
+        if self.session.inner.options.threaded
+            && matches!(call.function.as_str(), "HAPI_CookNode" | "HAPI_CreateNode")
+        {
+            self.session.cook()?;
+        }
+        Ok(result)
+    }
+
+    /// Collect the length arguments of a call before its buffers are replayed.
+    fn prepare(&mut self, names: &[&'static str], args: &[Value]) {
+        self.ints.clear();
+        self.tuple_size = None;
+        for (name, value) in names.iter().zip(args) {
+            match value {
+                Value::Int(value) => self.ints.push((name, *value)),
+                Value::Bytes(bytes)
+                    if *name == "attr_info" && bytes.len() == size_of::<HAPI_AttributeInfo>() =>
+                {
+                    let offset = std::mem::offset_of!(HAPI_AttributeInfo, tupleSize);
+                    let tuple_size = bytes[offset..offset + size_of::<i32>()].try_into();
+                    self.tuple_size = tuple_size.ok().map(i32::from_ne_bytes);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn buffer_len(&self, buffer: &str) -> Option<usize> {
+        let length = |length: &str| {
+            self.ints
+                .iter()
+                .find_map(|(name, value)| (*name == length).then_some(*value))
+        };
+        buffer_len(buffer, length, self.tuple_size)
+    }
+
+    /// Wait for the job replayed for the `recorded` job, then release its buffers.
+    fn finish_job(&mut self, recorded: i32) {
+        let Some(job) = self.ids.get(&(IdKind::Job, recorded)).copied() else {
+            return;
+        };
+        self.wait_job(job);
+    }
+
+    fn wait_job(&mut self, job: i32) {
+        let Some(buffers) = self.jobs.remove(&job) else {
+            return;
+        };
+        loop {
+            match self.session.get_job_status(job) {
+                Ok(JobStatus::Running) => std::thread::sleep(Duration::from_millis(1)),
+                Ok(_) => break,
+                Err(e) => {
+                    // The Engine may still read the buffers, leak them instead.
+                    warn!("Could not get the status of replayed job {job}: {e}");
+                    std::mem::forget(buffers);
+                    return;
+                }
+            }
+        }
+    }
+
+    fn map_id(&self, name: &str, id: i32) -> i32 {
+        id_kind(&self.function, name)
+            .and_then(|kind| self.ids.get(&(kind, id)).copied())
+            .unwrap_or(id)
+    }
+
+    /// Copy `bytes` into a buffer aligned for any Engine type.
+    fn buffer(&mut self, bytes: &[u8]) -> *mut u8 {
+        let mut words = vec![0u64; bytes.len().div_ceil(size_of::<u64>()).max(1)];
+        let ptr = words.as_mut_ptr().cast::<u8>();
+        // SAFETY: `words` holds at least `bytes.len()` bytes.
+        unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, bytes.len()) };
+        self.buffers.push(Box::new(words));
+        ptr
+    }
+
+    fn keep_string(&mut self, string: CString) -> *const c_char {
+        let ptr = string.as_ptr();
+        self.buffers.push(Box::new(string));
+        ptr
+    }
+
+    fn unexpected(&self, name: &str, value: &Value) -> HapiError {
+        HapiError::Internal(format!(
+            "Unexpected recorded value for {}({name}): {value:?}",
+            self.function
+        ))
+    }
+}
+
+impl Drop for Replayer<'_> {
+    fn drop(&mut self) {
+        let jobs: Vec<i32> = self.jobs.keys().copied().collect();
+        for job in jobs {
+            self.wait_job(job);
+        }
+    }
+}
+
+macro_rules! declare_replay {
+    ($(fn $name:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)?;)*) => {
+        /// Call `function` with recorded arguments.
+        #[allow(non_snake_case, unused_mut, unused_variables, clippy::let_unit_value)]
+        fn replay_call(replayer: &mut Replayer, function: &str, args: &[Value]) -> Result<Option<HapiResult>> {
+            match function {
+                $(
+                    stringify!($name) => {
+                        replayer.prepare(&[$(stringify!($arg)),*], args);
+                        let mut args = args.iter();
+                        $(
+                            let value = args.next().ok_or_else(|| {
+                                HapiError::Internal(format!("{function} is missing recorded arguments"))
+                            })?;
+                            let $arg = <$ty as ReplayArg>::replay(replayer, stringify!($arg), value)?;
+                        )*
+                        // SAFETY: arguments point to buffers owned by the replayer, sized by the
+                        // length arguments of the call.
+                        let result = unsafe { crate::ffi::raw::$name($($arg),*) };
+                        Ok(result_code(&result))
+                    }
+                )*
+                _ => Err(HapiError::Internal(format!("Unknown function {function} in recording"))),
+            }
+        }
+    };
+}
+
+hapi_functions!(declare_replay);
+
+fn write_varint(writer: &mut impl Write, mut value: u64) -> io::Result<()> {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            return writer.write_all(&[byte]);
+        }
+        writer.write_all(&[byte | 0x80])?;
+    }
+}
+
+fn read_varint(reader: &mut impl Read) -> io::Result<u64> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0];
+        reader.read_exact(&mut byte)?;
+        value |= u64::from(byte[0] & 0x7f) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid varint"))
+}
+
+fn write_bytes(writer: &mut impl Write, bytes: &[u8]) -> io::Result<()> {
+    write_varint(writer, bytes.len() as u64)?;
+    writer.write_all(bytes)
+}
+
+fn read_bytes(reader: &mut impl Read) -> io::Result<Vec<u8>> {
+    let len = read_varint(reader)?;
+    let mut bytes = Vec::new();
+    reader.take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(bytes)
+}
+
+fn read_cstring(reader: &mut impl Read) -> io::Result<CString> {
+    CString::new(read_bytes(reader)?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+const HOUDINI_VERSION: [u32; 3] = [
+    HAPI_VERSION_HOUDINI_MAJOR,
+    HAPI_VERSION_HOUDINI_MINOR,
+    HAPI_VERSION_HOUDINI_BUILD,
+];
+
+fn write_header(writer: &mut impl Write) -> io::Result<()> {
+    writer.write_all(MAGIC)?;
+    for version in HOUDINI_VERSION {
+        write_varint(writer, version.into())?;
+    }
+    Ok(())
+}
+
+fn read_header(reader: &mut impl Read) -> std::result::Result<(), String> {
+    let mut magic = [0; MAGIC.len()];
+    reader
+        .read_exact(&mut magic)
+        .map_err(|e| format!("Not a HAPI call recording: {e}"))?;
+    if &magic != MAGIC {
+        return Err("Not a HAPI call recording".to_string());
+    }
+    let mut version = [0; 3];
+    for part in &mut version {
+        *part = read_varint(reader).map_err(|e| e.to_string())? as u32;
+    }
+    if version != HOUDINI_VERSION {
+        return Err(format!(
+            "Recorded with Houdini {}.{}.{}, but hapi-rs was built for {}.{}.{}",
+            version[0],
+            version[1],
+            version[2],
+            HOUDINI_VERSION[0],
+            HOUDINI_VERSION[1],
+            HOUDINI_VERSION[2]
+        ));
+    }
+    Ok(())
+}
+
+const RESULTS: [HapiResult; 23] = [
+    HapiResult::Success,
+    HapiResult::Failure,
+    HapiResult::AlreadyInitialized,
+    HapiResult::NotInitialized,
+    HapiResult::CantLoadfile,
+    HapiResult::ParmSetFailed,
+    HapiResult::InvalidArgument,
+    HapiResult::CantLoadGeo,
+    HapiResult::CantGeneratePreset,
+    HapiResult::CantLoadPreset,
+    HapiResult::AssetDefAlreadyLoaded,
+    HapiResult::NoLicenseFound,
+    HapiResult::DisallowedNcLicenseFound,
+    HapiResult::DisallowedNcAssetWithCLicense,
+    HapiResult::DisallowedNcAssetWithLcLicense,
+    HapiResult::DisallowedLcAssetWithCLicense,
+    HapiResult::DisallowedHengineindieW3partyPlugin,
+    HapiResult::SharedMemoryBufferOverflow,
+    HapiResult::InvalidSharedMemoryBuffer,
+    HapiResult::AssetInvalid,
+    HapiResult::NodeInvalid,
+    HapiResult::UserInterrupted,
+    HapiResult::InvalidSession,
+];
+
+fn write_call(writer: &mut impl Write, call: &RecordedCall) -> io::Result<()> {
+    write_bytes(writer, call.function.as_bytes())?;
+    write_varint(writer, call.result.map_or(0, |r| r as u64 + 1))?;
+    write_varint(writer, call.args.len() as u64)?;
+    for arg in &call.args {
+        match arg {
+            Value::Session => writer.write_all(&[0])?,
+            Value::Null => writer.write_all(&[1])?,
+            Value::Int(value) => {
+                writer.write_all(&[2])?;
+                // Zigzag encoding keeps small negative values short.
+                write_varint(writer, ((value << 1) ^ (value >> 63)) as u64)?;
+            }
+            Value::Float(value) => {
+                writer.write_all(&[3])?;
+                writer.write_all(&value.to_le_bytes())?;
+            }
+            Value::String(value) => {
+                writer.write_all(&[4])?;
+                write_bytes(writer, value.as_bytes())?;
+            }
+            Value::Bytes(value) => {
+                writer.write_all(&[5])?;
+                write_bytes(writer, value)?;
+            }
+            Value::Strings(values) => {
+                writer.write_all(&[6])?;
+                write_varint(writer, values.len() as u64)?;
+                for value in values {
+                    write_bytes(writer, value.as_bytes())?;
+                }
+            }
+            Value::Output(None) => writer.write_all(&[7])?,
+            Value::Output(Some(value)) => {
+                writer.write_all(&[8])?;
+                writer.write_all(&value.to_le_bytes())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn read_call(reader: &mut impl Read) -> io::Result<RecordedCall> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+    let function = String::from_utf8(read_bytes(reader)?).map_err(|_| invalid("Invalid name"))?;
+    let result = match read_varint(reader)? {
+        0 => None,
+        code => Some(
+            RESULTS
+                .into_iter()
+                .find(|r| *r as u64 + 1 == code)
+                .ok_or_else(|| invalid("Invalid result code"))?,
+        ),
+    };
+    let count = read_varint(reader)?;
+    let mut args = Vec::new();
+    for _ in 0..count {
+        let mut tag = [0];
+        reader.read_exact(&mut tag)?;
+        args.push(match tag[0] {
+            0 => Value::Session,
+            1 => Value::Null,
+            2 => {
+                let value = read_varint(reader)?;
+                Value::Int((value >> 1) as i64 ^ -((value & 1) as i64))
+            }
+            3 => {
+                let mut bytes = [0; 8];
+                reader.read_exact(&mut bytes)?;
+                Value::Float(f64::from_le_bytes(bytes))
+            }
+            4 => Value::String(read_cstring(reader)?),
+            5 => Value::Bytes(read_bytes(reader)?),
+            6 => {
+                let count = read_varint(reader)?;
+                Value::Strings(
+                    (0..count)
+                        .map(|_| read_cstring(reader))
+                        .collect::<io::Result<_>>()?,
+                )
+            }
+            7 => Value::Output(None),
+            8 => {
+                let mut bytes = [0; 4];
+                reader.read_exact(&mut bytes)?;
+                Value::Output(Some(i32::from_le_bytes(bytes)))
+            }
+            _ => return Err(invalid("Invalid argument tag")),
+        });
+    }
+    Ok(RecordedCall {
+        function,
+        result,
+        args,
+    })
+}
@@ -15,11 +15,20 @@ use temp_env;
 use crate::{
     errors::{ErrorContext, HapiError, Result},
     ffi::{self, ThriftServerOptions, enums::StatusVerbosity},
-    session::UninitializedSession,
+    session::{LicenseType, UninitializedSession},
     utils,
 };
 
+mod install;
+mod logs;
+
 pub use crate::ffi::raw::ThriftSharedMemoryBufferType;
+pub use install::{
+    HoudiniInstall, HoudiniVersion, discover_installations, find_compatible_installation,
+    find_installations_in,
+};
+pub(crate) use logs::stop_log_tail;
+pub use logs::{SERVER_LOG_TARGET, ServerLogLine, ServerLogSink, parse_severity};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum LicensePreference {
@@ -48,6 +57,38 @@ impl std::fmt::Display for LicensePreference {
     }
 }
 
+/// Commercial tier of a [`LicenseType`], ordered from the most restricted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LicenseTier {
+    /// Education and apprentice licenses
+    NonCommercial,
+    /// Indie licenses and the Unity/Unreal plugin license
+    LimitedCommercial,
+    Commercial,
+}
+
+impl LicenseTier {
+    /// `None` for [`LicenseType::LicenseNone`].
+    pub fn of(license: LicenseType) -> Option<Self> {
+        use crate::ffi::raw::License::*;
+        match license {
+            HoudiniEngine | LicenseHoudini | HoudiniFx => Some(LicenseTier::Commercial),
+            EngineIndie | HoudiniIndie | UnityUnreal => Some(LicenseTier::LimitedCommercial),
+            HoudiniEducation | EngineEducation => Some(LicenseTier::NonCommercial),
+            LicenseNone | LicenseMax => None,
+        }
+    }
+}
+
+/// License checked out by a session, see [`Session::acquired_license`](crate::session::Session::acquired_license).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AcquiredLicense {
+    pub license: LicenseType,
+    pub tier: Option<LicenseTier>,
+    /// Preference the server was started with, `None` for in-process sessions or no preference
+    pub preference: Option<LicensePreference>,
+}
+
 #[derive(Clone, Debug)]
 pub struct ThriftSharedMemoryTransport {
     pub memory_name: String,
@@ -128,8 +169,16 @@ pub struct ServerOptions {
     pub log_file: Option<CString>,
     pub env_variables: Option<HashMap<OsString, OsString>>,
     pub license_preference: Option<LicensePreference>,
+    /// Tried in order when starting the server, see [`ServerOptions::with_license_preferences`]
+    pub license_preferences: Vec<LicensePreference>,
+    /// See [`ServerOptions::with_minimum_license`]
+    pub minimum_license: Option<LicenseTier>,
     pub connection_count: i32,
     pub server_ready_timeout: Option<u32>,
+    /// Launch HARS from this installation instead of the one libHAPIL belongs to
+    pub houdini_install: Option<HoudiniInstall>,
+    /// Forward the server output here, see [`ServerOptions::with_log_capture`]
+    pub log_capture: Option<ServerLogSink>,
     pub(crate) connection_retry_interval: Option<Duration>,
 }
 
@@ -144,8 +193,12 @@ impl Default for ServerOptions {
             log_file: None,
             env_variables: None,
             license_preference: None,
+            license_preferences: Vec::new(),
+            minimum_license: None,
             connection_count: 0,
             server_ready_timeout: None,
+            houdini_install: None,
+            log_capture: None,
             connection_retry_interval: Some(Duration::from_secs(10)),
         }
     }
@@ -197,6 +250,27 @@ impl ServerOptions {
         self
     }
 
+    /// Try license preferences in order when starting a session with
+    /// [`new_thrift_session`](crate::session::new_thrift_session). When a server can't check out a
+    /// license ([`HapiResult::NoLicenseFound`](crate::errors::HapiResult::NoLicenseFound) or
+    /// [`HapiResult::DisallowedNcLicenseFound`](crate::errors::HapiResult::DisallowedNcLicenseFound)),
+    /// or its license is below [`ServerOptions::with_minimum_license`], a new server is started with
+    /// the next preference.
+    pub fn with_license_preferences(
+        mut self,
+        preferences: impl IntoIterator<Item = LicensePreference>,
+    ) -> Self {
+        self.license_preferences = preferences.into_iter().collect();
+        self
+    }
+
+    /// Reject sessions with a license below `tier` with [`HapiError::LicenseRejected`],
+    /// e.g [`LicenseTier::Commercial`] to refuse indie and education licenses.
+    pub fn with_minimum_license(mut self, tier: LicenseTier) -> Self {
+        self.minimum_license.replace(tier);
+        self
+    }
+
     /// Set the log file for the server.
     /// BUG: HARS 21.0.685 has a bug where the log file is always created in the working directory
     pub fn with_log_file(mut self, file: impl AsRef<Path>) -> Self {
@@ -204,6 +278,14 @@ impl ServerOptions {
         self
     }
 
+    /// Capture the server output and forward each line with its parsed severity and the server pid
+    /// to `sink`. The log file set with [`ServerOptions::with_log_file`] is tailed, without one the
+    /// server logs to a temporary file, or a pipe when started from a [`HoudiniInstall`].
+    pub fn with_log_capture(mut self, sink: ServerLogSink) -> Self {
+        self.log_capture.replace(sink);
+        self
+    }
+
     /// Set **real** environment variables before the server starts.
     /// Unlike [`crate::session::Session::set_server_var`], where the variables are set in the session after the
     /// server starts.
@@ -243,11 +325,55 @@ impl ServerOptions {
 
     /// Set the timeout for the server to be ready in ms
     /// This is the timeout for the server to initialize and be ready to accept connections.
+    /// A server launched with [`ServerOptions::with_houdini_install`] is waited for when connecting.
     pub fn with_server_ready_timeout(mut self, timeout: u32) -> Self {
         self.server_ready_timeout.replace(timeout);
         self
     }
 
+    /// Launch the server executable from a specific Houdini installation,
+    /// see [`discover_installations`] and [`find_compatible_installation`].
+    pub fn with_houdini_install(mut self, install: HoudiniInstall) -> Self {
+        self.houdini_install.replace(install);
+        self
+    }
+
+    /// Options for the `index`-th server of a [`SessionPool`](crate::session::SessionPool):
+    /// shared memory and pipe servers get a unique name, socket servers listen on `port + index`.
+    pub(crate) fn for_pool_server(&self, index: usize) -> Result<Self> {
+        let suffix = format!("{index}-{}", utils::random_string(8));
+        let thrift_transport = match &self.thrift_transport {
+            ThriftTransport::SharedMemory(transport) => {
+                ThriftTransport::SharedMemory(ThriftSharedMemoryTransport {
+                    memory_name: format!("{}-{suffix}", transport.memory_name),
+                    ..transport.clone()
+                })
+            }
+            ThriftTransport::Pipe(transport) => {
+                let mut pipe_path = transport.pipe_path.clone().into_os_string();
+                pipe_path.push(format!("-{suffix}"));
+                ThriftTransport::Pipe(ThriftPipeTransport {
+                    pipe_path: pipe_path.into(),
+                })
+            }
+            ThriftTransport::Socket(transport) => {
+                let port = u16::try_from(index)
+                    .ok()
+                    .and_then(|index| transport.address.port().checked_add(index))
+                    .ok_or_else(|| {
+                        HapiError::Internal(format!(
+                            "No free port for pool server {index} after {}",
+                            transport.address
+                        ))
+                    })?;
+                ThriftTransport::Socket(ThriftSocketTransport {
+                    address: SocketAddrV4::new(*transport.address.ip(), port),
+                })
+            }
+        };
+        Ok(self.clone().with_thrift_transport(thrift_transport))
+    }
+
     pub(crate) fn session_info(&self) -> crate::ffi::SessionInfo {
         let mut session_info =
             crate::ffi::SessionInfo::default().with_connection_count(self.connection_count);
@@ -260,6 +386,19 @@ impl ServerOptions {
         session_info
     }
 
+    /// How long to retry connecting. HARS launched from a [`HoudiniInstall`] doesn't report when
+    /// it's ready, so the connection waits for at least the server ready timeout.
+    fn connect_timeout(&self) -> Option<Duration> {
+        match (&self.houdini_install, self.server_ready_timeout) {
+            (Some(_), Some(ready)) => {
+                let ready = Duration::from_millis(ready.into());
+                self.connection_retry_interval
+                    .map(|timeout| timeout.max(ready))
+            }
+            _ => self.connection_retry_interval,
+        }
+    }
+
     pub(crate) fn thrift_options(&self) -> crate::ffi::ThriftServerOptions {
         let mut options = ThriftServerOptions::default()
             .with_auto_close(self.auto_close)
@@ -282,15 +421,13 @@ where
     T: AsRef<OsStr>,
     F: FnOnce() -> Result<R>,
 {
-    if let Some(env_variables) = variables {
-        let env_variables: Vec<(&OsStr, Option<&OsStr>)> = env_variables
-            .iter()
-            .map(|(k, v)| (k.as_ref(), Some(v.as_ref())))
-            .collect::<Vec<_>>();
-        temp_env::with_vars(env_variables.as_slice(), f)
-    } else {
-        f()
-    }
+    let env_variables: Vec<(&OsStr, Option<&OsStr>)> = variables
+        .unwrap_or_default()
+        .iter()
+        .map(|(k, v)| (k.as_ref(), Some(v.as_ref())))
+        .collect::<Vec<_>>();
+    // Always taking the lock keeps servers started concurrently from inheriting each other's variables.
+    temp_env::with_vars(env_variables.as_slice(), f)
 }
 
 /// Connect to the Thrift pipe server and return an uninitialized session.
@@ -298,6 +435,7 @@ pub fn connect_to_pipe_server(
     server_options: ServerOptions,
     pid: Option<u32>,
 ) -> Result<UninitializedSession> {
+    crate::backend::check()?;
     let ThriftTransport::Pipe(ThriftPipeTransport { pipe_path }) = &server_options.thrift_transport
     else {
         return Err(HapiError::Internal(
@@ -307,7 +445,7 @@ pub fn connect_to_pipe_server(
     let pipe_name = utils::path_to_cstring(pipe_path)?;
     debug!("Connecting to pipe server: {:?}", pipe_path.display());
     let handle = try_connect_with_timeout(
-        server_options.connection_retry_interval,
+        server_options.connect_timeout(),
         Duration::from_millis(100),
         || ffi::new_thrift_piped_session(&pipe_name, &server_options.session_info().0),
     )?;
@@ -323,6 +461,7 @@ pub fn connect_to_memory_server(
     server_options: ServerOptions,
     pid: Option<u32>,
 ) -> Result<UninitializedSession> {
+    crate::backend::check()?;
     let ThriftTransport::SharedMemory(ThriftSharedMemoryTransport { memory_name, .. }) =
         &server_options.thrift_transport
     else {
@@ -333,7 +472,7 @@ pub fn connect_to_memory_server(
     let mem_name_cstr = CString::new(memory_name.clone())?;
     debug!("Connecting to shared memory server: {:?}", memory_name);
     let handle = try_connect_with_timeout(
-        server_options.connection_retry_interval,
+        server_options.connect_timeout(),
         Duration::from_millis(100),
         || ffi::new_thrift_shared_memory_session(&mem_name_cstr, &server_options.session_info().0),
     )?;
@@ -379,6 +518,7 @@ pub fn connect_to_socket_server(
     server_options: ServerOptions,
     pid: Option<u32>,
 ) -> Result<UninitializedSession> {
+    crate::backend::check()?;
     let ThriftTransport::Socket(ThriftSocketTransport { address }) =
         &server_options.thrift_transport
     else {
@@ -391,7 +531,7 @@ pub fn connect_to_socket_server(
         .map_err(HapiError::from)
         .context("Converting SocketAddr to CString")?;
     let handle = try_connect_with_timeout(
-        server_options.connection_retry_interval,
+        server_options.connect_timeout(),
         Duration::from_millis(100),
         || {
             ffi::new_thrift_socket_session(
@@ -408,14 +548,200 @@ pub fn connect_to_socket_server(
     })
 }
 
+/// Connect to an already running server using the transport from `server_options`.
+pub(crate) fn connect_to_server(
+    server_options: ServerOptions,
+    pid: Option<u32>,
+) -> Result<UninitializedSession> {
+    match server_options.thrift_transport {
+        ThriftTransport::SharedMemory(_) => connect_to_memory_server(server_options, pid),
+        ThriftTransport::Pipe(_) => connect_to_pipe_server(server_options, pid),
+        ThriftTransport::Socket(_) => connect_to_socket_server(server_options, pid),
+    }
+}
+
+/// Reconnect to the server `pid` if it's still running, otherwise start a new server.
+pub(crate) fn reconnect_or_restart(
+    server_options: ServerOptions,
+    pid: Option<u32>,
+) -> Result<UninitializedSession> {
+    if let Some(pid) = pid
+        && is_process_alive(pid)
+    {
+        match connect_to_server(server_options.clone(), Some(pid)) {
+            Ok(session) => return Ok(session),
+            Err(e) => {
+                warn!("Could not reconnect to server pid {pid}: {e}");
+                stop_unresponsive_server(pid);
+            }
+        }
+    }
+    let pid = start_engine_server(&server_options)?;
+    connect_to_server(server_options, Some(pid))
+}
+
+/// Kill a server which is running, but doesn't accept connections, before a new one replaces it.
+pub(crate) fn stop_unresponsive_server(pid: u32) {
+    if kill_process(pid) {
+        warn!("Killed unresponsive server pid {pid}");
+    } else {
+        error!("Could not kill unresponsive server pid {pid}");
+    }
+}
+
+/// Returns `true` if the server process `pid` is running, as seen by the current backend.
+pub(crate) fn is_process_alive(pid: u32) -> bool {
+    crate::backend::current().is_process_alive(pid)
+}
+
+/// Kill the server process `pid` through the current backend, returns `true` if it was signalled.
+pub(crate) fn kill_process(pid: u32) -> bool {
+    crate::backend::current().kill_process(pid)
+}
+
+/// Returns `true` if the process `pid` is running.
+///
+/// A server started by this process which exited stays a zombie until it's reaped, and zombies
+/// can still be signalled. Such a server is reaped here and reported as not running.
+#[cfg(unix)]
+pub(crate) fn os_is_process_alive(pid: u32) -> bool {
+    const EPERM: i32 = 1;
+    const WNOHANG: i32 = 1;
+    unsafe extern "C" {
+        fn kill(pid: i32, sig: i32) -> i32;
+        fn waitpid(pid: i32, status: *mut i32, options: i32) -> i32;
+    }
+    let Ok(pid) = i32::try_from(pid) else {
+        return false;
+    };
+    // pid 0 would signal the whole process group.
+    if pid <= 0 {
+        return false;
+    }
+    let mut status = 0;
+    // SAFETY: WNOHANG doesn't block and only children of this process can be reaped.
+    match unsafe { waitpid(pid, &mut status, WNOHANG) } {
+        // Our child and still running.
+        0 => return true,
+        reaped if reaped == pid => return false,
+        // Not our child, or it was already reaped.
+        _ => {}
+    }
+    // SAFETY: signal 0 only checks that the process exists.
+    let exists = unsafe { kill(pid, 0) } == 0
+        || std::io::Error::last_os_error().raw_os_error() == Some(EPERM);
+    exists && !is_zombie(pid)
+}
+
+/// A process which exited, but wasn't reaped by its parent yet.
+#[cfg(target_os = "linux")]
+fn is_zombie(pid: i32) -> bool {
+    // The state follows the parenthesized command name, which may contain spaces.
+    std::fs::read_to_string(format!("/proc/{pid}/stat"))
+        .ok()
+        .and_then(|stat| {
+            let (_, rest) = stat.rsplit_once(')')?;
+            rest.trim_start().chars().next()
+        })
+        == Some('Z')
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn is_zombie(_pid: i32) -> bool {
+    false
+}
+
+/// Kill the process `pid`, returns `true` if it was signalled.
+#[cfg(unix)]
+pub(crate) fn os_kill_process(pid: u32) -> bool {
+    const SIGKILL: i32 = 9;
+    unsafe extern "C" {
+        fn kill(pid: i32, sig: i32) -> i32;
+    }
+    let Ok(pid) = i32::try_from(pid) else {
+        return false;
+    };
+    // SAFETY: pid is positive so only that process is signalled.
+    pid > 0 && unsafe { kill(pid, SIGKILL) } == 0
+}
+
+/// Kill the process `pid`, returns `true` if it was terminated.
+#[cfg(windows)]
+pub(crate) fn os_kill_process(pid: u32) -> bool {
+    use std::ffi::c_void;
+    const PROCESS_TERMINATE: u32 = 0x0001;
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn OpenProcess(access: u32, inherit: i32, pid: u32) -> *mut c_void;
+        fn TerminateProcess(process: *mut c_void, exit_code: u32) -> i32;
+        fn CloseHandle(handle: *mut c_void) -> i32;
+    }
+    // SAFETY: the handle is checked for null and closed before returning.
+    unsafe {
+        let process = OpenProcess(PROCESS_TERMINATE, 0, pid);
+        if process.is_null() {
+            return false;
+        }
+        let terminated = TerminateProcess(process, 1) != 0;
+        CloseHandle(process);
+        terminated
+    }
+}
+
+/// Returns `true` if the process `pid` is running.
+#[cfg(windows)]
+pub(crate) fn os_is_process_alive(pid: u32) -> bool {
+    use std::ffi::c_void;
+    const PROCESS_QUERY_LIMITED_INFORMATION: u32 = 0x1000;
+    const STILL_ACTIVE: u32 = 259;
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn OpenProcess(access: u32, inherit: i32, pid: u32) -> *mut c_void;
+        fn GetExitCodeProcess(process: *mut c_void, exit_code: *mut u32) -> i32;
+        fn CloseHandle(handle: *mut c_void) -> i32;
+    }
+    // SAFETY: the handle is checked for null and closed before returning.
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if process.is_null() {
+            return false;
+        }
+        let mut exit_code = 0;
+        let queried = GetExitCodeProcess(process, &mut exit_code) != 0;
+        CloseHandle(process);
+        queried && exit_code == STILL_ACTIVE
+    }
+}
+
 pub fn start_engine_server(server_options: &ServerOptions) -> Result<u32> {
+    crate::backend::check()?;
+    if let Some(install) = &server_options.houdini_install {
+        if !install.is_compatible() {
+            return Err(HapiError::Internal(format!(
+                "Houdini {} at {} is not compatible with Houdini {} this crate was built for",
+                install.version,
+                install.hfs.display(),
+                HoudiniVersion::current()
+            )));
+        }
+        return install::start_hars(install, server_options);
+    }
     let env_variables = server_options.env_variables.as_ref().map(|env_variables| {
         env_variables
             .iter()
             .map(|(k, v)| (k.as_os_str(), v.as_os_str()))
             .collect::<Vec<_>>()
     });
-    match &server_options.thrift_transport {
+    // Captured output needs a log file to tail.
+    let temp_log_file = match (&server_options.log_capture, &server_options.log_file) {
+        (Some(_), None) => Some(logs::temp_log_file()),
+        _ => None,
+    };
+    let log_file = match &temp_log_file {
+        Some(file) => Some(utils::path_to_cstring(file)?),
+        None => server_options.log_file.clone(),
+    };
+    let pid = match &server_options.thrift_transport {
         ThriftTransport::SharedMemory(transport) => {
             debug!(
                 "Starting shared memory server name: {}",
@@ -427,7 +753,7 @@ pub fn start_engine_server(server_options: &ServerOptions) -> Result<u32> {
                 ffi::start_thrift_shared_memory_server(
                     &memory_name,
                     &server_options.thrift_options().0,
-                    server_options.log_file.as_deref(),
+                    log_file.as_deref(),
                 )
                 .with_context(|| {
                     format!(
@@ -445,7 +771,7 @@ pub fn start_engine_server(server_options: &ServerOptions) -> Result<u32> {
                 ffi::start_thrift_pipe_server(
                     &pipe_name,
                     &server_options.thrift_options().0,
-                    server_options.log_file.as_deref(),
+                    log_file.as_deref(),
                 )
                 .with_context(|| format!("Failed to start pipe server: {:?}", transport.pipe_path))
             })
@@ -460,11 +786,16 @@ pub fn start_engine_server(server_options: &ServerOptions) -> Result<u32> {
                 ffi::start_thrift_socket_server(
                     transport.address.port() as i32,
                     &server_options.thrift_options().0,
-                    server_options.log_file.as_deref(),
+                    log_file.as_deref(),
                 )
             })
         }
+    }?;
+    if let (Some(sink), Some(log_file)) = (&server_options.log_capture, &log_file) {
+        let path = PathBuf::from(log_file.to_string_lossy().into_owned());
+        logs::tail_log_file(path, pid, sink.clone(), temp_log_file.is_some())?;
     }
+    Ok(pid)
 }
 
 /// Start an interactive Houdini session with engine server embedded.
@@ -490,3 +821,62 @@ pub fn start_houdini_server(
             .map_err(HapiError::from)
     })
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exited_child_is_not_alive() {
+        let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+        let pid = child.id();
+        assert!(os_is_process_alive(pid));
+        child.kill().unwrap();
+        // The killed child is a zombie until it's reaped.
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while os_is_process_alive(pid) {
+            assert!(std::time::Instant::now() < deadline, "{pid} still alive");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        // Already reaped by os_is_process_alive.
+        assert!(child.wait().is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn unreaped_child_is_zombie() {
+        let mut child = Command::new("true").spawn().unwrap();
+        let pid = child.id() as i32;
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while !is_zombie(pid) {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "{pid} is not a zombie"
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        child.wait().unwrap();
+        assert!(!is_zombie(pid));
+    }
+
+    // Session recovery must start a new server instead of reconnecting to a crashed one,
+    // which fails here since there is no backend to start it with.
+    #[cfg(all(
+        target_os = "linux",
+        not(any(feature = "link", feature = "dynamic-load"))
+    ))]
+    #[test]
+    fn recovery_restarts_exited_server() {
+        let mut child = Command::new("true").spawn().unwrap();
+        while !is_zombie(child.id() as i32) {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let result = reconnect_or_restart(
+            ServerOptions::shared_memory_with_defaults(),
+            Some(child.id()),
+        );
+        assert!(matches!(result, Err(HapiError::Backend(_))));
+        // Reaped as a dead server instead of reconnected to.
+        assert!(child.wait().is_err());
+    }
+}
@@ -0,0 +1,383 @@
+//! Discovery of Houdini installations.
+
+use std::ffi::OsString;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use log::debug;
+
+use super::{ServerOptions, ThriftTransport, logs};
+use crate::errors::{HapiError, Result};
+use crate::ffi::raw::{
+    HAPI_VERSION_HOUDINI_BUILD, HAPI_VERSION_HOUDINI_MAJOR, HAPI_VERSION_HOUDINI_MINOR,
+};
+
+/// Houdini version as found in `toolkit/hdk_api_version.txt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HoudiniVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub build: u32,
+}
+
+impl HoudiniVersion {
+    /// The Houdini version this crate was built for.
+    pub const fn current() -> Self {
+        HoudiniVersion {
+            major: HAPI_VERSION_HOUDINI_MAJOR,
+            minor: HAPI_VERSION_HOUDINI_MINOR,
+            build: HAPI_VERSION_HOUDINI_BUILD,
+        }
+    }
+
+    /// Parse the `hdk_api_version.txt` format, e.g `21000512` for 21.0.512.
+    pub fn from_hdk_api_version(version: &str) -> Result<Self> {
+        let version = version.trim();
+        let number: u32 = version
+            .parse()
+            .ok()
+            .filter(|_| version.len() == 8)
+            .ok_or_else(|| HapiError::Internal(format!("Invalid HDK API version: {version:?}")))?;
+        Ok(HoudiniVersion {
+            major: number / 1_000_000,
+            minor: number / 10_000 % 100,
+            build: number % 10_000,
+        })
+    }
+
+    /// Sessions can only be created with servers of the same major and minor version.
+    pub fn is_compatible(&self) -> bool {
+        let current = Self::current();
+        self.major == current.major && self.minor == current.minor
+    }
+}
+
+impl fmt::Display for HoudiniVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.build)
+    }
+}
+
+/// A Houdini installation, see [`discover_installations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HoudiniInstall {
+    /// Installation root, the `HFS` directory
+    pub hfs: PathBuf,
+    pub version: HoudiniVersion,
+}
+
+impl HoudiniInstall {
+    /// Read the installation version from `hfs/toolkit/hdk_api_version.txt`.
+    pub fn from_hfs(hfs: impl AsRef<Path>) -> Result<Self> {
+        let hfs = hfs.as_ref();
+        let version_file = hfs.join("toolkit").join("hdk_api_version.txt");
+        let version = std::fs::read_to_string(&version_file).map_err(|e| {
+            HapiError::Internal(format!("Could not read {}: {e}", version_file.display()))
+        })?;
+        Ok(HoudiniInstall {
+            hfs: hfs.to_path_buf(),
+            version: HoudiniVersion::from_hdk_api_version(&version)?,
+        })
+    }
+
+    /// See [`HoudiniVersion::is_compatible`].
+    pub fn is_compatible(&self) -> bool {
+        self.version.is_compatible()
+    }
+
+    /// Path of the Houdini Engine server executable.
+    pub fn hars_executable(&self) -> PathBuf {
+        let name = if cfg!(windows) { "HARS.exe" } else { "hars" };
+        self.hfs.join("bin").join(name)
+    }
+}
+
+/// Find Houdini installations pointed by the `HFS` environment variable and in the standard
+/// locations: `/opt/hfs*` on Linux, `/Applications/Houdini` on macOS and
+/// `C:\Program Files\Side Effects Software` on Windows.
+///
+/// Installations are sorted by version, newest first.
+pub fn discover_installations() -> Vec<HoudiniInstall> {
+    let mut installs: Vec<HoudiniInstall> = std::env::var_os("HFS")
+        .and_then(|hfs| HoudiniInstall::from_hfs(hfs).ok())
+        .into_iter()
+        .collect();
+    for dir in search_dirs() {
+        installs.extend(find_installations_in(dir));
+    }
+    sort_installations(installs)
+}
+
+/// Find Houdini installations in the subdirectories of `dir`, e.g `/opt` containing `hfs21.0.512`.
+/// Installations are sorted by version, newest first.
+pub fn find_installations_in(dir: impl AsRef<Path>) -> Vec<HoudiniInstall> {
+    let Ok(entries) = std::fs::read_dir(dir.as_ref()) else {
+        return Vec::new();
+    };
+    let installs = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            // On macOS, HFS is inside the application bundle.
+            let bundle = entry
+                .path()
+                .join("Frameworks/Houdini.framework/Versions/Current/Resources");
+            HoudiniInstall::from_hfs(entry.path())
+                .or_else(|_| HoudiniInstall::from_hfs(bundle))
+                .ok()
+        })
+        .collect();
+    sort_installations(installs)
+}
+
+/// Pick the installation to launch servers from: the one matching the crate version exactly or
+/// else the newest with the same major and minor version.
+pub fn find_compatible_installation() -> Result<HoudiniInstall> {
+    let installs = discover_installations();
+    let current = HoudiniVersion::current();
+    installs
+        .iter()
+        .find(|install| install.version == current)
+        .or_else(|| installs.iter().find(|install| install.is_compatible()))
+        .cloned()
+        .ok_or_else(|| {
+            let found = installs
+                .iter()
+                .map(|install| format!("{} ({})", install.hfs.display(), install.version))
+                .collect::<Vec<_>>();
+            HapiError::Internal(format!(
+                "No Houdini {}.{} installation found, found: [{}]",
+                current.major,
+                current.minor,
+                found.join(", ")
+            ))
+        })
+}
+
+fn sort_installations(installs: Vec<HoudiniInstall>) -> Vec<HoudiniInstall> {
+    // The same installation can be found through $HFS and a symlink, e.g /opt/hfs21.0.
+    let mut installs: Vec<(PathBuf, HoudiniInstall)> = installs
+        .into_iter()
+        .map(|install| {
+            (
+                install.hfs.canonicalize().unwrap_or(install.hfs.clone()),
+                install,
+            )
+        })
+        .collect();
+    installs.sort_by(|(a_root, a), (b_root, b)| {
+        b.version.cmp(&a.version).then_with(|| a_root.cmp(b_root))
+    });
+    installs.dedup_by(|(a_root, _), (b_root, _)| a_root == b_root);
+    installs.into_iter().map(|(_, install)| install).collect()
+}
+
+fn search_dirs() -> Vec<PathBuf> {
+    if cfg!(target_os = "macos") {
+        vec![PathBuf::from("/Applications/Houdini")]
+    } else if cfg!(windows) {
+        let program_files =
+            std::env::var_os("ProgramFiles").unwrap_or_else(|| "C:\\Program Files".into());
+        vec![PathBuf::from(program_files).join("Side Effects Software")]
+    } else {
+        vec![PathBuf::from("/opt")]
+    }
+}
+
+/// Command line arguments for HARS with the transport and server settings from `server_options`,
+/// matching the [`ThriftServerOptions`](crate::ffi::ThriftServerOptions) used by
+/// [`start_engine_server`](super::start_engine_server). HARS doesn't report when it's ready, the
+/// session waits up to [`ServerOptions::server_ready_timeout`] when connecting instead.
+fn hars_args(server_options: &ServerOptions) -> Vec<OsString> {
+    let mut args: Vec<OsString> = Vec::new();
+    match &server_options.thrift_transport {
+        ThriftTransport::SharedMemory(transport) => {
+            args.push("--shared-memory".into());
+            args.push(transport.memory_name.clone().into());
+            args.push("--shared-memory-buffer-type".into());
+            args.push((transport.buffer_type as i32).to_string().into());
+            args.push("--shared-memory-buffer-size".into());
+            args.push(transport.buffer_size.to_string().into());
+        }
+        ThriftTransport::Pipe(transport) => {
+            args.push("--named-pipe".into());
+            args.push(transport.pipe_path.clone().into());
+        }
+        ThriftTransport::Socket(transport) => {
+            args.push("--socket-port".into());
+            args.push(transport.address.port().to_string().into());
+        }
+    }
+    args.push("--verbosity".into());
+    args.push((server_options.verbosity as i32).to_string().into());
+    if server_options.auto_close {
+        args.push("--auto-close".into());
+    }
+    args
+}
+
+/// Launch the HARS executable of `install` with the transport from `server_options`.
+pub(super) fn start_hars(install: &HoudiniInstall, server_options: &ServerOptions) -> Result<u32> {
+    let executable = install.hars_executable();
+    let mut command = Command::new(&executable);
+    command.args(hars_args(server_options));
+    let log_file = server_options
+        .log_file
+        .as_ref()
+        .map(|log_file| PathBuf::from(log_file.to_string_lossy().into_owned()));
+    if let Some(log_file) = &log_file {
+        command.arg("--log-file").arg(log_file);
+    }
+    // Without a log file, captured output is read from the pipes.
+    let piped = server_options.log_capture.is_some() && log_file.is_none();
+    let output = || if piped { Stdio::piped() } else { Stdio::null() };
+    if let Some(env_variables) = &server_options.env_variables {
+        command.envs(env_variables);
+    }
+    debug!("Starting {command:?}");
+    let mut child = command
+        .env("HFS", &install.hfs)
+        .stdin(Stdio::null())
+        .stdout(output())
+        .stderr(output())
+        .spawn()
+        .map_err(|e| {
+            HapiError::Internal(format!("Could not start {}: {e}", executable.display()))
+        })?;
+    let pid = child.id();
+    if let Some(sink) = &server_options.log_capture {
+        if let Some(log_file) = log_file {
+            logs::tail_log_file(log_file, pid, sink.clone(), false)?;
+        }
+        if let Some(stdout) = child.stdout.take() {
+            logs::forward_stream(stdout, pid, sink.clone())?;
+        }
+        if let Some(stderr) = child.stderr.take() {
+            logs::forward_stream(stderr, pid, sink.clone())?;
+        }
+    }
+    // Reap the process when it exits.
+    std::thread::Builder::new()
+        .name(format!("hapi-rs-hars-{pid}"))
+        .spawn(move || {
+            let status = child.wait();
+            debug!("Server {pid} exited: {status:?}");
+            logs::stop_log_tail(pid);
+        })?;
+    Ok(pid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake_install(root: &Path, name: &str, version: &str) -> PathBuf {
+        let hfs = root.join(name);
+        std::fs::create_dir_all(hfs.join("toolkit")).unwrap();
+        std::fs::write(hfs.join("toolkit/hdk_api_version.txt"), version).unwrap();
+        hfs
+    }
+
+    #[test]
+    fn parse_hdk_api_version() {
+        let version = HoudiniVersion::from_hdk_api_version("21000512\n").unwrap();
+        assert_eq!(
+            version,
+            HoudiniVersion {
+                major: 21,
+                minor: 0,
+                build: 512
+            }
+        );
+        assert_eq!(version.to_string(), "21.0.512");
+        assert!(HoudiniVersion::from_hdk_api_version("21.0.512").is_err());
+        assert!(HoudiniVersion::from_hdk_api_version("2100512").is_err());
+    }
+
+    #[test]
+    fn installations_are_sorted_newest_first() {
+        let root = tempfile::tempdir().unwrap();
+        let current = HoudiniVersion::current();
+        let older = format!("{:02}{:02}{:04}", current.major - 1, 5, 100);
+        fake_install(root.path(), "hfs-older", &older);
+        let newest = fake_install(
+            root.path(),
+            "hfs-current",
+            &format!(
+                "{:02}{:02}{:04}",
+                current.major, current.minor, current.build
+            ),
+        );
+        std::fs::create_dir(root.path().join("not-houdini")).unwrap();
+        std::fs::write(root.path().join("hfs.txt"), "").unwrap();
+
+        let installs = find_installations_in(root.path());
+        assert_eq!(installs.len(), 2);
+        assert_eq!(installs[0].hfs, newest);
+        assert!(installs[0].is_compatible());
+        assert!(!installs[1].is_compatible());
+        assert!(installs[0].hars_executable().starts_with(&newest));
+    }
+
+    #[test]
+    fn symlinked_installations_are_deduplicated() {
+        let root = tempfile::tempdir().unwrap();
+        let current = HoudiniVersion::current();
+        let version = format!(
+            "{:02}{:02}{:04}",
+            current.major, current.minor, current.build
+        );
+        let hfs = fake_install(root.path(), "hfs-a", &version);
+        fake_install(root.path(), "hfs-b", &version);
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&hfs, root.path().join("hfs-z")).unwrap();
+        let mut installs = find_installations_in(root.path());
+        // The same directory as found through $HFS.
+        installs.push(HoudiniInstall::from_hfs(root.path().join("hfs-a/.")).unwrap());
+        let installs = sort_installations(installs);
+        assert_eq!(installs.len(), 2);
+    }
+
+    #[test]
+    fn hars_arguments() {
+        use crate::ffi::enums::StatusVerbosity;
+        use crate::server::{
+            ThriftSharedMemoryBufferType, ThriftSharedMemoryTransportBuilder, ThriftSocketTransport,
+        };
+        use std::num::NonZeroU64;
+
+        let options = ServerOptions::default().with_thrift_transport(ThriftTransport::Socket(
+            ThriftSocketTransport {
+                address: "127.0.0.1:9090".parse().unwrap(),
+            },
+        ));
+        let args = hars_args(&options);
+        assert_eq!(args[..2], ["--socket-port", "9090"]);
+        assert!(args.iter().any(|arg| arg == "--auto-close"));
+
+        let transport = ThriftSharedMemoryTransportBuilder::default()
+            .with_memory_name("mem")
+            .with_buffer_type(ThriftSharedMemoryBufferType::RingBuffer)
+            .with_buffer_size(NonZeroU64::new(256).unwrap())
+            .build();
+        let options = ServerOptions::default()
+            .with_thrift_transport(ThriftTransport::SharedMemory(transport))
+            .with_verbosity(StatusVerbosity::Statusverbosity2)
+            .with_auto_close(false);
+        let args = hars_args(&options);
+        assert_eq!(
+            args,
+            [
+                "--shared-memory",
+                "mem",
+                "--shared-memory-buffer-type",
+                "1",
+                "--shared-memory-buffer-size",
+                "256",
+                "--verbosity",
+                "2"
+            ]
+        );
+    }
+}
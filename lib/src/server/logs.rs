@@ -0,0 +1,294 @@
+//! Forwarding of server log output to the `log` crate or a user callback.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::thread;
+use std::time::Duration;
+
+use log::{Level, warn};
+use parking_lot::Mutex;
+
+use crate::errors::Result;
+
+/// Log target used when forwarding server output to the `log` crate.
+pub const SERVER_LOG_TARGET: &str = "hars";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// Stop flags of the running log file tails by server pid.
+static TAILS: LazyLock<Mutex<HashMap<u32, Arc<AtomicBool>>>> = LazyLock::new(Default::default);
+
+/// A line of server output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerLogLine {
+    /// Pid of the server process the line came from
+    pub pid: u32,
+    pub level: Level,
+    /// The line without the severity prefix
+    pub message: String,
+}
+
+/// Where captured server output goes, see [`super::ServerOptions::with_log_capture`].
+#[derive(Clone)]
+pub enum ServerLogSink {
+    /// Forward to the `log` crate with the [`SERVER_LOG_TARGET`] target.
+    Log,
+    Callback(Arc<dyn Fn(&ServerLogLine) + Send + Sync>),
+}
+
+impl ServerLogSink {
+    pub fn callback(callback: impl Fn(&ServerLogLine) + Send + Sync + 'static) -> Self {
+        ServerLogSink::Callback(Arc::new(callback))
+    }
+
+    fn emit(&self, pid: u32, line: &str) {
+        let line = line.trim_end();
+        if line.is_empty() {
+            return;
+        }
+        let (level, message) = parse_severity(line);
+        match self {
+            ServerLogSink::Log => {
+                log::log!(target: SERVER_LOG_TARGET, level, "[pid {pid}] {message}")
+            }
+            ServerLogSink::Callback(callback) => callback(&ServerLogLine {
+                pid,
+                level,
+                message: message.to_owned(),
+            }),
+        }
+    }
+}
+
+impl fmt::Debug for ServerLogSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServerLogSink::Log => f.write_str("Log"),
+            ServerLogSink::Callback(_) => f.write_str("Callback"),
+        }
+    }
+}
+
+/// Split a server log line into its severity and message.
+/// Recognizes prefixes like `Warning:`, `[Error]` or `ERROR -`, lines without one are [`Level::Info`].
+pub fn parse_severity(line: &str) -> (Level, &str) {
+    const PREFIXES: &[(&str, Level)] = &[
+        ("fatal", Level::Error),
+        ("critical", Level::Error),
+        ("error", Level::Error),
+        ("warning", Level::Warn),
+        ("warn", Level::Warn),
+        ("message", Level::Info),
+        ("info", Level::Info),
+        ("debug", Level::Debug),
+        ("trace", Level::Trace),
+    ];
+    let trimmed = line.trim_start();
+    let (bracketed, rest) = match trimmed.strip_prefix('[') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed),
+    };
+    for (prefix, level) in PREFIXES {
+        let Some(head) = rest.get(..prefix.len()) else {
+            continue;
+        };
+        if !head.eq_ignore_ascii_case(prefix) {
+            continue;
+        }
+        let tail = &rest[prefix.len()..];
+        let tail = if bracketed {
+            match tail.strip_prefix(']') {
+                Some(tail) => tail,
+                None => continue,
+            }
+        } else if let Some(tail) = tail.strip_prefix([':', '-']) {
+            tail
+        } else if tail.trim_start().starts_with('-') {
+            tail.trim_start().trim_start_matches('-')
+        } else {
+            continue;
+        };
+        let message = tail
+            .trim_start()
+            .trim_start_matches([':', '-'])
+            .trim_start();
+        return (*level, message);
+    }
+    (Level::Info, trimmed)
+}
+
+/// Forward complete lines from `reader` to `sink`, waiting for more output while `is_running`
+/// returns `true`. Output left after the process exits is forwarded before returning.
+pub(crate) fn forward_lines(
+    reader: impl Read,
+    pid: u32,
+    sink: &ServerLogSink,
+    is_running: impl Fn() -> bool,
+) {
+    let mut reader = BufReader::new(reader);
+    let mut line = String::new();
+    loop {
+        // Check the process before reading so nothing written right before exit is lost.
+        let running = is_running();
+        match reader.read_line(&mut line) {
+            Ok(0) if !running => break,
+            Ok(0) => thread::sleep(POLL_INTERVAL),
+            Ok(_) if line.ends_with('\n') => {
+                sink.emit(pid, &line);
+                line.clear();
+            }
+            // A partial line, wait for the rest of it.
+            Ok(_) => {}
+            Err(e) => {
+                warn!("Could not read server {pid} log: {e}");
+                break;
+            }
+        }
+    }
+    sink.emit(pid, &line);
+}
+
+/// Tail the log `file` of server `pid` on a background thread until the process exits
+/// or [`stop_log_tail`] is called. With `remove` the file is deleted afterwards.
+pub(crate) fn tail_log_file(
+    file: PathBuf,
+    pid: u32,
+    sink: ServerLogSink,
+    remove: bool,
+) -> Result<()> {
+    let stopped = Arc::new(AtomicBool::new(false));
+    TAILS.lock().insert(pid, Arc::clone(&stopped));
+    thread::Builder::new()
+        .name(format!("hapi-rs-server-log-{pid}"))
+        .spawn(move || {
+            let is_running = || !stopped.load(Ordering::Acquire) && super::is_process_alive(pid);
+            // The server creates the log file once it's up.
+            let opened = loop {
+                match File::open(&file) {
+                    Ok(opened) => break Some(opened),
+                    Err(_) if is_running() => thread::sleep(POLL_INTERVAL),
+                    Err(_) => break None,
+                }
+            };
+            if let Some(opened) = opened {
+                forward_lines(opened, pid, &sink, is_running);
+            }
+            if remove {
+                let _ = std::fs::remove_file(&file);
+            }
+            // A restarted server may have reused the pid, keep its tail registered.
+            let mut tails = TAILS.lock();
+            if tails
+                .get(&pid)
+                .is_some_and(|flag| Arc::ptr_eq(flag, &stopped))
+            {
+                tails.remove(&pid);
+            }
+        })?;
+    Ok(())
+}
+
+/// Stop tailing the log file of server `pid` once it exited or its session shut down.
+/// Lines already written to the file are still forwarded.
+pub(crate) fn stop_log_tail(pid: u32) {
+    if let Some(stopped) = TAILS.lock().get(&pid) {
+        stopped.store(true, Ordering::Release);
+    }
+}
+
+/// Forward a piped stream of a spawned server on a background thread.
+pub(crate) fn forward_stream(
+    stream: impl Read + Send + 'static,
+    pid: u32,
+    sink: ServerLogSink,
+) -> Result<()> {
+    thread::Builder::new()
+        .name(format!("hapi-rs-server-log-{pid}"))
+        // A pipe read returns EOF only when the process closes it.
+        .spawn(move || forward_lines(stream, pid, &sink, || false))?;
+    Ok(())
+}
+
+/// A log file in the temp directory for servers started without [`super::ServerOptions::with_log_file`].
+pub(crate) fn temp_log_file() -> PathBuf {
+    std::env::temp_dir().join(format!("hars-{}.log", crate::utils::random_string(16)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn parse_server_severity() {
+        assert_eq!(
+            parse_severity("Warning: Unknown parm"),
+            (Level::Warn, "Unknown parm")
+        );
+        assert_eq!(
+            parse_severity("[ERROR] Cook failed"),
+            (Level::Error, "Cook failed")
+        );
+        assert_eq!(
+            parse_severity("debug - connection 1 opened"),
+            (Level::Debug, "connection 1 opened")
+        );
+        assert_eq!(
+            parse_severity("Errors were found"),
+            (Level::Info, "Errors were found")
+        );
+        assert_eq!(
+            parse_severity("Server ready"),
+            (Level::Info, "Server ready")
+        );
+    }
+
+    #[test]
+    fn forward_lines_to_callback() {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let sink = ServerLogSink::callback({
+            let lines = Arc::clone(&lines);
+            move |line| lines.lock().unwrap().push(line.clone())
+        });
+        let output = "Server ready\n\nWarning: slow cook\nError: no license";
+        forward_lines(output.as_bytes(), 42, &sink, || false);
+        let lines = lines.lock().unwrap();
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|line| line.pid == 42));
+        assert_eq!(lines[1].level, Level::Warn);
+        assert_eq!(lines[1].message, "slow cook");
+        assert_eq!(lines[2].level, Level::Error);
+        assert_eq!(lines[2].message, "no license");
+    }
+
+    #[test]
+    fn stopped_tail_forwards_output_and_removes_file() {
+        let file = temp_log_file();
+        std::fs::write(&file, "Server ready\nWarning: slow cook\n").unwrap();
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let sink = ServerLogSink::callback({
+            let lines = Arc::clone(&lines);
+            move |line| lines.lock().unwrap().push(line.clone())
+        });
+        // Our own process stays alive, only the stop ends the tail.
+        let pid = std::process::id();
+        tail_log_file(file.clone(), pid, sink, true).unwrap();
+        stop_log_tail(pid);
+        let start = std::time::Instant::now();
+        while file.exists() {
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "Log tail did not stop"
+            );
+            thread::sleep(POLL_INTERVAL);
+        }
+        let lines = lines.lock().unwrap();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].message, "slow cook");
+    }
+}
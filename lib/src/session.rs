@@ -16,10 +16,18 @@
 //!
 //! Helper constructors terminate the server by default. This is useful for quick one-off jobs.
 //!
-use log::{debug, error};
-use parking_lot::ReentrantMutex;
+//! With [SessionOptions::auto_recover], a Thrift session survives a server crash: the first failing
+//! API call restarts the server with the original [ServerOptions], replays loaded asset libraries,
+//! server variables and time settings, and returns [HapiError::SessionRecovered]. Node, geometry and
+//! other handles created before the crash are stale after that.
+//!
+use log::{debug, error, info, warn};
+use parking_lot::{Condvar, Mutex, ReentrantMutex};
+use std::cell::Cell;
 use std::fmt::Debug;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::time::Duration;
 use std::{ffi::CString, path::Path, sync::Arc};
 
 pub use crate::{
@@ -32,6 +40,7 @@ pub use crate::{
     node::{HoudiniNode, ManagerNode, ManagerType, NodeHandle, NodeType, Transform},
     parameter::Parameter,
     server::ServerOptions,
+    snapshot::SessionSnapshot,
     stringhandle::StringArray,
 };
 
@@ -41,6 +50,8 @@ pub type LicenseType = raw::License;
 
 use crate::cop::CopImageDescription;
 use crate::ffi::ImageInfo;
+use crate::path::NodePath;
+use crate::server::{AcquiredLicense, LicensePreference, LicenseTier};
 use crate::stringhandle::StringHandle;
 use crate::{ffi::raw, utils};
 
@@ -153,28 +164,216 @@ pub enum CookResult {
     CookErrors(String),
     /// One or more nodes could not cook - should abort cooking
     FatalErrors(String),
+    /// Cooking was cancelled with [`CookHandle::cancel`](crate::cooking::CookHandle::cancel)
+    Interrupted,
 }
 
 impl CookResult {
     /// Convenient method for cook result message if any
     pub fn message(&self) -> Option<&str> {
         match self {
-            Self::Succeeded => None,
+            Self::Succeeded | Self::Interrupted => None,
             Self::CookErrors(msg) => Some(msg.as_str()),
             Self::FatalErrors(msg) => Some(msg.as_str()),
         }
     }
 }
 
+/// Delays between cook state polls while waiting for a threaded session to finish cooking.
+///
+/// The first poll happens immediately, then the delay starts at `initial` and is multiplied
+/// by `factor` after every poll, up to `max`. A `factor` of 0 is treated as 1, so the delay
+/// never drops to zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CookBackoff {
+    pub initial: Duration,
+    pub max: Duration,
+    pub factor: u32,
+}
+
+impl Default for CookBackoff {
+    fn default() -> Self {
+        CookBackoff {
+            initial: Duration::from_millis(1),
+            max: Duration::from_millis(50),
+            factor: 2,
+        }
+    }
+}
+
+impl CookBackoff {
+    /// Poll at a fixed interval.
+    pub fn fixed(interval: Duration) -> Self {
+        CookBackoff {
+            initial: interval,
+            max: interval,
+            factor: 1,
+        }
+    }
+
+    /// Infinite sequence of delays between polls.
+    pub fn delays(&self) -> impl Iterator<Item = Duration> + Send + 'static {
+        let (max, factor) = (self.max, self.factor.max(1));
+        std::iter::successors(Some(self.initial.min(max)), move |delay| {
+            Some(delay.saturating_mul(factor).min(max))
+        })
+    }
+}
+
+/// Deadline for long running calls like [`HoudiniNode::cook_blocking`] or [`Session::load_hip`].
+///
+/// When `timeout` passes, the session is interrupted. If the call still hasn't returned after
+/// `grace_period`, the server process of a Thrift session is killed. Either way the call returns
+/// [`HapiError::Timeout`]. See [`SessionOptions::call_timeout`] and [`Session::with_timeout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CallTimeout {
+    pub timeout: Duration,
+    pub grace_period: Duration,
+}
+
+impl CallTimeout {
+    /// Timeout with a 5 seconds grace period.
+    pub fn new(timeout: Duration) -> Self {
+        CallTimeout {
+            timeout,
+            grace_period: Duration::from_secs(5),
+        }
+    }
+
+    /// How long to wait for the call to return after interrupting the session.
+    pub fn with_grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+}
+
+impl From<Duration> for CallTimeout {
+    fn from(timeout: Duration) -> Self {
+        CallTimeout::new(timeout)
+    }
+}
+
+/// The current `HAPI_Session` of a [`Session`], replaced when the session is recovered.
+/// Previous handles are kept alive, so pointers passed to in-flight calls stay valid.
+#[derive(Debug)]
+pub(crate) struct SessionHandle {
+    current: AtomicPtr<raw::HAPI_Session>,
+    // Boxed so the addresses don't change when the Vec grows.
+    #[allow(clippy::vec_box)]
+    handles: Mutex<Vec<Box<raw::HAPI_Session>>>,
+}
+
+impl SessionHandle {
+    fn new(handle: raw::HAPI_Session) -> Self {
+        let mut handle = Box::new(handle);
+        SessionHandle {
+            current: AtomicPtr::new(&mut *handle),
+            handles: Mutex::new(vec![handle]),
+        }
+    }
+
+    fn ptr(&self) -> *const raw::HAPI_Session {
+        self.current.load(Ordering::Acquire)
+    }
+
+    fn get(&self) -> raw::HAPI_Session {
+        // SAFETY: the pointer targets a box owned by self.handles.
+        unsafe { *self.ptr() }
+    }
+
+    fn replace(&self, handle: raw::HAPI_Session) {
+        let mut handles = self.handles.lock();
+        let mut handle = Box::new(handle);
+        self.current.store(&mut *handle, Ordering::Release);
+        handles.push(handle);
+    }
+}
+
+pub(crate) enum LibrarySource {
+    File(CString),
+    Memory(Vec<i8>),
+}
+
+/// Session state replayed on a new server by [`Session::recover`].
+#[derive(Default)]
+pub(crate) struct Journal {
+    libraries: Vec<LibrarySource>,
+    server_vars: Vec<(String, String)>,
+    time: Option<f64>,
+    timeline: Option<TimelineOptions>,
+}
+
+impl Debug for Journal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Journal")
+            .field("libraries", &self.libraries.len())
+            .field(
+                "server_vars",
+                &self.server_vars.iter().map(|(k, _)| k).collect::<Vec<_>>(),
+            )
+            .field("time", &self.time)
+            .field("timeline", &self.timeline)
+            .finish()
+    }
+}
+
+impl Journal {
+    pub(crate) fn add_library(&mut self, library: LibrarySource) {
+        self.libraries.push(library);
+    }
+
+    fn set_server_var(&mut self, key: &str, value: String) {
+        self.server_vars.retain(|(k, _)| k != key);
+        self.server_vars.push((key.to_string(), value));
+    }
+
+    fn replay(&self, session: &Session) -> Result<()> {
+        debug!("Replaying session journal: {self:?}");
+        for library in &self.libraries {
+            match library {
+                LibrarySource::File(path) => {
+                    crate::ffi::load_library_from_file(path, session, true)
+                        .with_context(|| format!("Loading asset library {path:?}"))?;
+                }
+                LibrarySource::Memory(data) => {
+                    crate::ffi::load_library_from_memory(session, data, true)
+                        .context("Loading asset library from memory")?;
+                }
+            }
+        }
+        for (key, value) in &self.server_vars {
+            str::set_value(session, key, value)
+                .with_context(|| format!("Setting server variable {key}"))?;
+        }
+        if let Some(timeline) = &self.timeline {
+            crate::ffi::set_timeline_options(session, &timeline.0)?;
+        }
+        if let Some(time) = self.time {
+            crate::ffi::set_time(session, time)?;
+        }
+        Ok(())
+    }
+}
+
 /// By which means the session communicates with the server.
 #[derive(Debug)]
 pub(crate) struct SessionInner {
-    pub(crate) handle: raw::HAPI_Session,
+    pub(crate) handle: SessionHandle,
     pub(crate) options: SessionOptions,
     // Server options are only available for Thrift servers.
     pub(crate) server_options: Option<ServerOptions>,
     pub(crate) lock: ReentrantMutex<()>,
-    pub(crate) server_pid: Option<u32>,
+    pub(crate) server_pid: Mutex<Option<u32>>,
+    // Only recorded with SessionOptions::auto_recover.
+    pub(crate) journal: Mutex<Journal>,
+    // Names of the variables set with Session::set_server_var, captured by snapshots.
+    pub(crate) server_var_names: Mutex<Vec<String>>,
+    // Held while recovering, set if the current thread is recovering the session.
+    recovery: ReentrantMutex<Cell<bool>>,
+    // Only collected with SessionOptions::collect_stats.
+    stats: Option<Arc<crate::stats::StatsCollector>>,
+    // Only recorded with SessionOptions::record_calls.
+    recorder: Option<Arc<crate::replay::Recorder>>,
 }
 
 /// Session represents a unique connection to the Engine instance and all API calls require a valid session.
@@ -186,8 +385,7 @@ pub struct Session {
 
 impl PartialEq for Session {
     fn eq(&self, other: &Self) -> bool {
-        self.inner.handle.id == other.inner.handle.id
-            && self.inner.handle.type_ == other.inner.handle.type_
+        self.inner.handle.get() == other.inner.handle.get()
     }
 }
 
@@ -200,34 +398,161 @@ pub struct UninitializedSession {
 impl UninitializedSession {
     pub fn initialize(self, session_options: SessionOptions) -> Result<Session> {
         debug!("Initializing session");
+        let recorder = match &session_options.record_calls {
+            Some(path) => Some(Arc::new(crate::replay::Recorder::create(path)?)),
+            None => None,
+        };
         crate::ffi::initialize_session(self.session_handle, &session_options)
             .map(|_| Session {
                 inner: Arc::new(SessionInner {
-                    handle: self.session_handle,
+                    stats: session_options.collect_stats.then(|| {
+                        let stats = Arc::default();
+                        crate::stats::register(&self.session_handle, Arc::clone(&stats));
+                        stats
+                    }),
+                    recorder: recorder.inspect(|recorder| {
+                        crate::replay::register(&self.session_handle, Arc::clone(recorder));
+                    }),
+                    handle: SessionHandle::new(self.session_handle),
                     options: session_options,
                     lock: ReentrantMutex::new(()),
                     server_options: self.server_options,
-                    server_pid: self.server_pid,
+                    server_pid: Mutex::new(self.server_pid),
+                    journal: Mutex::new(Journal::default()),
+                    server_var_names: Mutex::new(Vec::new()),
+                    recovery: ReentrantMutex::new(Cell::new(false)),
                 }),
             })
             .with_context(|| "Calling initialize_session")
+            .inspect_err(|e| {
+                // Without a license the server is useless, closing the connection lets an
+                // auto-close server exit.
+                if e.is_license_error()
+                    && let Err(e) = crate::ffi::close_session_handle(self.session_handle)
+                {
+                    warn!("Could not close unlicensed session: {e}");
+                }
+            })
     }
 }
 
 impl Session {
     /// Return [`SessionType`] current session is initialized with.
     pub fn session_type(&self) -> SessionType {
-        self.inner.handle.type_
+        self.inner.handle.get().type_
     }
 
     /// Return enum with extra connection data such as pipe file or socket.
     pub fn server_pid(&self) -> Option<u32> {
-        self.inner.server_pid
+        *self.inner.server_pid.lock()
     }
 
     #[inline(always)]
     pub(crate) fn ptr(&self) -> *const raw::HAPI_Session {
-        &(self.inner.handle) as *const _
+        self.inner.handle.ptr()
+    }
+
+    /// Snapshot of the Engine API calls made by this session, see [`crate::stats`].
+    /// Empty unless [`SessionOptions::collect_stats`] is set.
+    pub fn stats(&self) -> crate::stats::SessionStats {
+        self.inner
+            .stats
+            .as_ref()
+            .map(|stats| stats.snapshot())
+            .unwrap_or_default()
+    }
+
+    /// Clear the statistics collected so far.
+    pub fn reset_stats(&self) {
+        if let Some(stats) = &self.inner.stats {
+            stats.reset();
+        }
+    }
+
+    /// Record session state for [`Session::recover`] if [`SessionOptions::auto_recover`] is set.
+    pub(crate) fn journal(&self, f: impl FnOnce(&mut Journal)) {
+        if self.inner.options.auto_recover {
+            f(&mut self.inner.journal.lock());
+        }
+    }
+
+    /// If the server is gone, start a new one with the original [`ServerOptions`] and replay
+    /// loaded asset libraries, server variables and time settings.
+    /// Returns `true` if the session was recovered and `false` if it's still valid.
+    /// Requires [`SessionOptions::auto_recover`].
+    pub fn recover(&self) -> Result<bool> {
+        if !self.inner.options.auto_recover {
+            return Err(HapiError::Internal(
+                "Session recovery is not enabled, see SessionOptions::auto_recover".to_string(),
+            ));
+        }
+        let recovering = self.inner.recovery.lock();
+        if recovering.get() || self.is_valid() {
+            return Ok(false);
+        }
+        recovering.set(true);
+        let result = self.restart_server();
+        recovering.set(false);
+        result.map(|_| true)
+    }
+
+    fn restart_server(&self) -> Result<()> {
+        let server_options = self.inner.server_options.clone().ok_or_else(|| {
+            HapiError::Internal("Only Thrift sessions can be recovered".to_string())
+        })?;
+        let pid = self.server_pid();
+        warn!("Session server (pid {pid:?}) is gone, restarting");
+        let session = crate::server::reconnect_or_restart(server_options, pid)?;
+        crate::ffi::initialize_session(session.session_handle, &self.inner.options)
+            .context("Initializing recovered session")?;
+        if let Some(stats) = &self.inner.stats {
+            crate::stats::unregister(&self.inner.handle.get());
+            crate::stats::register(&session.session_handle, Arc::clone(stats));
+        }
+        if let Some(recorder) = &self.inner.recorder {
+            crate::replay::unregister(&self.inner.handle.get());
+            crate::replay::register(&session.session_handle, Arc::clone(recorder));
+        }
+        self.inner.handle.replace(session.session_handle);
+        *self.inner.server_pid.lock() = session.server_pid;
+        self.inner
+            .journal
+            .lock()
+            .replay(self)
+            .context("Replaying session journal")
+    }
+
+    /// Called when an API call failed and the session is not valid.
+    /// Returns the error to report instead of the call error.
+    pub(crate) fn recover_after_error(&self) -> Option<HapiError> {
+        if !self.inner.options.auto_recover
+            || self.inner.server_options.is_none()
+            || self.inner.recovery.lock().get()
+        {
+            return None;
+        }
+        Some(match self.recover() {
+            Ok(_) => HapiError::SessionRecovered {
+                server_pid: self.server_pid(),
+            },
+            Err(e) => HapiError::Context {
+                contexts: vec!["Could not recover session after server crash".to_string()],
+                source: Box::new(e),
+            },
+        })
+    }
+
+    /// Capture the scene, asset libraries, server variables, timeline, time and compositor options,
+    /// see [`crate::snapshot`].
+    pub fn snapshot(&self) -> Result<SessionSnapshot> {
+        debug_assert!(self.is_valid());
+        crate::snapshot::take(self)
+    }
+
+    /// Apply a snapshot taken from this or another session. The current scene is replaced.
+    pub fn restore(&self, snapshot: &SessionSnapshot) -> Result<()> {
+        debug_assert!(self.is_valid());
+        crate::snapshot::restore(self, snapshot)
     }
 
     /// Set environment variable on the server. This is set AFTER the server has started.
@@ -239,7 +564,19 @@ impl Session {
     ) -> Result<()> {
         debug_assert!(self.is_valid());
         debug!("Setting server variable {key}={value:?}");
-        T::set_value(self, key, value)
+        T::set_value(self, key, value)?;
+        {
+            let mut names = self.inner.server_var_names.lock();
+            if !names.iter().any(|name| name == key) {
+                names.push(key.to_string());
+            }
+        }
+        if self.inner.options.auto_recover {
+            // Every variable is a string on the server, whatever type it was set with.
+            let value = str::get_value(self, key)?;
+            self.journal(|journal| journal.set_server_var(key, value));
+        }
+        Ok(())
     }
 
     /// Get environment variable from the server
@@ -397,15 +734,16 @@ impl Session {
     }
 
     /// Find a node given an absolute path. To find a child node, pass the `parent` node
-    /// or use [`HoudiniNode::find_child_node`]
+    /// or use [`HoudiniNode::find_child_node`]. The path is normalized, see [`NodePath`].
     pub fn get_node_from_path(
         &self,
-        path: impl AsRef<str>,
+        path: impl TryInto<NodePath, Error = impl Into<HapiError>>,
         parent: impl Into<Option<NodeHandle>>,
     ) -> Result<Option<HoudiniNode>> {
         debug_assert!(self.is_valid());
-        debug!("Searching node at path: {}", path.as_ref());
-        let path = CString::new(path.as_ref())?;
+        let path: NodePath = path.try_into().map_err(Into::into)?;
+        debug!("Searching node at path: {}", path);
+        let path = CString::new(path.to_string())?;
         match crate::ffi::get_node_from_path(self, parent.into(), &path) {
             Ok(handle) => Ok(NodeHandle(handle).to_node(self).ok()),
             Err(HapiError::Hapi { result_code, .. })
@@ -420,15 +758,16 @@ impl Session {
     /// Find a parameter by path, absolute or relative to a start node.
     pub fn find_parameter_from_path(
         &self,
-        path: impl AsRef<str>,
+        path: impl TryInto<NodePath, Error = impl Into<HapiError>>,
         start: impl Into<Option<NodeHandle>>,
     ) -> Result<Option<Parameter>> {
         debug_assert!(self.is_valid());
-        debug!("Searching parameter at path: {}", path.as_ref());
-        let Some((path, parm)) = path.as_ref().rsplit_once('/') else {
+        let path: NodePath = path.try_into().map_err(Into::into)?;
+        debug!("Searching parameter at path: {}", path);
+        let (Some(parm), Some(path)) = (path.name(), path.parent()) else {
             return Ok(None);
         };
-        let Some(node) = self.get_node_from_path(path, start)? else {
+        let Some(node) = self.get_node_from_path(&path, start)? else {
             debug!("Node {} not found", path);
             return Ok(None);
         };
@@ -472,7 +811,7 @@ impl Session {
         debug!("Loading hip file: {:?}", path.as_ref());
         debug_assert!(self.is_valid());
         let path = utils::path_to_cstring(path)?;
-        crate::ffi::load_hip(self, &path, cook)
+        self.watch("load_hip", || crate::ffi::load_hip(self, &path, cook))
     }
 
     /// Merge a hip file into current session
@@ -480,7 +819,7 @@ impl Session {
         debug!("Merging hip file: {}", name);
         debug_assert!(self.is_valid());
         let name = CString::new(name)?;
-        crate::ffi::merge_hip(self, &name, cook)
+        self.watch("merge_hip", || crate::ffi::merge_hip(self, &name, cook))
     }
 
     /// Get node ids created by merging [`Session::merge_hip`] a hip file.
@@ -577,31 +916,68 @@ impl Session {
     }
 
     /// In threaded mode wait for Session finishes cooking. In single-thread mode, immediately return
+    /// The cook state is polled with [`SessionOptions::cook_backoff`] delays.
     /// See [Documentation](https://www.sidefx.com/docs/hengine/_h_a_p_i__sessions.html)
     pub fn cook(&self) -> Result<CookResult> {
         debug_assert!(self.is_valid());
         debug!("Cooking session..");
-        if self.inner.options.threaded {
-            loop {
-                match self.get_cook_state_status()? {
-                    SessionState::Ready => break Ok(CookResult::Succeeded),
-                    SessionState::ReadyWithFatalErrors => {
-                        self.interrupt()?;
-                        let err = self.get_cook_result_string(StatusVerbosity::Errors)?;
-                        break Ok(CookResult::FatalErrors(err));
-                    }
-                    SessionState::ReadyWithCookErrors => {
-                        let err = self.get_cook_result_string(StatusVerbosity::Errors)?;
-                        break Ok(CookResult::CookErrors(err));
-                    }
-                    // Continue polling
-                    _ => {}
-                }
+        self.watch("cook", || self.wait_for_cook())
+    }
+
+    /// Block until the session is done cooking, without a timeout.
+    pub(crate) fn wait_for_cook(&self) -> Result<CookResult> {
+        let mut delays = self.inner.options.cook_backoff.delays();
+        loop {
+            if let Some(result) = self.poll_cook()? {
+                break Ok(result);
             }
-        } else {
+            std::thread::sleep(delays.next().unwrap_or_default());
+        }
+    }
+
+    /// Run `f` with a deadline, overriding [`SessionOptions::call_timeout`] for the calls it makes.
+    /// See [`CallTimeout`].
+    pub fn with_timeout<T>(
+        &self,
+        timeout: impl Into<CallTimeout>,
+        f: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        crate::watchdog::watch(self, "call", Some(timeout.into()), f)
+    }
+
+    /// Run `f` with [`SessionOptions::call_timeout`].
+    pub(crate) fn watch<T>(&self, operation: &str, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        crate::watchdog::watch(self, operation, self.inner.options.call_timeout, f)
+    }
+
+    /// Returns a future which resolves when the session is done cooking.
+    /// Same as [`Session::cook`] without blocking the calling thread.
+    #[cfg(feature = "async-cooking")]
+    pub fn wait_cook(&self) -> crate::cooking::CookFuture {
+        debug_assert!(self.is_valid());
+        crate::cooking::CookFuture::new(self.clone())
+    }
+
+    /// Check the cook state once, returns `None` if the session is still cooking.
+    pub(crate) fn poll_cook(&self) -> Result<Option<CookResult>> {
+        if !self.inner.options.threaded {
             // In single threaded mode, the cook happens inside of HAPI_CookNode(),
             // and HAPI_GetStatus() will immediately return HAPI_STATE_READY.
-            Ok(CookResult::Succeeded)
+            return Ok(Some(CookResult::Succeeded));
+        }
+        match self.get_cook_state_status()? {
+            SessionState::Ready => Ok(Some(CookResult::Succeeded)),
+            SessionState::ReadyWithFatalErrors => {
+                self.interrupt()?;
+                let err = self.get_cook_result_string(StatusVerbosity::Errors)?;
+                Ok(Some(CookResult::FatalErrors(err)))
+            }
+            SessionState::ReadyWithCookErrors => {
+                let err = self.get_cook_result_string(StatusVerbosity::Errors)?;
+                Ok(Some(CookResult::CookErrors(err)))
+            }
+            // Continue polling
+            _ => Ok(None),
         }
     }
 
@@ -620,7 +996,9 @@ impl Session {
     /// Set Houdini time
     pub fn set_time(&self, time: f64) -> Result<()> {
         debug_assert!(self.is_valid());
-        crate::ffi::set_time(self, time)
+        crate::ffi::set_time(self, time)?;
+        self.journal(|journal| journal.time = Some(time));
+        Ok(())
     }
 
     /// Lock the internal reentrant mutex. Should not be used in general, but may be useful
@@ -632,7 +1010,9 @@ impl Session {
     /// Set Houdini timeline options
     pub fn set_timeline_options(&self, options: TimelineOptions) -> Result<()> {
         debug_assert!(self.is_valid());
-        crate::ffi::set_timeline_options(self, &options.0)
+        crate::ffi::set_timeline_options(self, &options.0)?;
+        self.journal(|journal| journal.timeline = Some(options));
+        Ok(())
     }
 
     /// Get Houdini timeline options
@@ -682,6 +1062,21 @@ impl Session {
         crate::ffi::set_session_sync_info(self, &info.0)
     }
 
+    /// License checked out by this session and the preference its server was started with,
+    /// see [`ServerOptions::with_license_preferences`].
+    pub fn acquired_license(&self) -> Result<AcquiredLicense> {
+        let license = self.get_license_type()?;
+        Ok(AcquiredLicense {
+            license,
+            tier: LicenseTier::of(license),
+            preference: self
+                .inner
+                .server_options
+                .as_ref()
+                .and_then(|options| options.license_preference),
+        })
+    }
+
     /// Get license type used by this session
     pub fn get_license_type(&self) -> Result<LicenseType> {
         debug_assert!(self.is_valid());
@@ -794,6 +1189,11 @@ impl Session {
         })
     }
 
+    /// Typed view of the memory caches with sizes in bytes, see [`crate::cache`].
+    pub fn cache_manager(&self) -> crate::cache::CacheManager {
+        crate::cache::CacheManager::new(self.clone())
+    }
+
     pub fn get_active_cache_names(&self) -> Result<StringArray> {
         debug_assert!(self.is_valid());
         crate::ffi::get_active_cache_names(self)
@@ -863,6 +1263,12 @@ impl Drop for Session {
     fn drop(&mut self) {
         if Arc::strong_count(&self.inner) == 1 {
             debug!("Dropping session pid: {:?}", self.server_pid());
+            if self.inner.stats.is_some() {
+                crate::stats::unregister(&self.inner.handle.get());
+            }
+            if self.inner.recorder.is_some() {
+                crate::replay::unregister(&self.inner.handle.get());
+            }
             if self.is_valid() {
                 if self.inner.options.cleanup
                     && let Err(e) = crate::ffi::cleanup_session(self)
@@ -886,6 +1292,9 @@ impl Drop for Session {
                     let _ = std::fs::remove_file(&transport.pipe_path);
                 }
             }
+            if let Some(pid) = self.server_pid() {
+                crate::server::stop_log_tail(pid);
+            }
         }
     }
 }
@@ -899,6 +1308,16 @@ pub struct SessionOptions {
     pub threaded: bool,
     /// Cleanup session upon close
     pub cleanup: bool,
+    /// Polling delays used when waiting for a threaded session to cook
+    pub cook_backoff: CookBackoff,
+    /// Restart the server and replay the session state after a server crash
+    pub auto_recover: bool,
+    /// Collect Engine API call statistics, see [`Session::stats`]
+    pub collect_stats: bool,
+    /// Record all Engine API calls to this file, see [`crate::replay`]
+    pub record_calls: Option<PathBuf>,
+    /// Deadline for long running calls, see [`CallTimeout`]
+    pub call_timeout: Option<CallTimeout>,
     pub env_files: Option<CString>,
     pub otl_path: Option<CString>,
     pub dso_path: Option<CString>,
@@ -908,6 +1327,7 @@ pub struct SessionOptions {
 
 impl SessionOptions {
     /// A list of Houdini environment files the Engine will load from.
+    /// Use [`crate::houdini_env::HoudiniEnv::from_files`] to preview what they set.
     pub fn houdini_env_files<I>(mut self, files: I) -> Self
     where
         I: IntoIterator,
@@ -984,6 +1404,40 @@ impl SessionOptions {
         self.cleanup = cleanup;
         self
     }
+
+    /// Set the polling delays used when waiting for a threaded session to cook
+    pub fn cook_backoff(mut self, backoff: CookBackoff) -> Self {
+        self.cook_backoff = backoff;
+        self
+    }
+
+    /// Recover Thrift sessions after a server crash, see [`Session::recover`].
+    /// Asset libraries, server variables and time settings are recorded to be replayed on the new
+    /// server. The API call which detected the crash returns [`HapiError::SessionRecovered`].
+    pub fn auto_recover(mut self, recover: bool) -> Self {
+        self.auto_recover = recover;
+        self
+    }
+
+    /// Collect call counts, latencies and transferred bytes per Engine API function,
+    /// see [`Session::stats`].
+    pub fn collect_stats(mut self, collect: bool) -> Self {
+        self.collect_stats = collect;
+        self
+    }
+
+    /// Record every Engine API call made with the session to a file, which can be replayed
+    /// with [`crate::replay::replay`].
+    pub fn record_calls(mut self, path: impl Into<PathBuf>) -> Self {
+        self.record_calls = Some(path.into());
+        self
+    }
+
+    /// Interrupt cooks, hip file and asset loads which take longer than `timeout`, see [`CallTimeout`].
+    pub fn call_timeout(mut self, timeout: impl Into<CallTimeout>) -> Self {
+        self.call_timeout = Some(timeout.into());
+        self
+    }
 }
 
 /// Create an in-process session.
@@ -991,6 +1445,7 @@ impl SessionOptions {
 /// For production use, use [`new_thrift_session`] instead.
 pub fn new_in_process_session(options: Option<SessionOptions>) -> Result<Session> {
     debug!("Creating new in-process session");
+    crate::backend::check()?;
     let session_options = options.unwrap_or_default();
     let session_info = SessionInfo::default();
     let handle = crate::ffi::create_inprocess_session(&session_info.0)?;
@@ -1003,10 +1458,126 @@ pub fn new_in_process_session(options: Option<SessionOptions>) -> Result<Session
     Ok(session)
 }
 
+/// Load a custom session implementation from a shared library and bind it to `session_type`,
+/// which must be one of [`SessionType::Custom1`], [`SessionType::Custom2`] or [`SessionType::Custom3`].
+/// Sessions using it are created with [`new_custom_session`].
+///
+/// # Safety
+///
+/// The Engine loads the library and runs its initialization routines, which can do anything.
+/// Like [`libloading::Library::new`](https://docs.rs/libloading/latest/libloading/struct.Library.html#method.new),
+/// the library must be trusted and implement the HAPI session interface for this Houdini version.
+pub unsafe fn bind_custom_implementation(
+    session_type: SessionType,
+    library: impl AsRef<Path>,
+) -> Result<()> {
+    check_custom_session_type(session_type)?;
+    crate::backend::check()?;
+    let library = utils::path_to_cstring(library)?;
+    crate::ffi::bind_custom_implementation(session_type, &library)
+}
+
+/// Implementation specific info passed to [`new_custom_session`] as a `void*`, null if unused.
+pub type CustomSessionInfo = *mut std::ffi::c_void;
+
+/// Create a session with a custom implementation, e.g. a proxy to a remote engine.
+/// The implementation must be bound with [`bind_custom_implementation`] first.
+/// `session_info` is passed to the implementation as is.
+///
+/// # Safety
+///
+/// `session_info` must be null or point to the type the implementation expects.
+/// The implementation must not keep the pointer after the call returns.
+pub unsafe fn new_custom_session(
+    session_type: SessionType,
+    session_info: CustomSessionInfo,
+    options: Option<SessionOptions>,
+) -> Result<Session> {
+    debug!("Creating new custom session {session_type:?}");
+    check_custom_session_type(session_type)?;
+    crate::backend::check()?;
+    let handle = crate::ffi::create_custom_session(session_type, session_info)?;
+    UninitializedSession {
+        session_handle: handle,
+        server_options: None,
+        server_pid: None,
+    }
+    .initialize(options.unwrap_or_default())
+}
+
+fn check_custom_session_type(session_type: SessionType) -> Result<()> {
+    match session_type {
+        SessionType::Custom1 | SessionType::Custom2 | SessionType::Custom3 => Ok(()),
+        other => Err(HapiError::Internal(format!(
+            "{other:?} is not a custom session type"
+        ))),
+    }
+}
+
 /// Start a Thrift server and initialize a session with it.
+///
+/// With [`ServerOptions::with_license_preferences`] or [`ServerOptions::with_minimum_license`], a
+/// new server is started with the next preference until the session gets an acceptable license.
 pub fn new_thrift_session(
     session_options: SessionOptions,
     server_options: ServerOptions,
+) -> Result<Session> {
+    if server_options.license_preferences.is_empty() && server_options.minimum_license.is_none() {
+        return start_thrift_session(session_options, server_options);
+    }
+    // Only the minimum license is checked without a list of preferences.
+    let preferences: Vec<Option<LicensePreference>> =
+        if server_options.license_preferences.is_empty() {
+            vec![server_options.license_preference]
+        } else {
+            server_options
+                .license_preferences
+                .iter()
+                .copied()
+                .map(Some)
+                .collect()
+        };
+    let mut last_error = None;
+    for preference in preferences {
+        let options = match preference {
+            Some(preference) => server_options.clone().with_license_preference(preference),
+            None => server_options.clone(),
+        };
+        let session = match start_thrift_session(session_options.clone(), options) {
+            Ok(session) => session,
+            Err(e) if e.is_license_error() => {
+                warn!("No license with preference {preference:?}: {e}");
+                last_error = Some(e);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        let acquired = session.acquired_license()?;
+        if let Some(minimum) = server_options.minimum_license
+            && acquired.tier.is_none_or(|tier| tier < minimum)
+        {
+            warn!(
+                "Rejecting {:?} license with preference {preference:?}, {minimum:?} is required",
+                acquired.license
+            );
+            last_error = Some(HapiError::LicenseRejected {
+                license: acquired.license,
+                minimum,
+            });
+            continue;
+        }
+        info!(
+            "Session checked out a {:?} license with preference {preference:?}",
+            acquired.license
+        );
+        return Ok(session);
+    }
+    Err(last_error.expect("at least one license preference is tried"))
+}
+
+fn start_thrift_session(
+    session_options: SessionOptions,
+    server_options: ServerOptions,
 ) -> Result<Session> {
     match server_options.thrift_transport {
         crate::server::ThriftTransport::SharedMemory(_) => {
@@ -1038,3 +1609,177 @@ pub fn simple_session() -> Result<Session> {
         ServerOptions::shared_memory_with_defaults(),
     )
 }
+
+/// A fixed size pool of Thrift sessions, each connected to its own server.
+///
+/// All servers are started from one [`ServerOptions`] template, shared memory and pipe servers get
+/// a unique name, socket servers listen on consecutive ports starting at the template port.
+/// [`SessionPool::checkout`] hands out a [`PooledSession`] which returns to the pool when dropped.
+/// Sessions are checked with [`Session::is_valid`] before being handed out, broken sessions are
+/// reconnected and servers which died are restarted.
+#[derive(Debug, Clone)]
+pub struct SessionPool {
+    inner: Arc<PoolInner>,
+}
+
+#[derive(Debug)]
+struct PoolInner {
+    session_options: SessionOptions,
+    server_options: ServerOptions,
+    size: usize,
+    // Idle slots. A slot without a session failed to restart and is retried on the next checkout.
+    idle: Mutex<Vec<(usize, Option<Session>)>>,
+    available: Condvar,
+}
+
+impl SessionPool {
+    /// Start `size` servers and initialize a session with each of them.
+    /// Returns an error if `size` is 0, since nothing could be checked out of the pool.
+    pub fn new(
+        size: usize,
+        session_options: SessionOptions,
+        server_options: ServerOptions,
+    ) -> Result<Self> {
+        if size == 0 {
+            return Err(HapiError::Internal(
+                "Session pool size must be at least 1".to_string(),
+            ));
+        }
+        debug!("Starting session pool of {size} servers");
+        let inner = PoolInner {
+            session_options,
+            server_options,
+            size,
+            idle: Mutex::new(Vec::with_capacity(size)),
+            available: Condvar::new(),
+        };
+        let sessions = (0..size)
+            .map(|index| {
+                inner
+                    .start_session(index)
+                    .with_context(|| format!("Could not start pool server {index}"))
+                    .map(|session| (index, Some(session)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        *inner.idle.lock() = sessions;
+        Ok(SessionPool {
+            inner: Arc::new(inner),
+        })
+    }
+
+    /// Number of sessions in the pool.
+    pub fn size(&self) -> usize {
+        self.inner.size
+    }
+
+    /// Number of sessions which are not checked out.
+    pub fn idle_count(&self) -> usize {
+        self.inner.idle.lock().len()
+    }
+
+    /// Check out a session, blocking until one is returned to the pool.
+    pub fn checkout(&self) -> Result<PooledSession> {
+        let mut idle = self.inner.idle.lock();
+        let slot = loop {
+            match idle.pop() {
+                Some(slot) => break slot,
+                None => self.inner.available.wait(&mut idle),
+            }
+        };
+        drop(idle);
+        self.prepare(slot)
+    }
+
+    /// Check out a session if one is available.
+    pub fn try_checkout(&self) -> Result<Option<PooledSession>> {
+        let slot = self.inner.idle.lock().pop();
+        slot.map(|slot| self.prepare(slot)).transpose()
+    }
+
+    fn prepare(&self, (index, session): (usize, Option<Session>)) -> Result<PooledSession> {
+        match self.inner.ensure_session(index, session) {
+            Ok(session) => Ok(PooledSession {
+                pool: Arc::clone(&self.inner),
+                index,
+                session: Some(session),
+            }),
+            Err(e) => {
+                self.inner.release(index, None);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl PoolInner {
+    fn start_session(&self, index: usize) -> Result<Session> {
+        let server_options = self.server_options.for_pool_server(index)?;
+        new_thrift_session(self.session_options.clone(), server_options)
+    }
+
+    fn ensure_session(&self, index: usize, session: Option<Session>) -> Result<Session> {
+        let Some(session) = session else {
+            debug!("Restarting pool server {index}");
+            return self.start_session(index);
+        };
+        if session.is_valid() {
+            return Ok(session);
+        }
+        let server_pid = session.server_pid();
+        let server_options = session.inner.server_options.clone();
+        drop(session);
+        if let (Some(pid), Some(server_options)) = (server_pid, server_options)
+            && crate::server::is_process_alive(pid)
+        {
+            warn!("Pool session {index} is invalid, reconnecting to server pid {pid}");
+            match crate::server::connect_to_server(server_options, Some(pid))
+                .and_then(|session| session.initialize(self.session_options.clone()))
+            {
+                Ok(session) => return Ok(session),
+                Err(e) => {
+                    warn!("Could not reconnect to pool server {index}: {e}");
+                    crate::server::stop_unresponsive_server(pid);
+                }
+            }
+        }
+        warn!("Pool server {index} (pid {server_pid:?}) is gone, starting a new one");
+        self.start_session(index)
+    }
+
+    fn release(&self, index: usize, session: Option<Session>) {
+        self.idle.lock().push((index, session));
+        self.available.notify_one();
+    }
+}
+
+/// A session checked out from a [`SessionPool`]. Dereferences to [`Session`] and returns to the
+/// pool when dropped.
+#[derive(Debug)]
+pub struct PooledSession {
+    pool: Arc<PoolInner>,
+    index: usize,
+    session: Option<Session>,
+}
+
+impl PooledSession {
+    /// Index of the server in the pool.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl std::ops::Deref for PooledSession {
+    type Target = Session;
+
+    fn deref(&self) -> &Session {
+        self.session
+            .as_ref()
+            .expect("session is only taken in Drop")
+    }
+}
+
+impl Drop for PooledSession {
+    fn drop(&mut self) {
+        self.pool.release(self.index, self.session.take());
+    }
+}
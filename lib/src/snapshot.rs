@@ -0,0 +1,128 @@
+//! Capture the state of a session and apply it to other sessions.
+//!
+//! [`Session::snapshot`] saves the scene to a hip file and records loaded asset libraries, server
+//! variables set with [`Session::set_server_var`], timeline, time and compositor options. [`Session::restore`] applies a snapshot to
+//! another session, e.g to fork a warmed-up scene into worker sessions without redoing the setup:
+//!
+//! ```no_run
+//! use hapi_rs::session::{SessionOptions, new_thrift_session};
+//! use hapi_rs::server::ServerOptions;
+//! # fn run() -> hapi_rs::Result<()> {
+//! let session = new_thrift_session(
+//!     SessionOptions::default(),
+//!     ServerOptions::shared_memory_with_defaults(),
+//! )?;
+//! session.load_asset_file("otls/hapi_geo.hda")?;
+//! session.create_node("Object/hapi_geo")?;
+//! let snapshot = session.snapshot()?;
+//!
+//! let worker = new_thrift_session(
+//!     SessionOptions::default(),
+//!     ServerOptions::shared_memory_with_defaults(),
+//! )?;
+//! worker.restore(&snapshot)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The hip file is written and read by the server, so the server must share the file system
+//! with the process taking and restoring the snapshot.
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use log::debug;
+
+use crate::errors::{ErrorContext, Result};
+use crate::ffi::{CompositorOptions, TimelineOptions};
+use crate::session::Session;
+
+/// State of a session captured with [`Session::snapshot`].
+#[derive(Debug, Clone)]
+pub struct SessionSnapshot {
+    /// Contents of the hip file saved with [`Session::save_hip`]
+    pub hip: Vec<u8>,
+    /// Loaded asset library files, including the ones Houdini loads by default
+    pub asset_libraries: Vec<PathBuf>,
+    /// Variables set with [`Session::set_server_var`] as `(name, value)` pairs. Variables the
+    /// server sets itself, like `HFS` or `HIP`, are not captured
+    pub server_vars: Vec<(String, String)>,
+    pub timeline: TimelineOptions,
+    pub time: f64,
+    pub compositor: CompositorOptions,
+}
+
+impl SessionSnapshot {
+    /// Write the snapshot hip file to `path`, e.g to open the scene in Houdini.
+    pub fn save_hip(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, &self.hip)?;
+        Ok(())
+    }
+}
+
+fn temp_hip_file() -> Result<tempfile::TempPath> {
+    Ok(tempfile::Builder::new()
+        .prefix("hapi-snapshot-")
+        .suffix(".hip")
+        .tempfile()?
+        .into_temp_path())
+}
+
+pub(crate) fn take(session: &Session) -> Result<SessionSnapshot> {
+    debug!("Taking session snapshot");
+    let hip_file = temp_hip_file()?;
+    session
+        .save_hip(&hip_file, false)
+        .context("Saving snapshot hip file")?;
+    let hip = std::fs::read(&hip_file)?;
+    let asset_libraries = session
+        .get_loaded_asset_libraries()?
+        .into_iter()
+        .filter_map(|library| library.file)
+        .collect();
+    let names = session.inner.server_var_names.lock().clone();
+    let server_vars = names
+        .into_iter()
+        .map(|name| {
+            let value = session.get_server_var::<str>(&name)?;
+            Ok((name, value))
+        })
+        .collect::<Result<_>>()?;
+    Ok(SessionSnapshot {
+        hip,
+        asset_libraries,
+        server_vars,
+        timeline: session.get_timeline_options()?,
+        time: session.get_time()?,
+        compositor: session.get_compositor_options()?,
+    })
+}
+
+pub(crate) fn restore(session: &Session, snapshot: &SessionSnapshot) -> Result<()> {
+    debug!("Restoring session snapshot");
+    let loaded: HashSet<PathBuf> = session
+        .get_loaded_asset_libraries()?
+        .into_iter()
+        .filter_map(|library| library.file)
+        .collect();
+    // Libraries and variables first, the scene may depend on them.
+    for library in &snapshot.asset_libraries {
+        if !loaded.contains(library) {
+            session
+                .load_asset_file(library)
+                .with_context(|| format!("Loading asset library {library:?}"))?;
+        }
+    }
+    for (name, value) in &snapshot.server_vars {
+        session
+            .set_server_var::<str>(name, value)
+            .with_context(|| format!("Setting server variable {name}"))?;
+    }
+    let hip_file = temp_hip_file()?;
+    snapshot.save_hip(&hip_file)?;
+    session
+        .load_hip(&hip_file, false)
+        .context("Loading snapshot hip file")?;
+    session.set_timeline_options(snapshot.timeline.clone())?;
+    session.set_time(snapshot.time)?;
+    session.set_compositor_options(&snapshot.compositor)
+}
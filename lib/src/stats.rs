@@ -0,0 +1,419 @@
+//! Engine API call statistics and tracing.
+//!
+//! Every function in [`crate::raw`] can be measured. Enable statistics for a session with
+//! [`SessionOptions::collect_stats`](crate::session::SessionOptions::collect_stats) and take a
+//! snapshot with [`Session::stats`](crate::session::Session::stats):
+//!
+//! ```no_run
+//! use hapi_rs::session::{SessionOptions, new_thrift_session};
+//! use hapi_rs::server::ServerOptions;
+//! # fn run() -> hapi_rs::Result<()> {
+//! let session = new_thrift_session(
+//!     SessionOptions::default().collect_stats(true),
+//!     ServerOptions::shared_memory_with_defaults(),
+//! )?;
+//! session.create_node("Object/geo")?;
+//! for (function, stats) in session.stats().by_total_time() {
+//!     println!("{function}: {} calls, {:?}", stats.calls, stats.total);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! With the `tracing` feature, every call is also wrapped in a `TRACE` level span named after the
+//! HAPI function (target `hapi_rs::ffi`), with `node_id` and `bytes` fields where they apply.
+//!
+//! Byte counts are derived from the buffer arguments: element size times the number of elements,
+//! for attribute data multiplied by the attribute tuple size.
+
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use parking_lot::{Mutex, RwLock};
+
+use crate::ffi::raw::{HAPI_AttributeInfo, HAPI_Session, HapiResult};
+
+/// Number of buckets in a [`LatencyHistogram`].
+pub const LATENCY_BUCKETS: usize = 24;
+
+/// Call latencies in power of two microsecond buckets: bucket `i` counts calls which took
+/// less than 2<sup>i</sup> µs, the last bucket counts everything slower.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    buckets: [u64; LATENCY_BUCKETS],
+}
+
+impl LatencyHistogram {
+    fn record(&mut self, latency: Duration) {
+        let micros = latency.as_micros();
+        let index = (u128::BITS - micros.leading_zeros()) as usize;
+        self.buckets[index.min(LATENCY_BUCKETS - 1)] += 1;
+    }
+
+    fn merge(&mut self, other: &LatencyHistogram) {
+        for (bucket, count) in self.buckets.iter_mut().zip(other.buckets) {
+            *bucket += count;
+        }
+    }
+
+    /// Number of calls in each bucket.
+    pub fn buckets(&self) -> &[u64; LATENCY_BUCKETS] {
+        &self.buckets
+    }
+
+    /// Upper bound of bucket `index`.
+    pub fn bucket_bound(index: usize) -> Duration {
+        Duration::from_micros(1 << index.min(LATENCY_BUCKETS - 1))
+    }
+
+    /// Upper bound of the bucket containing the `quantile` (0.0..=1.0) of all calls.
+    pub fn quantile(&self, quantile: f64) -> Option<Duration> {
+        let total: u64 = self.buckets.iter().sum();
+        if total == 0 {
+            return None;
+        }
+        let rank = ((total as f64 * quantile.clamp(0.0, 1.0)).ceil() as u64).max(1);
+        let mut seen = 0;
+        self.buckets.iter().enumerate().find_map(|(index, count)| {
+            seen += count;
+            (seen >= rank).then(|| Self::bucket_bound(index))
+        })
+    }
+}
+
+/// Statistics of a single HAPI function.
+#[derive(Debug, Clone, Default)]
+pub struct FunctionStats {
+    /// Number of calls
+    pub calls: u64,
+    /// Number of calls which didn't return [`HapiResult::Success`]
+    pub failures: u64,
+    /// Cumulative time spent in the function
+    pub total: Duration,
+    /// Slowest call
+    pub max: Duration,
+    /// Bytes passed in and out through buffer arguments
+    pub bytes: u64,
+    /// Latency distribution
+    pub latency: LatencyHistogram,
+}
+
+impl FunctionStats {
+    /// Average call latency.
+    pub fn mean(&self) -> Duration {
+        match self.calls {
+            0 => Duration::ZERO,
+            calls => self.total / calls.min(u32::MAX as u64) as u32,
+        }
+    }
+
+    fn record(&mut self, latency: Duration, bytes: u64, failed: bool) {
+        self.calls += 1;
+        self.failures += failed as u64;
+        self.total += latency;
+        self.max = self.max.max(latency);
+        self.bytes += bytes;
+        self.latency.record(latency);
+    }
+}
+
+/// Snapshot of the API calls made by a session, see [`Session::stats`](crate::session::Session::stats).
+#[derive(Debug, Clone, Default)]
+pub struct SessionStats {
+    /// Statistics per HAPI function, e.g `"HAPI_CookNode"`
+    pub functions: BTreeMap<&'static str, FunctionStats>,
+}
+
+impl SessionStats {
+    /// Total number of calls.
+    pub fn total_calls(&self) -> u64 {
+        self.functions.values().map(|f| f.calls).sum()
+    }
+
+    /// Total time spent in API calls.
+    pub fn total_time(&self) -> Duration {
+        self.functions.values().map(|f| f.total).sum()
+    }
+
+    /// Total bytes passed through buffer arguments.
+    pub fn total_bytes(&self) -> u64 {
+        self.functions.values().map(|f| f.bytes).sum()
+    }
+
+    /// Latency distribution of all calls.
+    pub fn latency(&self) -> LatencyHistogram {
+        let mut histogram = LatencyHistogram::default();
+        for function in self.functions.values() {
+            histogram.merge(&function.latency);
+        }
+        histogram
+    }
+
+    /// Functions sorted by cumulative time, slowest first.
+    pub fn by_total_time(&self) -> Vec<(&'static str, &FunctionStats)> {
+        let mut functions: Vec<_> = self.functions.iter().map(|(k, v)| (*k, v)).collect();
+        functions.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.total));
+        functions
+    }
+}
+
+/// Collects statistics for one session.
+#[derive(Debug, Default)]
+pub(crate) struct StatsCollector {
+    functions: Mutex<HashMap<&'static str, FunctionStats>>,
+}
+
+impl StatsCollector {
+    pub(crate) fn snapshot(&self) -> SessionStats {
+        SessionStats {
+            functions: self
+                .functions
+                .lock()
+                .iter()
+                .map(|(k, v)| (*k, v.clone()))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn reset(&self) {
+        self.functions.lock().clear();
+    }
+}
+
+pub(crate) type SessionKey = (i32, i64);
+
+pub(crate) fn session_key(session: &HAPI_Session) -> SessionKey {
+    (session.type_ as i32, session.id)
+}
+
+static COLLECTORS: LazyLock<RwLock<HashMap<SessionKey, Arc<StatsCollector>>>> =
+    LazyLock::new(Default::default);
+// Number of registered collectors, checked before measuring a call.
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+/// Start collecting statistics for calls made with `session`.
+pub(crate) fn register(session: &HAPI_Session, collector: Arc<StatsCollector>) {
+    if COLLECTORS
+        .write()
+        .insert(session_key(session), collector)
+        .is_none()
+    {
+        ACTIVE.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub(crate) fn unregister(session: &HAPI_Session) {
+    if COLLECTORS.write().remove(&session_key(session)).is_some() {
+        ACTIVE.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// A single measured API call, built by the functions in [`crate::raw`].
+#[derive(Default)]
+pub(crate) struct Call {
+    session: Option<SessionKey>,
+    node_id: Option<i32>,
+    element_size: usize,
+    length: Option<i64>,
+    // Attribute data lengths are in tuples rather than elements.
+    length_in_tuples: bool,
+    tuple_size: Option<i32>,
+}
+
+impl Call {
+    /// Whether calls need to be measured at all.
+    #[inline]
+    pub(crate) fn enabled() -> bool {
+        cfg!(feature = "tracing") || ACTIVE.load(Ordering::Relaxed) > 0
+    }
+
+    pub(crate) fn set_session(&mut self, session: impl SessionArg) {
+        self.session = session.session_key();
+    }
+
+    pub(crate) fn set_node_id(&mut self, node_id: impl IntArg) {
+        self.node_id = node_id.value();
+    }
+
+    pub(crate) fn set_buffer(&mut self, buffer: impl BufferArg) {
+        self.element_size = buffer.element_size();
+    }
+
+    pub(crate) fn set_length(&mut self, length: impl IntArg, in_tuples: bool) {
+        self.length = length.value().map(i64::from);
+        self.length_in_tuples = in_tuples;
+    }
+
+    pub(crate) fn set_attr_info(&mut self, info: impl AttrInfoArg) {
+        self.tuple_size = info.tuple_size();
+    }
+
+    fn bytes(&self) -> Option<u64> {
+        let length = self.length.filter(|_| self.element_size > 0)?;
+        let tuple_size = match self.length_in_tuples {
+            true => i64::from(self.tuple_size.unwrap_or(1)),
+            false => 1,
+        };
+        u64::try_from(length * tuple_size * self.element_size as i64).ok()
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn record_span(&self, span: &tracing::Span) {
+        if let Some(node_id) = self.node_id {
+            span.record("node_id", node_id);
+        }
+        if let Some(bytes) = self.bytes() {
+            span.record("bytes", bytes);
+        }
+    }
+
+    pub(crate) fn finish<R: Any>(self, function: &'static str, latency: Duration, result: &R) {
+        let Some(key) = self.session else {
+            return;
+        };
+        if ACTIVE.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+        let Some(collector) = COLLECTORS.read().get(&key).cloned() else {
+            return;
+        };
+        let failed = (result as &dyn Any)
+            .downcast_ref::<HapiResult>()
+            .is_some_and(|r| *r != HapiResult::Success);
+        collector
+            .functions
+            .lock()
+            .entry(function)
+            .or_default()
+            .record(latency, self.bytes().unwrap_or(0), failed);
+    }
+}
+
+pub(crate) trait SessionArg {
+    fn session_key(self) -> Option<SessionKey>;
+}
+
+impl SessionArg for *const HAPI_Session {
+    fn session_key(self) -> Option<SessionKey> {
+        // SAFETY: session pointers passed to the API point to a valid handle or are null.
+        unsafe { self.as_ref() }.map(session_key)
+    }
+}
+
+impl SessionArg for *mut HAPI_Session {
+    // Output argument of the session constructors, not initialized yet.
+    fn session_key(self) -> Option<SessionKey> {
+        None
+    }
+}
+
+pub(crate) trait IntArg {
+    fn value(self) -> Option<i32>;
+}
+
+impl IntArg for i32 {
+    fn value(self) -> Option<i32> {
+        Some(self)
+    }
+}
+
+impl IntArg for *mut i32 {
+    // Output argument, not known until the call returns.
+    fn value(self) -> Option<i32> {
+        None
+    }
+}
+
+pub(crate) trait BufferArg {
+    fn element_size(self) -> usize;
+}
+
+impl<T> BufferArg for *const T {
+    fn element_size(self) -> usize {
+        size_of::<T>()
+    }
+}
+
+impl<T> BufferArg for *mut T {
+    fn element_size(self) -> usize {
+        size_of::<T>()
+    }
+}
+
+pub(crate) trait AttrInfoArg {
+    fn tuple_size(self) -> Option<i32>;
+}
+
+impl AttrInfoArg for *const HAPI_AttributeInfo {
+    fn tuple_size(self) -> Option<i32> {
+        // SAFETY: attribute info pointers passed to the API point to a valid struct or are null.
+        unsafe { self.as_ref() }.map(|info| info.tupleSize)
+    }
+}
+
+impl AttrInfoArg for *mut HAPI_AttributeInfo {
+    fn tuple_size(self) -> Option<i32> {
+        (self as *const HAPI_AttributeInfo).tuple_size()
+    }
+}
+
+/// Feed an argument of a [`crate::raw`] function to a [`Call`], based on the argument name.
+macro_rules! observe_arg {
+    ($call:ident, session, $arg:ident) => {
+        $call.set_session($arg)
+    };
+    ($call:ident, node_id, $arg:ident) => {
+        $call.set_node_id($arg)
+    };
+    ($call:ident, attr_info, $arg:ident) => {
+        $call.set_attr_info($arg)
+    };
+    ($call:ident, data_array, $arg:ident) => {
+        $call.set_buffer($arg)
+    };
+    ($call:ident, data_fixed_array, $arg:ident) => {
+        $call.set_buffer($arg)
+    };
+    ($call:ident, values_array, $arg:ident) => {
+        $call.set_buffer($arg)
+    };
+    ($call:ident, vertex_list_array, $arg:ident) => {
+        $call.set_buffer($arg)
+    };
+    ($call:ident, face_counts_array, $arg:ident) => {
+        $call.set_buffer($arg)
+    };
+    ($call:ident, string_value, $arg:ident) => {
+        $call.set_buffer($arg)
+    };
+    ($call:ident, buffer, $arg:ident) => {
+        $call.set_buffer($arg)
+    };
+    ($call:ident, char_buffer, $arg:ident) => {
+        $call.set_buffer($arg)
+    };
+    ($call:ident, library_buffer, $arg:ident) => {
+        $call.set_buffer($arg)
+    };
+    ($call:ident, length, $arg:ident) => {
+        $call.set_length($arg, true)
+    };
+    ($call:ident, data_fixed_length, $arg:ident) => {
+        $call.set_length($arg, false)
+    };
+    ($call:ident, buffer_length, $arg:ident) => {
+        $call.set_length($arg, false)
+    };
+    ($call:ident, char_array_length, $arg:ident) => {
+        $call.set_length($arg, false)
+    };
+    ($call:ident, library_buffer_length, $arg:ident) => {
+        $call.set_length($arg, false)
+    };
+    ($call:ident, $other:ident, $arg:ident) => {};
+}
+
+pub(crate) use observe_arg;
@@ -0,0 +1,121 @@
+//! Watchdog for calls with a [`CallTimeout`].
+//!
+//! The call runs on the calling thread while a watchdog thread waits for the deadline.
+//! When it passes, the session is interrupted and if the call still hasn't returned after
+//! the grace period, the server process is killed.
+use std::cell::Cell;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+
+use log::{error, warn};
+
+use crate::errors::{HapiError, Result};
+use crate::session::{CallTimeout, Session};
+
+thread_local! {
+    // Set while a watched call runs on this thread, nested calls share its deadline.
+    static WATCHED: Cell<bool> = const { Cell::new(false) };
+}
+
+enum Outcome {
+    InTime,
+    Interrupted,
+    Killed,
+}
+
+struct WatchedGuard;
+
+impl WatchedGuard {
+    fn new() -> Self {
+        WATCHED.set(true);
+        WatchedGuard
+    }
+}
+
+impl Drop for WatchedGuard {
+    fn drop(&mut self) {
+        WATCHED.set(false);
+    }
+}
+
+/// Run `f` with a watchdog, returns [`HapiError::Timeout`] if `timeout` expired.
+pub(crate) fn watch<T>(
+    session: &Session,
+    operation: &str,
+    timeout: Option<CallTimeout>,
+    f: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    let Some(timeout) = timeout else {
+        return f();
+    };
+    if WATCHED.get() {
+        return f();
+    }
+    let (done, finished) = mpsc::channel::<()>();
+    let watchdog = thread::Builder::new()
+        .name("hapi-rs-watchdog".to_string())
+        .spawn({
+            let session = session.clone();
+            let operation = operation.to_string();
+            move || watchdog(&session, &operation, timeout, finished)
+        })?;
+    let result = {
+        let _guard = WatchedGuard::new();
+        f()
+    };
+    // Wakes up the watchdog.
+    drop(done);
+    let outcome = watchdog
+        .join()
+        .map_err(|_| HapiError::Internal("Watchdog thread panicked".to_string()))?;
+    match outcome {
+        Outcome::InTime => result,
+        Outcome::Interrupted | Outcome::Killed => {
+            if let Err(e) = result {
+                warn!("{operation} failed after timing out: {e}");
+            }
+            Err(HapiError::Timeout {
+                operation: operation.to_string(),
+                timeout: timeout.timeout,
+                server_killed: matches!(outcome, Outcome::Killed),
+            })
+        }
+    }
+}
+
+fn watchdog(
+    session: &Session,
+    operation: &str,
+    timeout: CallTimeout,
+    finished: Receiver<()>,
+) -> Outcome {
+    if finished.recv_timeout(timeout.timeout) != Err(RecvTimeoutError::Timeout) {
+        return Outcome::InTime;
+    }
+    warn!(
+        "{operation} timed out after {:?}, interrupting",
+        timeout.timeout
+    );
+    if let Err(e) = crate::ffi::interrupt(session) {
+        error!("Could not interrupt session: {e}");
+    }
+    if finished.recv_timeout(timeout.grace_period) != Err(RecvTimeoutError::Timeout) {
+        return Outcome::Interrupted;
+    }
+    // Only servers started for Thrift sessions can be killed, an in-process session is this process.
+    let pid = session
+        .inner
+        .server_options
+        .as_ref()
+        .and(session.server_pid());
+    match pid {
+        Some(pid) if crate::server::kill_process(pid) => {
+            warn!("{operation} did not respond to interrupt, killed server pid {pid}");
+            Outcome::Killed
+        }
+        _ => {
+            error!("{operation} did not respond to interrupt and the server can't be killed");
+            Outcome::Interrupted
+        }
+    }
+}
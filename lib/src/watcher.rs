@@ -0,0 +1,291 @@
+//! Watch nodes for changes, e.g made by a user in a Houdini GUI synced with [`Session::set_sync`].
+//!
+//! [`NodeWatcher`] polls the cook count, the geometry and the parameter values of its nodes and
+//! reports what changed since the last poll as [`NodeEvent`]s. [`NodeWatcher::spawn`] polls on a
+//! background thread and sends the events to a channel:
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use hapi_rs::watcher::{NodeEvent, NodeWatcher};
+//! # fn run(node: hapi_rs::node::HoudiniNode) -> hapi_rs::Result<()> {
+//! let mut watcher = NodeWatcher::new(&node.session).with_interval(Duration::from_millis(100));
+//! watcher.watch(&node)?;
+//! let (tx, rx) = std::sync::mpsc::channel();
+//! let handle = watcher.spawn(tx)?;
+//! for event in rx.iter().take(10) {
+//!     match event {
+//!         NodeEvent::ParmsChanged { parms, .. } => println!("Changed {parms:?}"),
+//!         NodeEvent::Deleted { .. } => break,
+//!         _ => {}
+//!     }
+//! }
+//! handle.stop()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Geometry changes are read from [`GeoInfo::has_geo_changed`](crate::geometry::GeoInfo::has_geo_changed),
+//! which the Engine resets on every geometry info query, including the ones made outside the watcher.
+use std::collections::HashMap;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use log::debug;
+
+use crate::errors::{HapiError, Result};
+use crate::node::{HoudiniNode, NodeHandle, NodeInfo, NodeType};
+use crate::session::Session;
+use crate::stringhandle::{StringHandle, get_string_array};
+
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Change of a watched node, see [`NodeWatcher::poll`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeEvent {
+    /// Values of these parameters changed
+    ParmsChanged {
+        node: NodeHandle,
+        parms: Vec<String>,
+    },
+    /// The node cooked, `cook_count` is its total cook count
+    Cooked { node: NodeHandle, cook_count: i32 },
+    /// The node, or the display SOP of an object, produced new geometry
+    GeometryChanged { node: NodeHandle },
+    /// The node was deleted and is no longer watched
+    Deleted { node: NodeHandle },
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct ParmValues {
+    ints: Vec<i32>,
+    floats: Vec<f32>,
+    strings: Vec<String>,
+}
+
+impl ParmValues {
+    fn read(node: &HoudiniNode) -> Result<Self> {
+        let (handle, session) = (node.handle, &node.session);
+        let info = &node.info;
+        let mut values = ParmValues::default();
+        if info.parm_int_value_count() > 0 {
+            values.ints =
+                crate::ffi::get_parm_int_values(handle, session, 0, info.parm_int_value_count())?;
+        }
+        if info.parm_float_value_count() > 0 {
+            values.floats = crate::ffi::get_parm_float_values(
+                handle,
+                session,
+                0,
+                info.parm_float_value_count(),
+            )?;
+        }
+        if info.parm_string_value_count() > 0 {
+            values.strings = crate::ffi::get_parm_string_values(
+                handle,
+                session,
+                0,
+                info.parm_string_value_count(),
+            )?
+            .into();
+        }
+        Ok(values)
+    }
+}
+
+// Compare a value range of a parameter, values added or removed by multiparms count as changed.
+fn differs<T: PartialEq>(old: &[T], new: &[T], start: i32, size: i32) -> bool {
+    if start < 0 {
+        return false;
+    }
+    let range = start as usize..(start + size.max(0)) as usize;
+    old.get(range.clone()) != new.get(range)
+}
+
+fn changed_parms(node: &HoudiniNode, old: &ParmValues, new: &ParmValues) -> Result<Vec<String>> {
+    let handles: Vec<StringHandle> = crate::ffi::get_parameters(node)?
+        .iter()
+        .filter(|info| {
+            differs(&old.ints, &new.ints, info.intValuesIndex, info.size)
+                || differs(&old.floats, &new.floats, info.floatValuesIndex, info.size)
+                || differs(
+                    &old.strings,
+                    &new.strings,
+                    info.stringValuesIndex,
+                    info.size,
+                )
+        })
+        .map(|info| StringHandle(info.nameSH))
+        .collect();
+    if handles.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(get_string_array(&handles, &node.session)?.into())
+}
+
+// Querying the geo info resets its changed flag, so the flag is kept per geometry node for
+// an object and its display SOP watched together.
+fn geometry_changed(node: &HoudiniNode, changes: &mut HashMap<NodeHandle, bool>) -> Result<bool> {
+    let info = match node.info.node_type() {
+        NodeType::Sop if changes.contains_key(&node.handle) => return Ok(changes[&node.handle]),
+        NodeType::Sop => crate::ffi::get_geo_info(&node.session, node.handle)?,
+        // An empty object has no display geometry yet.
+        NodeType::Obj if node.info.child_node_count() == 0 => return Ok(false),
+        NodeType::Obj => crate::ffi::get_geo_display_info(node)?,
+        _ => return Ok(false),
+    };
+    Ok(*changes
+        .entry(NodeHandle(info.nodeId))
+        .or_insert(info.hasGeoChanged > 0))
+}
+
+#[derive(Debug)]
+struct WatchedNode {
+    node: HoudiniNode,
+    values: ParmValues,
+}
+
+/// Polls nodes for changes, see the [module docs](self).
+#[derive(Debug)]
+pub struct NodeWatcher {
+    session: Session,
+    nodes: Vec<WatchedNode>,
+    interval: Duration,
+}
+
+impl NodeWatcher {
+    pub fn new(session: &Session) -> Self {
+        NodeWatcher {
+            session: session.clone(),
+            nodes: Vec::new(),
+            interval: DEFAULT_INTERVAL,
+        }
+    }
+
+    /// Time between polls of [`NodeWatcher::spawn`], default is 250ms.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Start watching a node. Changes made before this call are not reported.
+    pub fn watch(&mut self, node: &HoudiniNode) -> Result<()> {
+        debug_assert!(node.is_valid()?, "Invalid node: {}", node.path()?);
+        if self.is_watching(node.handle) {
+            return Ok(());
+        }
+        let _lock = self.session.lock();
+        let node = HoudiniNode::new(
+            self.session.clone(),
+            node.handle,
+            Some(NodeInfo::new(&self.session, node.handle)?),
+        )?;
+        // Reset the changed flag of the geometry.
+        geometry_changed(&node, &mut HashMap::new())?;
+        let values = ParmValues::read(&node)?;
+        self.nodes.push(WatchedNode { node, values });
+        Ok(())
+    }
+
+    /// Stop watching a node.
+    pub fn unwatch(&mut self, node: NodeHandle) {
+        self.nodes.retain(|watched| watched.node.handle != node);
+    }
+
+    pub fn is_watching(&self, node: NodeHandle) -> bool {
+        self.nodes.iter().any(|watched| watched.node.handle == node)
+    }
+
+    /// Changes of all watched nodes since the last poll. Per node the events are ordered
+    /// [`NodeEvent::Cooked`], [`NodeEvent::GeometryChanged`], [`NodeEvent::ParmsChanged`].
+    /// Deleted nodes are reported once and then removed from the watcher.
+    pub fn poll(&mut self) -> Result<Vec<NodeEvent>> {
+        let session = self.session.clone();
+        let _lock = session.lock();
+        let mut events = Vec::new();
+        let mut deleted = Vec::new();
+        let mut geo_changes = HashMap::new();
+        for watched in &mut self.nodes {
+            let handle = watched.node.handle;
+            if !crate::ffi::is_node_valid(&session, &watched.node.info.0)? {
+                events.push(NodeEvent::Deleted { node: handle });
+                deleted.push(handle);
+                continue;
+            }
+            let info = NodeInfo::new(&session, handle)?;
+            let cook_count = info.total_cook_count();
+            let cooked = cook_count != watched.node.info.total_cook_count();
+            let node = HoudiniNode::new(session.clone(), handle, Some(info))?;
+            if cooked {
+                events.push(NodeEvent::Cooked {
+                    node: handle,
+                    cook_count,
+                });
+            }
+            if geometry_changed(&node, &mut geo_changes)? {
+                events.push(NodeEvent::GeometryChanged { node: handle });
+            }
+            let values = ParmValues::read(&node)?;
+            if values != watched.values {
+                let parms = changed_parms(&node, &watched.values, &values)?;
+                events.push(NodeEvent::ParmsChanged {
+                    node: handle,
+                    parms,
+                });
+            }
+            *watched = WatchedNode { node, values };
+        }
+        self.nodes
+            .retain(|watched| !deleted.contains(&watched.node.handle));
+        Ok(events)
+    }
+
+    /// Poll on a background thread and send the events to `events`. The thread stops when
+    /// the [`WatchHandle`] is stopped or dropped, the receiver is dropped, all watched nodes
+    /// are deleted, or a poll fails.
+    pub fn spawn(mut self, events: Sender<NodeEvent>) -> Result<WatchHandle> {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let thread = std::thread::Builder::new()
+            .name("hapi-rs-node-watcher".to_string())
+            .spawn(move || {
+                loop {
+                    for event in self.poll()? {
+                        if events.send(event).is_err() {
+                            debug!("Node event receiver dropped, stopping the watcher");
+                            return Ok(());
+                        }
+                    }
+                    if self.nodes.is_empty() {
+                        return Ok(());
+                    }
+                    match stopped.recv_timeout(self.interval) {
+                        Err(RecvTimeoutError::Timeout) => {}
+                        Ok(()) | Err(RecvTimeoutError::Disconnected) => return Ok(()),
+                    }
+                }
+            })?;
+        Ok(WatchHandle { stop, thread })
+    }
+}
+
+/// Handle to a watcher running on a background thread, see [`NodeWatcher::spawn`].
+#[derive(Debug)]
+pub struct WatchHandle {
+    stop: Sender<()>,
+    thread: JoinHandle<Result<()>>,
+}
+
+impl WatchHandle {
+    /// Returns `true` if the watcher thread has stopped.
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    /// Stop polling and wait for the thread. Returns the error if a poll failed.
+    pub fn stop(self) -> Result<()> {
+        let _ = self.stop.send(());
+        self.thread
+            .join()
+            .map_err(|_| HapiError::Internal("Node watcher thread panicked".to_string()))?
+    }
+}
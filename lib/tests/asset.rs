@@ -7,6 +7,10 @@ mod utils;
 use utils::{HdaFile, with_session, with_session_asset};
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn asset_get_count() {
     with_session_asset(HdaFile::Parameters, |lib| {
         assert_eq!(lib.get_asset_count()?, 1);
@@ -16,6 +20,10 @@ fn asset_get_count() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn asset_load_from_memory() {
     with_session(|session| {
         let mem = std::fs::read("../otls/hapi_geo.hda").unwrap();
@@ -26,6 +34,10 @@ fn asset_load_from_memory() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn asset_get_names() {
     with_session_asset(HdaFile::Parameters, |lib| {
         assert!(
@@ -38,6 +50,10 @@ fn asset_get_names() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn asset_parameter_tags() {
     with_session_asset(HdaFile::Parameters, |lib| {
         let parms = lib.get_asset_parms("Object/hapi_parms").unwrap();
@@ -55,6 +71,10 @@ fn asset_parameter_tags() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn asset_get_first_name() {
     with_session_asset(HdaFile::Parameters, |lib| {
         assert_eq!(
@@ -67,6 +87,10 @@ fn asset_get_first_name() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn asset_load_from_file() {
     with_session(|session| {
         let lib = AssetLibrary::from_file(session.clone(), HdaFile::Parameters.path())?;
@@ -81,6 +105,10 @@ fn asset_load_from_file() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn asset_default_parameters() {
     with_session_asset(HdaFile::Parameters, |lib| {
         let parms = lib.get_asset_parms("Object/hapi_parms")?;
@@ -101,6 +129,10 @@ fn asset_default_parameters() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn asset_menu_parameters() {
     with_session_asset(HdaFile::Parameters, |lib| {
         let parms = lib.get_asset_parms("Object/hapi_parms")?;
@@ -122,6 +154,10 @@ fn asset_menu_parameters() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn asset_create_node_fully_qualified() {
     use hapi_rs::HapiError;
     with_session_asset(HdaFile::Parameters, |lib| {
@@ -139,6 +175,10 @@ fn asset_create_node_fully_qualified() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn asset_try_create_first() {
     with_session_asset(HdaFile::Parameters, |lib| {
         assert_eq!(
@@ -153,6 +193,10 @@ fn asset_try_create_first() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn asset_parameters_iter() {
     with_session_asset(HdaFile::Parameters, |lib| {
         let parms = lib.get_asset_parms("Object/hapi_parms")?;
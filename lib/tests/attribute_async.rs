@@ -14,6 +14,10 @@ mod utils;
 use utils::{HdaFile, create_single_point_geo, with_async_session};
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_set_dictionary_attribute_async() {
     with_async_session(|session| {
         let geo = create_single_point_geo(&session)?;
@@ -41,6 +45,10 @@ fn geometry_set_dictionary_attribute_async() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_test_get_numeric_attribute_async() {
     with_async_session(|session| {
         session.load_asset_file(HdaFile::Geometry.path())?;
@@ -72,6 +80,10 @@ fn geometry_test_get_numeric_attribute_async() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_test_get_string_attribute_async() {
     with_async_session(|session| {
         session.load_asset_file(HdaFile::Geometry.path())?;
@@ -97,6 +109,10 @@ fn geometry_test_get_string_attribute_async() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_test_get_string_array_attribute_async() {
     with_async_session(|session| {
         session.load_asset_file(HdaFile::Geometry.path())?;
@@ -124,6 +140,10 @@ fn geometry_test_get_string_array_attribute_async() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_test_get_dictionary_array_attribute_async() {
     with_async_session(|session| {
         session.load_asset_file(HdaFile::Geometry.path())?;
@@ -16,17 +16,22 @@ use utils::{
 
 #[test]
 fn geometry_wrong_attribute() {
-    with_test_geometry(|geometry| {
+    with_session(|session| {
+        let geometry = create_triangle(&session)?;
         let foo_bar = geometry
             .get_attribute(0, AttributeOwner::Prim, c"foo_bar")
             .expect("attribute");
         assert!(foo_bar.is_none());
-        Ok(())
+        geometry.node.delete()
     })
     .unwrap()
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_attribute_names() {
     with_test_geometry(|geo| {
         let part = geo.part_info(0).unwrap();
@@ -72,10 +77,7 @@ fn geometry_numeric_attributes() {
 #[test]
 fn numeric_attr_read_into_reuses_buffer() {
     with_session(|session| {
-        session.load_asset_file(HdaFile::Geometry.path())?;
-        let node = session.create_node("Object/hapi_geo")?;
-        node.cook_blocking()?;
-        let geo = node.geometry()?.expect("must have geometry");
+        let geo = create_triangle(&session)?;
         let part = geo.part_info(0)?;
         let attr = geo
             .get_attribute(0, AttributeOwner::Point, AttributeName::P)?
@@ -94,7 +96,7 @@ fn numeric_attr_read_into_reuses_buffer() {
         attr.read_into(part.part_id(), &mut buffer)?;
         assert_eq!(buffer.len(), expected.len());
         assert_eq!(buffer, expected);
-        node.delete()
+        geo.node.delete()
     })
     .unwrap()
 }
@@ -132,6 +134,10 @@ fn geometry_create_string_attrib() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_set_unique_str_attrib_value() {
     with_session(|session| {
         let geo = create_triangle(&session)?;
@@ -157,15 +163,13 @@ fn geometry_set_unique_str_attrib_value() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn string_attr_set_indexed_updates_values() {
     with_session(|session| {
-        session.load_asset_file(HdaFile::Geometry.path())?;
-        let asset_node = session.create_node("Object/hapi_geo")?;
-        asset_node.cook_blocking()?;
-        let asset_geo = asset_node.geometry()?.expect("must have geometry");
-        let point_count = asset_geo.part_info(0)?.point_count();
-        asset_node.delete()?;
-
+        let point_count = 8;
         let input = session.create_input_node("indexed_string_attr", None)?;
         let part = PartInfo::default()
             .with_part_type(PartType::Mesh)
@@ -210,6 +214,10 @@ fn string_attr_set_indexed_updates_values() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_set_unique_int_attrib_value() {
     with_session(|session| {
         let geo = create_triangle(&session)?;
@@ -235,6 +243,10 @@ fn geometry_set_unique_int_attrib_value() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_create_string_array_attrib() {
     with_session(|session| {
         let geo = create_triangle(&session)?;
@@ -289,6 +301,10 @@ fn geometry_create_string_array_attrib() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_attribute_storage_type() -> hapi_rs::Result<()> {
     with_test_geometry(|geo| {
         let attrib_list = [
@@ -321,6 +337,10 @@ fn geometry_attribute_storage_type() -> hapi_rs::Result<()> {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_string_array_attribute() {
     with_test_geometry(|geo| {
         let attr = geo
@@ -348,6 +368,10 @@ fn geometry_string_array_attribute() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_test_get_dictionary_attributes() {
     use hapi_rs::attribute::DictionaryAttr;
     use std::collections::HashMap;
@@ -376,6 +400,10 @@ fn geometry_test_get_dictionary_attributes() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn dictionary_array_attr_get_returns_expected_values() {
     use std::collections::HashMap;
     use std::str::FromStr;
@@ -417,6 +445,10 @@ fn dictionary_array_attr_get_returns_expected_values() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_test_set_dictionary_attributes() {
     use std::collections::HashMap;
     use std::str::FromStr;
@@ -455,6 +487,10 @@ fn geometry_test_set_dictionary_attributes() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_get_set_dictionary_array_attribute() {
     use std::collections::HashMap;
     use tinyjson::JsonValue;
@@ -496,24 +532,30 @@ fn geometry_get_set_dictionary_array_attribute() {
 
 #[test]
 fn attribute_send_to_thread() {
-    with_test_geometry(|geo| {
-        let str_attr = geo
-            .get_attribute(0, AttributeOwner::Point, c"pscale")
+    with_session(|session| {
+        let geo = create_triangle(&session)?;
+        let attr = geo
+            .get_attribute(0, AttributeOwner::Point, AttributeName::P)
             .unwrap()
             .unwrap();
         std::thread::spawn(move || {
-            if let Some(attr) = str_attr.downcast::<NumericAttr<f32>>() {
-                let _ = attr.get(0);
-            }
+            let attr = attr
+                .downcast::<NumericAttr<f32>>()
+                .expect("NumericAttr<f32>");
+            assert_eq!(attr.get(0).unwrap().len(), 9);
         })
         .join()
         .unwrap();
-        Ok(())
+        geo.node.delete()
     })
     .unwrap()
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_read_array_attributes() {
     with_test_geometry(|geo| {
         let attr = geo
@@ -542,6 +584,10 @@ fn geometry_read_array_attributes() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_create_and_set_array_attributes() {
     with_session(|session| {
         let input = session.create_input_node("test", None).unwrap();
@@ -0,0 +1,67 @@
+use hapi_rs::{
+    Result,
+    batch::{Batch, BatchJob, BatchOutput, JobOutput},
+    geometry::GeoFormat,
+};
+
+mod utils;
+use utils::{fake_session_pool, with_fake_engine};
+
+#[test]
+fn batch_run_with_pool() -> Result<()> {
+    with_fake_engine(|_| {
+        let pool = fake_session_pool(2);
+        let mut jobs: Vec<_> = (1..=4)
+            .map(|i| BatchJob::new().with_parm("size", [i as f32; 3]))
+            .collect();
+        jobs.insert(2, BatchJob::new().with_parm("nope", 1));
+        jobs.push(BatchJob::new().with_parm("size", "big"));
+        let batch =
+            Batch::new("otls/box.hda", BatchOutput::Memory(GeoFormat::Geo)).with_asset("Sop/box");
+        let report = batch.run_with_pool(&pool, jobs)?;
+        assert_eq!(report.jobs.len(), 6);
+        assert!(
+            report
+                .jobs
+                .iter()
+                .enumerate()
+                .all(|(i, job)| job.index == i)
+        );
+        let failed: Vec<_> = report.failed().map(|job| job.index).collect();
+        assert_eq!(failed, [2, 5]);
+        assert!(report.jobs[2].cook_result.is_none());
+        let outputs: Vec<_> = report
+            .succeeded()
+            .map(|job| match &job.result {
+                Ok(JobOutput::Memory(bytes)) => bytes.clone(),
+                other => panic!("Unexpected job output: {other:?}"),
+            })
+            .collect();
+        assert_eq!(outputs.len(), 4);
+        assert!(outputs.iter().all(|bytes| !bytes.is_empty()));
+        assert_ne!(outputs[0], outputs[1]);
+        // Job nodes are deleted.
+        assert_eq!(pool.idle_count(), 2);
+        let session = pool.checkout()?;
+        assert_eq!(session.create_node("Sop/box")?.name()?, "box1");
+        drop(session);
+
+        let dir = tempfile::tempdir()?;
+        let pattern = dir.path().join("box_{job}.geo");
+        let report = Batch::new("otls/box.hda", BatchOutput::files(&pattern))
+            .with_asset("Sop/box")
+            .run_with_pool(
+                &pool,
+                [BatchJob::new(), BatchJob::new().with_parm("size", 2)],
+            )?;
+        assert!(report.all_succeeded());
+        for job in &report.jobs {
+            let Ok(JobOutput::File(path)) = &job.result else {
+                panic!("Unexpected job output: {:?}", job.result);
+            };
+            assert_eq!(path, &dir.path().join(format!("box_{}.geo", job.index)));
+            assert!(path.exists());
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,38 @@
+use hapi_rs::Result;
+
+mod utils;
+use utils::with_fake_session;
+
+const MB: u64 = 1024 * 1024;
+
+#[test]
+fn cache_manager() -> Result<()> {
+    with_fake_session(|session| {
+        let caches = session.cache_manager();
+        assert!(caches.names()?.contains(&"SOP Cache".to_string()));
+        let info = caches.info("HDA Contents Cache")?;
+        assert_eq!(info.max, None);
+        caches.set_max_bytes("SOP Cache", 10 * MB + 1)?;
+        assert_eq!(caches.info("SOP Cache")?.max, Some(11 * MB));
+        assert!(caches.info("Nope Cache").is_err());
+
+        let before = caches.report()?;
+        for _ in 0..3 {
+            session.create_node("Sop/box")?.cook_blocking()?;
+        }
+        let after = caches.report()?;
+        assert_eq!(
+            after.growth_since(&before),
+            [("SOP Cache".to_string(), 3 * MB as i64)]
+        );
+        assert_eq!(after.total_bytes() - before.total_bytes(), 3 * MB);
+        assert!(after.to_string().contains("SOP Cache"));
+        assert_eq!(after.near_limit(0.01).count(), 1);
+
+        caches.trim("SOP Cache", MB)?;
+        assert!(caches.info("SOP Cache")?.current <= MB);
+        caches.clear_all()?;
+        assert_eq!(caches.report()?.total_bytes(), 0);
+        Ok(())
+    })
+}
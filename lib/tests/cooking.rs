@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use hapi_rs::{
+    HapiError, Result,
+    session::{CallTimeout, CookBackoff, CookOptions, CookResult, SessionOptions},
+};
+
+mod utils;
+use utils::{fake_session, with_fake_engine, with_fake_session, with_threaded_fake_session};
+
+#[test]
+fn cooking_backoff_delays() {
+    let ms = Duration::from_millis;
+    let backoff = CookBackoff {
+        initial: ms(1),
+        max: ms(5),
+        factor: 2,
+    };
+    let delays: Vec<_> = backoff.delays().take(5).collect();
+    assert_eq!(delays, [ms(1), ms(2), ms(4), ms(5), ms(5)]);
+    let backoff = CookBackoff {
+        factor: 0,
+        ..backoff
+    };
+    assert!(backoff.delays().take(3).all(|delay| delay == ms(1)));
+}
+
+#[test]
+fn cooking_threaded() -> Result<()> {
+    with_threaded_fake_session(|session| {
+        let node = session.create_node("Sop/box")?;
+        assert_eq!(node.cook_blocking()?, CookResult::Succeeded);
+        node.cook()?;
+        assert!(session.is_cooking()?);
+        assert_eq!(session.cook()?, CookResult::Succeeded);
+        assert!(!session.is_cooking()?);
+        Ok(())
+    })
+}
+
+#[test]
+fn cooking_with_progress() -> Result<()> {
+    with_threaded_fake_session(|session| {
+        let node = session.create_node("Sop/box")?;
+        let (tx, rx) = std::sync::mpsc::channel();
+        let handle = node.cook_with_progress(move |progress| {
+            let _ = tx.send(progress);
+        })?;
+        assert_eq!(handle.wait()?, CookResult::Succeeded);
+        let updates: Vec<_> = rx.try_iter().collect();
+        assert_eq!(updates.len(), 4);
+        assert!(updates.iter().all(|p| p.total == 3));
+        assert!(updates.windows(2).all(|w| w[0].current <= w[1].current));
+        assert!(updates.windows(2).all(|w| w[0].elapsed <= w[1].elapsed));
+        assert_eq!(updates[0].status, "Cooking");
+        assert_eq!(updates.last().unwrap().status, "Ready");
+        Ok(())
+    })
+}
+
+#[test]
+fn cooking_cancel() -> Result<()> {
+    with_threaded_fake_session(|session| {
+        let node = session.create_node("Sop/box")?;
+        let (progress_tx, progress_rx) = std::sync::mpsc::channel();
+        let (resume_tx, resume_rx) = std::sync::mpsc::channel::<()>();
+        let handle = node.cook_with_progress(move |progress| {
+            let _ = progress_tx.send(progress);
+            let _ = resume_rx.recv();
+        })?;
+        assert_eq!(progress_rx.recv().unwrap().status, "Cooking");
+        handle.cancel()?;
+        drop(resume_tx);
+        assert_eq!(handle.wait()?, CookResult::Interrupted);
+        assert!(!session.is_cooking()?);
+        Ok(())
+    })
+}
+
+#[test]
+fn cooking_cancel_after_completion() -> Result<()> {
+    with_threaded_fake_session(|session| {
+        let node = session.create_node("Sop/box")?;
+        let (progress_tx, progress_rx) = std::sync::mpsc::channel();
+        let (resume_tx, resume_rx) = std::sync::mpsc::channel::<()>();
+        // Hold the progress thread after the server finished, before it returns the result.
+        let handle = node.cook_with_progress(move |progress| {
+            let ready = progress.status == "Ready";
+            let _ = progress_tx.send(progress);
+            if ready {
+                let _ = resume_rx.recv();
+            }
+        })?;
+        while progress_rx.recv().unwrap().status != "Ready" {}
+        handle.cancel()?;
+        drop(resume_tx);
+        assert_eq!(handle.wait()?, CookResult::Succeeded);
+        Ok(())
+    })
+}
+
+#[test]
+fn cooking_cancel_single_threaded() -> Result<()> {
+    with_fake_session(|session| {
+        let node = session.create_node("Sop/box")?;
+        let (resume_tx, resume_rx) = std::sync::mpsc::channel::<()>();
+        let handle = node.cook_with_progress(move |_| {
+            let _ = resume_rx.recv();
+        })?;
+        handle.cancel()?;
+        drop(resume_tx);
+        assert_eq!(handle.wait()?, CookResult::Succeeded);
+        Ok(())
+    })
+}
+
+#[test]
+fn cooking_call_timeout() -> Result<()> {
+    with_fake_engine(|_| {
+        // Every cook state poll takes longer than the timeout.
+        let options = SessionOptions::default()
+            .threaded(true)
+            .cook_backoff(CookBackoff::fixed(Duration::from_millis(200)))
+            .call_timeout(
+                CallTimeout::new(Duration::from_millis(20))
+                    .with_grace_period(Duration::from_secs(5)),
+            );
+        let session = fake_session(options);
+        let node = session.create_node("Sop/box")?;
+        let error = node.cook_blocking().unwrap_err();
+        assert!(error.is_timeout(), "{error}");
+        assert!(matches!(
+            error,
+            HapiError::Timeout {
+                server_killed: false,
+                ..
+            }
+        ));
+        assert!(!session.is_cooking()?);
+        let result = node.cook_with_timeout(&CookOptions::default(), Duration::from_secs(10))?;
+        assert_eq!(result, CookResult::Succeeded);
+        Ok(())
+    })
+}
+
+#[cfg(feature = "async-cooking")]
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    struct ThreadWaker(std::thread::Thread);
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = std::pin::pin!(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+#[cfg(feature = "async-cooking")]
+#[test]
+fn cooking_async() -> Result<()> {
+    with_threaded_fake_session(|session| {
+        let node = session.create_node("Sop/box")?;
+        assert_eq!(block_on(node.cook_async())?, CookResult::Succeeded);
+        node.cook()?;
+        assert_eq!(block_on(session.wait_cook())?, CookResult::Succeeded);
+
+        let error = session.create_node("Sop/error")?;
+        if let hapi_rs::parameter::Parameter::String(text) = error.parameter("text")? {
+            text.set(0, "boom")?;
+        }
+        let result = block_on(error.cook_async())?;
+        assert!(matches!(result, CookResult::CookErrors(ref msg) if msg.contains("boom")));
+        Ok(())
+    })
+}
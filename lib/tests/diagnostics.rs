@@ -0,0 +1,65 @@
+use hapi_rs::{
+    CookMessage, Result, diagnostics::Severity, enums::StatusVerbosity, network::NetworkBuilder,
+};
+
+mod utils;
+use utils::with_fake_session;
+
+#[test]
+fn diagnostics_collect() -> Result<()> {
+    with_fake_session(|session| {
+        let geo = session.create_node("Object/geo")?;
+        let network = NetworkBuilder::new()
+            .node("base", "box", |n| n)
+            .node("note", "error", |n| {
+                n.with_parm("text", "Just saying").with_parm("severity", 0)
+            })
+            .node("old", "error", |n| {
+                n.with_parm("text", "Deprecated input")
+                    .with_parm("severity", 1)
+            })
+            .node("broken", "error", |n| n.with_parm("text", "Boom"))
+            .create(&geo)?;
+        for name in ["note", "old", "broken"] {
+            network.get(name).unwrap().cook_blocking()?;
+        }
+        let report = geo.cook_diagnostics()?;
+        assert_eq!(report.len(), 3, "{report}");
+        assert!(report.has_errors());
+        let error = report.errors().next().unwrap();
+        assert_eq!(error.node_path, "/obj/geo1/broken");
+        assert_eq!(error.message, "Boom");
+        let warning = report.warnings().next().unwrap();
+        assert_eq!(warning.node_path, "/obj/geo1/old");
+        assert_eq!(warning.message, "Deprecated input");
+        assert_eq!(report.messages().count(), 1);
+
+        let severe = report.at_least(Severity::Warning);
+        assert_eq!(severe.len(), 2);
+        assert!(
+            severe
+                .iter()
+                .all(|entry| entry.severity >= Severity::Warning)
+        );
+        assert!(
+            report
+                .to_string()
+                .contains("/obj/geo1/old: Warning: Deprecated input")
+        );
+        assert!(network.get("base").unwrap().cook_diagnostics()?.is_empty());
+        let session_result = session.get_cook_result_string(StatusVerbosity::Errors)?;
+        let messages = CookMessage::parse(&session_result);
+        assert_eq!(messages.len(), 1, "{session_result}");
+        assert_eq!(messages[0].node_path.as_deref(), Some("/obj/geo1/broken"));
+        assert_eq!(messages[0].severity, Severity::Error);
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&severe).unwrap();
+            assert!(json.contains(r#""severity":"warning""#), "{json}");
+            let parsed: hapi_rs::diagnostics::CookDiagnostics =
+                serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, severe);
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,35 @@
+#![cfg(all(feature = "dynamic-load", not(feature = "link")))]
+
+use hapi_rs::HapiError;
+use hapi_rs::backend::DynamicBackend;
+use hapi_rs::session::new_in_process_session;
+
+#[test]
+fn dynamic_load_missing_library() {
+    let hfs = tempfile::tempdir().unwrap();
+    let Err(HapiError::Backend(message)) = DynamicBackend::from_hfs(hfs.path()) else {
+        panic!("Expected a backend error");
+    };
+    assert!(message.contains("libHAPIL"), "{message}");
+}
+
+#[test]
+fn dynamic_load_invalid_library() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), b"not a library").unwrap();
+    assert!(matches!(
+        DynamicBackend::from_library(file.path()),
+        Err(HapiError::Backend(_))
+    ));
+}
+
+#[test]
+fn dynamic_load_error_reported_at_session_creation() {
+    if std::env::var_os("HFS").is_some() {
+        return;
+    }
+    let Err(HapiError::Backend(message)) = new_in_process_session(None) else {
+        panic!("Expected a backend error");
+    };
+    assert!(message.contains("HFS"), "{message}");
+}
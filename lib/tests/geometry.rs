@@ -1,5 +1,9 @@
 use hapi_rs::geometry::extra::GeometryExtension;
-use hapi_rs::{attribute::*, geometry::*};
+use hapi_rs::{
+    attribute::*,
+    enums::{AttributeOwner, PartType},
+    geometry::*,
+};
 mod utils;
 
 use tempfile::NamedTempFile;
@@ -8,6 +12,10 @@ use utils::{HdaFile, create_triangle, with_session, with_test_geometry};
 use crate::utils::with_session_asset;
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_save_and_load_to_file() {
     with_session(|session| {
         let geo = create_triangle(&session)?;
@@ -27,6 +35,10 @@ fn geometry_save_and_load_to_file() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_save_and_load_to_memory() {
     with_session(|session| {
         let src_geo = create_triangle(&session)?;
@@ -60,6 +72,10 @@ fn geometry_commit_and_revert() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_elements() {
     with_test_geometry(|geo| {
         let part = geo.part_info(0).unwrap();
@@ -106,6 +122,10 @@ fn geometry_elements() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_partitions_report_counts() {
     with_test_geometry(|geo| {
         let info = geo.geo_info()?;
@@ -173,6 +193,10 @@ fn geometry_partitions() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_add_and_delete_group() {
     with_session(|session| {
         let mut geo = create_triangle(&session)?;
@@ -195,6 +219,10 @@ fn geometry_add_and_delete_group() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_geo_info_updates_after_group_edits() {
     with_session(|session| {
         let mut geo = create_triangle(&session)?;
@@ -231,6 +259,10 @@ fn geometry_geo_info_updates_after_group_edits() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_basic_instancing() {
     with_session(|session| {
         session.load_asset_file(HdaFile::Geometry.path())?;
@@ -266,6 +298,10 @@ fn geometry_basic_instancing() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_get_face_materials() {
     with_session(|session| {
         session.load_asset_file(HdaFile::Spaceship.path())?;
@@ -281,6 +317,10 @@ fn geometry_get_face_materials() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_create_input_curve() {
     with_session(|session| {
         let geo = session.create_input_curve_node("InputCurve", None).unwrap();
@@ -298,6 +338,10 @@ fn geometry_create_input_curve() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_multiple_input_curves() {
     with_session(|session| {
         let geo = session.create_input_node("InputCurves", None).unwrap();
@@ -343,6 +387,10 @@ fn geometry_multiple_input_curves() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn geometry_read_write_volume() {
     with_session_asset(HdaFile::Volume, |lib| {
         let node = lib.try_create_first().expect("create_node");
@@ -0,0 +1,58 @@
+use hapi_rs::{Result, graph::GraphFormat, network::NetworkBuilder};
+
+mod utils;
+use utils::with_fake_session;
+
+#[test]
+fn graph_export() -> Result<()> {
+    with_fake_session(|session| {
+        let geo = session.create_node("Object/geo")?;
+        let network = NetworkBuilder::new()
+            .node("base", "box", |n| n)
+            .node("lift", "xform", |n| {
+                n.with_input(0, "base").with_display(true)
+            })
+            .node("broken", "error", |n| {
+                n.with_parm("text", "Boom \"quoted\"")
+            })
+            .create(&geo)?;
+        network.get("broken").unwrap().cook_blocking()?;
+        let other = session.create_node("Object/geo")?;
+        let null = session.node_builder("null").with_parent(&other).create()?;
+        null.connect_input(0, network.get("base").unwrap(), 0)?;
+        let id = |name: &str| format!("n{}", i32::from(network.get(name).unwrap().handle));
+        let (base, lift, broken) = (id("base"), id("lift"), id("broken"));
+        let null_id = format!("n{}", i32::from(null.handle));
+
+        let dot = geo.export_graph(GraphFormat::Dot)?;
+        assert!(dot.starts_with("digraph \"geo1\" {"), "{dot}");
+        assert!(
+            dot.contains(&format!("{base} -> {lift} [label=\"0\"];")),
+            "{dot}"
+        );
+        assert!(dot.contains("base\\nSop/box"), "{dot}");
+        assert!(dot.contains("[display, output]"), "{dot}");
+        assert!(dot.contains("Boom \\\"quoted\\\""), "{dot}");
+        assert!(dot.contains("color=red"), "{dot}");
+        assert!(
+            dot.contains(&format!(
+                "{null_id} [label=\"/obj/geo2/null1\", style=\"rounded,dashed\"];"
+            )),
+            "{dot}"
+        );
+        assert!(dot.contains(&format!("{base} -> {null_id};")), "{dot}");
+
+        let mermaid = geo.export_graph(GraphFormat::Mermaid)?;
+        assert!(mermaid.starts_with("flowchart TB\n"), "{mermaid}");
+        assert!(
+            mermaid.contains(&format!("{base} -->|0| {lift}")),
+            "{mermaid}"
+        );
+        assert!(mermaid.contains("#quot;quoted#quot;"), "{mermaid}");
+        assert!(
+            mermaid.contains(&format!("class {broken} error")),
+            "{mermaid}"
+        );
+        Ok(())
+    })
+}
@@ -0,0 +1,38 @@
+use hapi_rs::{
+    Result,
+    houdini_env::{EnvDiff, HoudiniEnv},
+    server::ServerOptions,
+};
+
+mod utils;
+use utils::with_fake_session;
+
+#[test]
+fn houdini_env_diff_and_apply() -> Result<()> {
+    with_fake_session(|session| {
+        let env = HoudiniEnv::parse_with(
+            "JOB = /jobs/show\nHOUDINI_OTLSCAN_PATH = $JOB/otls;&\nHOUDINI_MAXTHREADS = 4",
+            |_| None,
+        )?;
+        session.set_server_var::<i32>("HOUDINI_MAXTHREADS", &2)?;
+        let diff = env.diff(&session)?;
+        assert_eq!(diff.len(), 3);
+        assert!(diff.contains(&EnvDiff::Changed {
+            name: "HOUDINI_MAXTHREADS".to_string(),
+            expected: "4".to_string(),
+            actual: "2".to_string(),
+        }));
+        env.apply(&session)?;
+        assert!(env.diff(&session)?.is_empty());
+        assert_eq!(session.get_server_var::<i32>("HOUDINI_MAXTHREADS")?, 4);
+        assert_eq!(
+            session.get_server_var::<str>("HOUDINI_OTLSCAN_PATH")?,
+            "/jobs/show/otls;&"
+        );
+
+        let server_options = env.apply_to_server_options(ServerOptions::default());
+        let vars = server_options.env_variables.expect("server variables");
+        assert_eq!(vars.get(std::ffi::OsStr::new("JOB")).unwrap(), "/jobs/show");
+        Ok(())
+    })
+}
@@ -5,6 +5,10 @@ mod utils;
 use utils::{HdaFile, with_session};
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn image_file_formats() {
     with_session(|session| {
         let formats = session.get_supported_image_formats()?;
@@ -16,6 +20,10 @@ fn image_file_formats() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn image_extract_api() {
     with_session(|session| {
         session.load_asset_file(HdaFile::Spaceship.path())?;
@@ -0,0 +1,103 @@
+use hapi_rs::{
+    Result,
+    network::{NetworkBuilder, NetworkDef, ParmValue},
+    node::ManagerType,
+    parameter::{Parameter, ParmBaseTrait},
+    session::{CookResult, SessionOptions},
+};
+
+mod utils;
+use utils::{fake_session, with_fake_session};
+
+#[test]
+fn network_export_and_build() -> Result<()> {
+    with_fake_session(|session| {
+        let rig = session.create_node("Object/geo")?;
+        let cube = session.node_builder("box").with_parent(&rig).create()?;
+        let xform = session.node_builder("xform").with_parent(&rig).create()?;
+        if let Parameter::Float(size) = cube.parameter("size")? {
+            size.set_array([2.0, 2.0, 2.0])?;
+        }
+        xform.parameter("scale")?.set_expression("ch('../s')", 0)?;
+        xform.connect_input(0, &cube, 0)?;
+        xform.set_display_flag(true)?;
+
+        let def = NetworkDef::export(&rig)?;
+        assert_eq!(def.nodes.len(), 2);
+        // Reference nodes are created and deleted outside of the exported network.
+        let obj = session.get_manager_node(ManagerType::Obj)?;
+        assert_eq!(obj.get_children()?, [rig.handle]);
+        assert_eq!(rig.get_children()?, [cube.handle, xform.handle]);
+        let (box_def, xform_def) = (&def.nodes[0], &def.nodes[1]);
+        assert_eq!(
+            (box_def.name.as_str(), box_def.node_type.as_str()),
+            ("box1", "box")
+        );
+        assert_eq!(
+            box_def.parms.iter().collect::<Vec<_>>(),
+            [(&"size".to_string(), &ParmValue::Float(vec![2.0; 3]))]
+        );
+        assert!(!box_def.display && xform_def.display);
+        assert_eq!(xform_def.inputs[0].node, "box1");
+        assert_eq!(xform_def.expressions[0].expression, "ch('../s')");
+        assert!(xform_def.parms.is_empty());
+        #[cfg(feature = "serde")]
+        {
+            let json: NetworkDef =
+                serde_json::from_str(&serde_json::to_string(&def).unwrap()).unwrap();
+            assert_eq!(json, def);
+            let toml: NetworkDef = toml::from_str(&toml::to_string(&def).unwrap()).unwrap();
+            assert_eq!(toml, def);
+        }
+
+        let other = fake_session(SessionOptions::default());
+        let target = other.create_node("Object/geo")?;
+        let nodes = def.build(&target)?;
+        assert_eq!(nodes[1].input_node(0)?.unwrap().handle, nodes[0].handle);
+        let display = target.query().display_only().first()?.unwrap();
+        assert_eq!(display.handle, nodes[1].handle);
+        assert_eq!(
+            nodes[1].parameter("scale")?.expression(0)?.as_deref(),
+            Some("ch('../s')")
+        );
+        assert_eq!(NetworkDef::export(&target)?, def);
+        Ok(())
+    })
+}
+
+#[test]
+fn network_builder() -> Result<()> {
+    with_fake_session(|session| {
+        let geo = session.create_node("Object/geo")?;
+        let network = NetworkBuilder::new()
+            .node("base", "box", |n| n.with_parm("size", [2.0, 1.0, 2.0]))
+            .node("lift", "xform", |n| {
+                n.with_input(0, "base")
+                    .with_parm("t", [0, 1, 0])
+                    .with_display(true)
+            })
+            .create(&geo)?;
+        assert_eq!(network.cook_result, CookResult::Succeeded);
+        let lift = network.get("lift").unwrap();
+        assert_eq!(lift.input_node(0)?.unwrap().name()?, "base");
+        assert_eq!(lift.get_info()?.total_cook_count(), 1);
+        let part = lift.geometry()?.unwrap().part_info(0)?;
+        assert_eq!(part.point_count(), 8);
+
+        let error = NetworkBuilder::new()
+            .node("a", "null", |n| n.with_input(0, "missing"))
+            .node("a", "null", |n| n)
+            .create(&geo)
+            .unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("unknown node missing"), "{message}");
+        assert!(message.contains("duplicate node name a"), "{message}");
+        let error = NetworkBuilder::new()
+            .node("ok", "null", |n| n)
+            .node("bad", "null", |n| n.with_parm("nope", 1))
+            .create(&geo);
+        assert!(error.is_err());
+        assert_eq!(geo.query().to_vec()?.len(), 2);
+        Ok(())
+    })
+}
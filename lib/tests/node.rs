@@ -9,9 +9,13 @@ use hapi_rs::{
 };
 
 mod utils;
-use utils::{HdaFile, with_session};
+use utils::{HdaFile, with_fake_session, with_session};
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn node_create() {
     with_session(|session| {
         session.load_asset_file(HdaFile::Spaceship.path())?;
@@ -43,6 +47,10 @@ fn node_set_node_display_flag() -> Result<()> {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn node_inputs_and_outputs() {
     with_session(|session| {
         session.load_asset_file(HdaFile::Geometry.path())?;
@@ -65,6 +73,10 @@ fn node_inputs_and_outputs() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn node_search() {
     with_session(|session| {
         session.load_asset_file(HdaFile::Geometry.path())?;
@@ -86,6 +98,10 @@ fn node_search() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn node_transform() {
     with_session(|session| {
         let obj = session.create_node("Object/null")?;
@@ -109,6 +125,10 @@ fn node_transform() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn node_save_and_load() {
     with_session(|session| {
         let cam = session.create_node("Object/cam").unwrap();
@@ -126,6 +146,10 @@ fn node_save_and_load() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn node_number_of_geo_outputs() {
     with_session(|session| {
         session.load_asset_file(HdaFile::Geometry.path())?;
@@ -139,6 +163,10 @@ fn node_number_of_geo_outputs() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn node_get_message_nodes() {
     with_session(|session| {
         session.load_asset_file(HdaFile::Geometry.path())?;
@@ -157,6 +185,10 @@ fn node_get_message_nodes() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn node_output_names() {
     with_session(|session| {
         session.load_asset_file(HdaFile::Parameters.path())?;
@@ -169,6 +201,10 @@ fn node_output_names() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn node_get_parm_with_tag() {
     with_session(|session| {
         session.load_asset_file(HdaFile::Parameters.path())?;
@@ -180,6 +216,10 @@ fn node_get_parm_with_tag() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn node_set_animate_transform() {
     with_session(|session| {
         let bone = session.create_node("Object/bone")?;
@@ -208,6 +248,10 @@ fn node_set_animate_transform() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn node_get_set_preset() {
     with_session(|session| {
         let node = session.create_node("Object/null")?;
@@ -222,3 +266,45 @@ fn node_get_set_preset() {
     })
     .unwrap()
 }
+
+#[test]
+fn node_create_and_delete() -> Result<()> {
+    with_fake_session(|session| {
+        let obj = session.create_node("Object/geo")?;
+        assert_eq!(obj.path()?, "/obj/geo1");
+        let other = session.create_node("Object/geo")?;
+        assert_eq!(other.path()?, "/obj/geo2");
+        let sphere = session.node_builder("sphere").with_parent(&obj).create()?;
+        assert_eq!(sphere.path()?, "/obj/geo1/sphere1");
+        let found = session
+            .get_node_from_path("/obj/geo1/sphere1", None)?
+            .expect("sphere node");
+        assert_eq!(found.handle, sphere.handle);
+        assert!(session.get_node_from_path("/obj/nope", None)?.is_none());
+        assert!(session.create_node("Object/nope").is_err());
+        obj.delete()?;
+        assert!(!sphere.is_valid().unwrap_or(false));
+        assert!(session.get_node_from_path("/obj/geo1", None)?.is_none());
+        Ok(())
+    })
+}
+
+#[test]
+fn node_cook_box_and_display_flag() -> Result<()> {
+    with_fake_session(|session| {
+        let obj = session.create_node("Object/geo")?;
+        let sphere = session.node_builder("sphere").with_parent(&obj).create()?;
+        let cube = session.node_builder("box").with_parent(&obj).create()?;
+        sphere.cook_blocking()?;
+        cube.cook_blocking()?;
+        let geometry = cube.geometry()?.expect("box geometry");
+        let part = geometry.part_info(0)?;
+        assert_eq!(part.point_count(), 8);
+        assert_eq!(part.face_count(), 6);
+        assert!(sphere.geometry()?.unwrap().geo_info()?.is_display_geo());
+        cube.set_display_flag(true)?;
+        assert!(!sphere.geometry()?.unwrap().geo_info()?.is_display_geo());
+        assert!(geometry.geo_info()?.is_display_geo());
+        Ok(())
+    })
+}
@@ -4,9 +4,13 @@ use hapi_rs::{
 };
 
 mod utils;
-use utils::{HdaFile, with_session};
+use utils::{HdaFile, with_fake_session, with_session};
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn parameters_get_set() {
     with_session(|session| {
         session.load_asset_file(HdaFile::Parameters.path())?;
@@ -116,6 +120,10 @@ fn parameters_set_anim_expression() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn parameters_reset_to_default() {
     with_session(|session| {
         let node = session
@@ -135,6 +143,10 @@ fn parameters_reset_to_default() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn parameter_tags() {
     with_session(|session| {
         let node = session
@@ -154,6 +166,10 @@ fn parameter_tags() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn parameters_save_parm_file() {
     with_session(|session| {
         let node = session
@@ -176,6 +192,10 @@ fn parameters_save_parm_file() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn get_set_value_as_node() {
     with_session(|session| {
         let node = session
@@ -200,6 +220,10 @@ fn get_set_value_as_node() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn parameters_concurrent_access() {
     // This is a dumb test of accessing parameters randomly from multiple threads
     // HAPI claims each session is protected with a lock....
@@ -282,3 +306,25 @@ fn parameters_concurrent_access() {
     })
     .unwrap()
 }
+
+#[test]
+fn parameters_fake_xform() -> Result<()> {
+    with_fake_session(|session| {
+        let node = session.create_node("Sop/xform")?;
+        let Parameter::Float(t) = node.parameter("t")? else {
+            panic!("t must be a float parameter");
+        };
+        t.set_array([1.0, 2.0, 3.0])?;
+        assert_eq!(t.get(1)?, 2.0);
+        t.set(2, 5.0)?;
+        assert_eq!(t.get_array()?, vec![1.0, 2.0, 5.0]);
+        let Parameter::String(group) = node.parameter("group")? else {
+            panic!("group must be a string parameter");
+        };
+        group.set(0, "@P.y>0")?;
+        assert_eq!(group.get(0)?, "@P.y>0");
+        assert!(node.parameter("missing").is_err());
+        assert_eq!(node.parameters()?.len(), 5);
+        Ok(())
+    })
+}
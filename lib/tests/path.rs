@@ -0,0 +1,108 @@
+use hapi_rs::{Result, path::NodePath};
+
+mod utils;
+use utils::with_fake_session;
+
+#[test]
+fn path_parse_and_join() -> Result<()> {
+    let path = NodePath::parse("/obj/geo1/../geo2/./box1/")?;
+    assert_eq!(path.to_string(), "/obj/geo2/box1");
+    assert_eq!(NodePath::parse("/..")?, NodePath::root());
+    assert_eq!(NodePath::parse("../a/../../b")?.to_string(), "../../b");
+    assert!(NodePath::parse("").is_err());
+    assert!(NodePath::parse("/obj/geo 1").is_err());
+    assert_eq!(path.name(), Some("box1"));
+    assert_eq!(path.parent().unwrap().to_string(), "/obj/geo2");
+    assert_eq!(NodePath::root().parent(), None);
+    assert_eq!(
+        NodePath::parse("..")?.parent().unwrap().to_string(),
+        "../.."
+    );
+    let geo1 = NodePath::parse("/obj/geo1")?;
+    assert_eq!(
+        geo1.join("box1/../sphere1")?.to_string(),
+        "/obj/geo1/sphere1"
+    );
+    assert_eq!(geo1.join("/out")?.to_string(), "/out");
+    assert_eq!(path.relative_to(&geo1).unwrap().to_string(), "../geo2/box1");
+    assert_eq!(geo1.relative_to(&geo1).unwrap().to_string(), ".");
+    let pattern = NodePath::parse("/obj/*/OUT_?")?;
+    assert!(pattern.is_glob());
+    assert!(pattern.matches(&NodePath::parse("/obj/geo1/OUT_a")?));
+    assert!(!pattern.matches(&NodePath::parse("/obj/geo1/sub/OUT_a")?));
+
+    Ok(())
+}
+
+#[test]
+fn path_resolve_and_glob() -> Result<()> {
+    with_fake_session(|session| {
+        let geo1 = session.create_node("Object/geo")?;
+        let geo2 = session.create_node("Object/geo")?;
+        let out1 = session
+            .node_builder("null")
+            .with_parent(&geo1)
+            .with_label("OUT_a")
+            .create()?;
+        let out2 = session
+            .node_builder("null")
+            .with_parent(&geo2)
+            .with_label("OUT_b")
+            .create()?;
+        session.node_builder("box").with_parent(&geo2).create()?;
+        let found = session
+            .get_node_from_path(NodePath::parse("/obj/geo2/../geo1/OUT_a")?, None)?
+            .unwrap();
+        assert_eq!(found.handle, out1.handle);
+        assert_eq!(
+            geo2.get_child_by_path(NodePath::parse("OUT_b")?)?
+                .unwrap()
+                .handle,
+            out2.handle
+        );
+        let size = session
+            .find_parameter_from_path(NodePath::parse("/obj/geo2/box1/size")?, None)?
+            .unwrap();
+        assert_eq!(size.name()?, "size");
+
+        let outputs: Vec<_> = session
+            .glob_nodes("/obj/*/OUT_*")?
+            .iter()
+            .map(|node| node.handle)
+            .collect();
+        assert_eq!(outputs, vec![out1.handle, out2.handle]);
+        assert_eq!(session.glob_nodes("/*/geo?")?.len(), 2);
+        // Root networks like /stage are skipped if the server doesn't have them.
+        let roots: Vec<_> = session
+            .glob_nodes("/*")?
+            .iter()
+            .map(|node| node.path())
+            .collect::<Result<_>>()?;
+        assert!(roots.contains(&"/obj".to_string()), "{roots:?}");
+        assert_eq!(
+            out1.node_path_relative(geo1.handle)?,
+            NodePath::parse("OUT_a")?
+        );
+        assert_eq!(
+            out1.node_path_relative(None)?,
+            NodePath::parse("/obj/geo1/OUT_a")?
+        );
+        assert!(session.glob_nodes("OUT_*").is_err());
+        let resolved = session.resolve_node_paths(&[
+            NodePath::parse("/obj/geo1/OUT_a")?,
+            NodePath::parse("/obj/geo2")?,
+            NodePath::parse("/obj/geo2/nope")?,
+        ])?;
+        let handles: Vec<_> = resolved
+            .iter()
+            .map(|n| n.as_ref().map(|n| n.handle))
+            .collect();
+        assert_eq!(handles, vec![Some(out1.handle), Some(geo2.handle), None]);
+        assert!(
+            session
+                .resolve_node_paths(&[NodePath::parse("/obj/*")?])
+                .is_err()
+        );
+        Ok(())
+    })
+}
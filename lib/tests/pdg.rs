@@ -2,6 +2,10 @@ mod utils;
 use utils::with_session;
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn pdg_create_workitems() {
     with_session(|session| {
         let topnet = session.create_node("Object/topnet")?;
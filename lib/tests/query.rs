@@ -0,0 +1,51 @@
+use hapi_rs::{
+    Result,
+    node::{ManagerType, NodeFlags, NodeType},
+    query::NodeQuery,
+};
+
+mod utils;
+use utils::with_fake_session;
+
+#[test]
+fn query_filters() -> Result<()> {
+    with_fake_session(|session| {
+        let geo1 = session.create_node("Object/geo")?;
+        let sphere = session.node_builder("sphere").with_parent(&geo1).create()?;
+        session.node_builder("box").with_parent(&geo1).create()?;
+        session.node_builder("box").with_parent(&geo1).create()?;
+        let geo2 = session.create_node("Object/geo")?;
+        session.node_builder("box").with_parent(&geo2).create()?;
+        let paths = |query: NodeQuery| -> Result<Vec<String>> {
+            query.iter()?.map(|node| node?.path()).collect()
+        };
+
+        let obj = session.get_manager_node(ManagerType::Obj)?;
+        assert_eq!(paths(obj.query())?, ["/obj/geo1", "/obj/geo2"]);
+        let sops = obj.query().recursive(true).node_types(NodeType::Sop);
+        assert_eq!(
+            paths(sops.clone().name("box*"))?,
+            ["/obj/geo1/box1", "/obj/geo1/box2", "/obj/geo2/box1"]
+        );
+        assert_eq!(
+            paths(sops.clone().name("?ox1").batch_size(1))?,
+            ["/obj/geo1/box1", "/obj/geo2/box1"]
+        );
+        assert_eq!(
+            paths(sops.clone().display_only())?,
+            ["/obj/geo1/sphere1", "/obj/geo2/box1"]
+        );
+        assert_eq!(
+            paths(sops.clone().outputs_only())?,
+            ["/obj/geo1/sphere1", "/obj/geo2/box1"]
+        );
+        assert_eq!(paths(sops.path("/obj/geo2/*"))?, ["/obj/geo2/box1"]);
+        assert!(obj.query().name("cam*").first()?.is_none());
+
+        let children = geo1.query().batch_size(2).to_vec()?;
+        assert_eq!(children.len(), 3);
+        assert_eq!(children[0].handle, sphere.handle);
+        assert_eq!(geo1.query().flags(NodeFlags::Display).to_vec()?.len(), 1);
+        Ok(())
+    })
+}
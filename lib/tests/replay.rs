@@ -0,0 +1,157 @@
+use hapi_rs::{
+    HapiError, Result,
+    attribute::*,
+    enums::{AttributeOwner, JobStatus, PartType},
+    geometry::PartInfo,
+    parameter::Parameter,
+    replay,
+    session::SessionOptions,
+};
+
+mod utils;
+use utils::{fake_session, with_fake_engine};
+
+#[test]
+fn replay_record_and_replay() -> Result<()> {
+    with_fake_engine(|_| {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("session.hapirec");
+        let session = fake_session(SessionOptions::default().record_calls(&path));
+        let geo = session.create_input_node("recorded", None)?;
+        let part = PartInfo::default()
+            .with_part_type(PartType::Mesh)
+            .with_point_count(3);
+        geo.set_part_info(&part)?;
+        let info = AttributeInfo::default()
+            .with_count(3)
+            .with_tuple_size(3)
+            .with_owner(AttributeOwner::Point)
+            .with_storage(StorageType::Float);
+        let attr_p = geo.add_numeric_attribute::<f32>("P", 0, info)?;
+        attr_p.set(0, &[1.0; 9])?;
+        geo.commit()?;
+        let xform = session.create_node("Sop/xform")?;
+        let Parameter::Float(t) = xform.parameter("t")? else {
+            panic!("t must be a float parameter");
+        };
+        t.set_array([1.0, 2.0, 3.0])?;
+        let xform_path = xform.path()?;
+        drop(session);
+
+        let calls = replay::read_calls(&path)?;
+        let set_data = calls
+            .iter()
+            .find(|call| call.function == "HAPI_SetAttributeFloatData")
+            .expect("recorded attribute data");
+        assert!(set_data.args.contains(&replay::Value::Bytes(
+            [1.0f32; 9].iter().flat_map(|v| v.to_le_bytes()).collect()
+        )));
+
+        // Shift node ids in the new session, the recorded ones have to be remapped.
+        let target = fake_session(SessionOptions::default());
+        target.create_node("Object/geo")?;
+        // SAFETY: the recording was written by the session above.
+        let report = unsafe { replay::replay(&path, &target)? };
+        assert_eq!(report.calls, calls.len());
+        assert!(report.replayed > 0 && report.replayed < report.calls);
+        assert!(report.mismatches.is_empty(), "{:?}", report.mismatches);
+        let xform = target
+            .get_node_from_path(&xform_path, None)?
+            .expect("replayed xform");
+        let Parameter::Float(t) = xform.parameter("t")? else {
+            panic!("t must be a float parameter");
+        };
+        assert_eq!(t.get_array()?, vec![1.0, 2.0, 3.0]);
+        Ok(())
+    })
+}
+
+#[test]
+fn replay_rejects_short_buffer() -> Result<()> {
+    with_fake_engine(|_| {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("session.hapirec");
+        let session = fake_session(SessionOptions::default().record_calls(&path));
+        let geo = session.create_input_node("recorded", None)?;
+        geo.set_part_info(
+            &PartInfo::default()
+                .with_part_type(PartType::Mesh)
+                .with_point_count(3),
+        )?;
+        let info = AttributeInfo::default()
+            .with_count(3)
+            .with_tuple_size(3)
+            .with_owner(AttributeOwner::Point)
+            .with_storage(StorageType::Float);
+        geo.add_numeric_attribute::<f32>("P", 0, info)?
+            .set(0, &[1.0; 9])?;
+        drop(session);
+
+        // Drop the last float of the attribute data, tag 5 is followed by the byte count.
+        let data: Vec<u8> = [1.0f32; 9].iter().flat_map(|v| v.to_le_bytes()).collect();
+        let mut recorded = [vec![5, 36], data.clone()].concat();
+        let mut bytes = std::fs::read(&path)?;
+        let at = bytes
+            .windows(recorded.len())
+            .position(|window| window == recorded)
+            .expect("recorded attribute data");
+        recorded = [vec![5, 32], data[..32].to_vec()].concat();
+        bytes.splice(at..at + 38, recorded);
+        std::fs::write(&path, bytes)?;
+
+        let target = fake_session(SessionOptions::default());
+        // SAFETY: the buffer size is checked before the call.
+        let result = unsafe { replay::replay(&path, &target) };
+        let Err(HapiError::Internal(message)) = result else {
+            panic!("short buffer must be rejected: {result:?}");
+        };
+        assert!(message.contains("data_array"), "{message}");
+        Ok(())
+    })
+}
+
+#[test]
+fn replay_async_attribute_set() -> Result<()> {
+    with_fake_engine(|_| {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("session.hapirec");
+        let session = fake_session(SessionOptions::default().record_calls(&path));
+        let geo = session.create_input_node("recorded", None)?;
+        geo.set_part_info(
+            &PartInfo::default()
+                .with_part_type(PartType::Mesh)
+                .with_point_count(3),
+        )?;
+        let info = AttributeInfo::default()
+            .with_count(3)
+            .with_tuple_size(3)
+            .with_owner(AttributeOwner::Point)
+            .with_storage(StorageType::Float);
+        let attr_p = geo.add_numeric_attribute::<f32>("P", 0, info)?;
+        let positions: Vec<f32> = (0..9).map(|i| i as f32).collect();
+        let job = attr_p.set_async(0, &positions)?;
+        while session.get_job_status(job)? == JobStatus::Running {}
+        geo.commit()?;
+        let geo_path = geo.node.path()?;
+        drop(session);
+
+        let target = fake_session(SessionOptions::default());
+        // SAFETY: the recording was written by the session above.
+        let report = unsafe { replay::replay(&path, &target)? };
+        assert!(report.mismatches.is_empty(), "{:?}", report.mismatches);
+        // The replayed job read the attribute data before its buffer was released.
+        let geo = target
+            .get_node_from_path(&geo_path, None)?
+            .expect("replayed input node")
+            .geometry()?
+            .expect("geometry");
+        let attr_p = geo
+            .get_attribute(0, AttributeOwner::Point, c"P")?
+            .expect("P attribute");
+        let attr_p = attr_p
+            .downcast::<NumericAttr<f32>>()
+            .expect("NumericAttr<f32>");
+        assert_eq!(attr_p.get(0)?, positions);
+        Ok(())
+    })
+}
@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use hapi_rs::{
+    HapiError, Result,
+    server::{LicensePreference, LicenseTier, ServerOptions},
+    session::{HapiResult, LicenseType, SessionOptions, new_thrift_session},
+};
+
+mod utils;
+use utils::with_fake_engine;
+
+#[test]
+fn server_license_fallback() -> Result<()> {
+    with_fake_engine(|engine| {
+        let server_options = |preferences: &[LicensePreference]| {
+            ServerOptions::shared_memory_with_defaults()
+                .with_connection_timeout(Some(Duration::from_millis(100)))
+                .with_license_preferences(preferences.iter().copied())
+        };
+        engine.set_licenses([LicenseType::HoudiniEducation, LicenseType::HoudiniFx]);
+        let error = new_thrift_session(
+            SessionOptions::default(),
+            server_options(&[LicensePreference::HoudiniEngineOnly]),
+        )
+        .unwrap_err();
+        assert!(error.is_license_error());
+        assert_eq!(error.result_code(), Some(HapiResult::NoLicenseFound));
+
+        let preferences = [
+            LicensePreference::HoudiniEngineOnly,
+            LicensePreference::HoudiniEngineAndCore,
+        ];
+        let session = new_thrift_session(SessionOptions::default(), server_options(&preferences))?;
+        let acquired = session.acquired_license()?;
+        assert_eq!(acquired.license, LicenseType::HoudiniEducation);
+        assert_eq!(acquired.tier, Some(LicenseTier::NonCommercial));
+        assert_eq!(
+            acquired.preference,
+            Some(LicensePreference::HoudiniEngineAndCore)
+        );
+
+        let error = new_thrift_session(
+            SessionOptions::default(),
+            server_options(&preferences).with_minimum_license(LicenseTier::Commercial),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            HapiError::LicenseRejected {
+                license: LicenseType::HoudiniEducation,
+                minimum: LicenseTier::Commercial
+            }
+        ));
+
+        engine.set_licenses([LicenseType::HoudiniFx]);
+        let session = new_thrift_session(
+            SessionOptions::default(),
+            server_options(&[
+                LicensePreference::HoudiniEngineOnly,
+                LicensePreference::AnyAvailable,
+            ])
+            .with_minimum_license(LicenseTier::Commercial),
+        )?;
+        assert_eq!(session.acquired_license()?.license, LicenseType::HoudiniFx);
+        Ok(())
+    })
+}
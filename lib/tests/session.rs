@@ -1,21 +1,21 @@
+use std::time::Duration;
+
 use hapi_rs::raw::CacheProperty;
 use hapi_rs::server::ServerOptions;
 use hapi_rs::session::{
-    CookResult, License, ManagerType, SessionOptions, SessionSyncInfo, TimelineOptions, Viewport,
-    new_thrift_session,
+    CookResult, License, ManagerType, SessionOptions, SessionPool, SessionSyncInfo, SessionType,
+    TimelineOptions, Viewport, bind_custom_implementation, new_custom_session, new_thrift_session,
 };
+use hapi_rs::{HapiError, Result};
 
 mod utils;
-use utils::with_session;
+use utils::{fake_session_pool, start_session, with_fake_engine, with_fake_session, with_session};
 
 #[test]
 fn session_get_set_time() {
     // For some reason, this test randomly fails when using shared session
-    let session = new_thrift_session(
-        SessionOptions::default(),
-        ServerOptions::shared_memory_with_defaults(),
-    )
-    .expect("Could not start session");
+    let session = start_session(ServerOptions::shared_memory_with_defaults())
+        .expect("Could not start session");
     // let _lock = session.lock();
     let opt = TimelineOptions::default().with_end_time(5.5);
     assert!(session.set_timeline_options(opt.clone()).is_ok());
@@ -27,9 +27,13 @@ fn session_get_set_time() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn session_server_variables() {
-    let session = new_thrift_session(
-        SessionOptions::default(),
+    // The fake engine doesn't pass the server environment to the session.
+    let session = start_session(
         ServerOptions::shared_memory_with_defaults()
             .with_env_variables([("HAPI_RS_TEST", "hapi_rs_is_awesome")].iter()),
     )
@@ -48,6 +52,10 @@ fn session_server_variables() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn session_set_viewport() {
     with_session(|session| {
         let vp = Viewport::default()
@@ -65,6 +73,10 @@ fn session_set_viewport() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn session_sync() {
     with_session(|session| {
         assert!(session.is_valid());
@@ -117,7 +129,12 @@ fn cache_properties() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn test_license_set_via_environment() {
+    // The fake engine doesn't pass the server environment to the session.
     let env = [(
         "HOUDINI_PLUGIN_LIC_OPT",
         "--check-licenses=Houdini-Escape --skip-licenses=Houdini-Engine",
@@ -125,8 +142,7 @@ fn test_license_set_via_environment() {
 
     let server_options =
         ServerOptions::shared_memory_with_defaults().with_env_variables(env.iter());
-    let session = new_thrift_session(SessionOptions::default(), server_options)
-        .expect("Could not start session");
+    let session = start_session(server_options).expect("Could not start session");
     let plugin_lic_opt = session.get_server_var::<str>(env[0].0).unwrap();
     session.create_node("Object/null").unwrap();
     let license_type = session.get_license_type().unwrap();
@@ -135,6 +151,10 @@ fn test_license_set_via_environment() {
 }
 
 #[test]
+#[cfg_attr(
+    not(any(feature = "link", feature = "dynamic-load")),
+    ignore = "needs Houdini"
+)]
 fn test_get_preset_names() {
     let bytes = std::fs::read("tests/data/bone.idx").expect("read file");
     with_session(|session| {
@@ -144,3 +164,145 @@ fn test_get_preset_names() {
     })
     .unwrap()
 }
+
+#[test]
+fn session_lifecycle() -> Result<()> {
+    with_fake_session(|session| {
+        assert!(session.is_valid());
+        session.set_time(2.5).unwrap();
+        assert_eq!(session.get_time().unwrap(), 2.5);
+        session.set_server_var::<str>("FOO", "bar").unwrap();
+        assert_eq!(session.get_server_var::<str>("FOO").unwrap(), "bar");
+        Ok(())
+    })
+}
+
+#[test]
+fn session_custom() -> Result<()> {
+    with_fake_engine(|_| {
+        // SAFETY: the fake engine doesn't load the library or read the session info.
+        unsafe {
+            assert!(new_custom_session(SessionType::Custom2, std::ptr::null_mut(), None).is_err());
+            assert!(bind_custom_implementation(SessionType::Thrift, "libproxy.so").is_err());
+            bind_custom_implementation(SessionType::Custom2, "libproxy.so")?;
+        }
+        let mut info = 42i32;
+        let info = (&mut info as *mut i32).cast();
+        // SAFETY: see above.
+        let session = unsafe { new_custom_session(SessionType::Custom2, info, None)? };
+        assert!(session.is_valid());
+        assert_eq!(session.session_type(), SessionType::Custom2);
+        assert_eq!(session.create_node("Object/geo")?.path()?, "/obj/geo1");
+        Ok(())
+    })
+}
+
+#[test]
+fn session_pool_checkout() -> Result<()> {
+    with_fake_engine(|_| {
+        let pool = fake_session_pool(2);
+        assert_eq!(pool.idle_count(), 2);
+        let first = pool.checkout()?;
+        let second = pool.checkout()?;
+        assert_ne!(first.server_pid(), second.server_pid());
+        assert_ne!(first.index(), second.index());
+        assert!(pool.try_checkout()?.is_none());
+        first.create_node("Sop/box")?;
+        drop(first);
+        assert_eq!(pool.idle_count(), 1);
+        let handle = std::thread::spawn({
+            let pool = pool.clone();
+            move || pool.checkout().map(|session| session.index())
+        });
+        drop(second);
+        handle.join().unwrap()?;
+        assert_eq!(pool.idle_count(), 2);
+        Ok(())
+    })
+}
+
+#[test]
+fn session_pool_rejects_empty_pool() -> Result<()> {
+    with_fake_engine(|_| {
+        let pool = SessionPool::new(
+            0,
+            SessionOptions::default(),
+            ServerOptions::shared_memory_with_defaults(),
+        );
+        assert!(matches!(pool, Err(HapiError::Internal(_))));
+        Ok(())
+    })
+}
+
+#[test]
+fn session_pool_restarts_dead_server() -> Result<()> {
+    with_fake_engine(|engine| {
+        let pool = fake_session_pool(1);
+        let pid = pool.checkout()?.server_pid().expect("server pid");
+        engine.kill_server(pid);
+        let session = pool.checkout()?;
+        assert!(session.is_valid());
+        assert_ne!(session.server_pid(), Some(pid));
+        session.create_node("Sop/box")?;
+        Ok(())
+    })
+}
+
+#[test]
+fn session_pool_kills_unresponsive_server() -> Result<()> {
+    with_fake_engine(|engine| {
+        let pool = fake_session_pool(1);
+        let pid = pool.checkout()?.server_pid().expect("server pid");
+        engine.hang_server(pid);
+        assert!(engine.is_server_running(pid));
+        let session = pool.checkout()?;
+        assert!(session.is_valid());
+        assert_ne!(session.server_pid(), Some(pid));
+        assert!(!engine.is_server_running(pid));
+        Ok(())
+    })
+}
+
+#[test]
+fn session_recovery() -> Result<()> {
+    with_fake_engine(|engine| {
+        let server_options = ServerOptions::shared_memory_with_defaults()
+            .with_connection_timeout(Some(Duration::from_millis(100)));
+        let session =
+            new_thrift_session(SessionOptions::default().auto_recover(true), server_options)?;
+        session.set_server_var::<str>("FOO", "bar")?;
+        session.set_server_var::<i32>("BAR", &1)?;
+        session.set_server_var::<i32>("BAR", &2)?;
+        session.set_time(3.0)?;
+        let node = session.create_node("Sop/box")?;
+        let pid = session.server_pid().expect("server pid");
+        engine.kill_server(pid);
+
+        let error = node.cook_blocking().unwrap_err();
+        assert!(error.is_session_recovered(), "{error}");
+        assert!(session.is_valid());
+        assert_ne!(session.server_pid(), Some(pid));
+        assert!(!session.recover()?);
+        assert_eq!(session.get_server_var::<str>("FOO")?, "bar");
+        assert_eq!(session.get_server_var::<i32>("BAR")?, 2);
+        assert_eq!(session.get_time()?, 3.0);
+        assert!(!node.is_valid().unwrap_or(false));
+        session.create_node("Sop/box")?.cook_blocking()?;
+        Ok(())
+    })
+}
+
+#[test]
+fn session_without_recovery() -> Result<()> {
+    with_fake_engine(|engine| {
+        let server_options = ServerOptions::shared_memory_with_defaults();
+        let session = new_thrift_session(SessionOptions::default(), server_options)?;
+        let node = session.create_node("Sop/box")?;
+        engine.kill_server(session.server_pid().expect("server pid"));
+        let error = node.cook_blocking().unwrap_err();
+        assert!(!error.is_session_recovered());
+        assert!(!session.is_valid());
+        assert!(session.recover().is_err());
+        Ok(())
+    })
+}
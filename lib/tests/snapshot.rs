@@ -0,0 +1,43 @@
+use hapi_rs::{Result, session::SessionOptions};
+
+mod utils;
+use utils::{fake_session, with_fake_session};
+
+#[test]
+fn snapshot_and_restore() -> Result<()> {
+    with_fake_session(|session| {
+        session.load_asset_file("otls/fake.hda")?;
+        session.set_server_var::<str>("JOB", "/jobs/fake")?;
+        session.set_time(4.0)?;
+        let mut compositor = session.get_compositor_options()?;
+        compositor.set_max_resolution_x(1024);
+        session.set_compositor_options(&compositor)?;
+        let obj = session.create_node("Object/geo")?;
+        session.node_builder("box").with_parent(&obj).create()?;
+        let snapshot = session.snapshot()?;
+        assert!(!snapshot.hip.is_empty());
+        // Only the variables set on the session, not the ones of the server process.
+        assert_eq!(
+            snapshot.server_vars,
+            [("JOB".to_string(), "/jobs/fake".to_string())]
+        );
+
+        let worker = fake_session(SessionOptions::default());
+        worker.set_server_var::<str>("HIP", "/worker")?;
+        worker.restore(&snapshot)?;
+        assert_eq!(worker.get_server_var::<str>("HIP")?, "/worker");
+        assert!(worker.get_node_from_path("/obj/geo1/box1", None)?.is_some());
+        assert_eq!(worker.get_server_var::<str>("JOB")?, "/jobs/fake");
+        assert_eq!(worker.get_time()?, 4.0);
+        assert_eq!(worker.get_compositor_options()?.max_resolution_x(), 1024);
+        let libraries: Vec<_> = worker
+            .get_loaded_asset_libraries()?
+            .into_iter()
+            .filter_map(|library| library.file)
+            .collect();
+        assert_eq!(libraries, [std::path::PathBuf::from("otls/fake.hda")]);
+        // New nodes don't clash with the restored ones.
+        assert_eq!(worker.create_node("Object/geo")?.path()?, "/obj/geo2");
+        Ok(())
+    })
+}
@@ -0,0 +1,50 @@
+use hapi_rs::{
+    Result,
+    attribute::*,
+    enums::{AttributeOwner, PartType},
+    geometry::PartInfo,
+    session::SessionOptions,
+};
+
+mod utils;
+use utils::{fake_session, with_fake_engine};
+
+#[test]
+fn stats_collect() -> Result<()> {
+    with_fake_engine(|_| {
+        let session = fake_session(SessionOptions::default().collect_stats(true));
+        assert_eq!(
+            fake_session(SessionOptions::default())
+                .stats()
+                .total_calls(),
+            0
+        );
+        let geo = session.create_input_node("stats", None)?;
+        let part = PartInfo::default()
+            .with_part_type(PartType::Mesh)
+            .with_point_count(3);
+        geo.set_part_info(&part)?;
+        let info = AttributeInfo::default()
+            .with_count(3)
+            .with_tuple_size(3)
+            .with_owner(AttributeOwner::Point)
+            .with_storage(StorageType::Float);
+        let attr_p = geo.add_numeric_attribute::<f32>("P", 0, info)?;
+        attr_p.set(0, &[0.0; 9])?;
+        assert!(session.create_node("Sop/nope").is_err());
+
+        let stats = session.stats();
+        let set_data = &stats.functions["HAPI_SetAttributeFloatData"];
+        assert_eq!(set_data.calls, 1);
+        assert_eq!(set_data.bytes, 9 * 4);
+        assert_eq!(set_data.latency.buckets().iter().sum::<u64>(), 1);
+        let create = &stats.functions["HAPI_CreateNode"];
+        assert_eq!((create.calls, create.failures), (1, 1));
+        assert!(stats.functions.contains_key("HAPI_CreateInputNode"));
+        assert!(stats.total_calls() >= stats.functions.len() as u64);
+        assert!(stats.latency().quantile(0.5).is_some());
+        session.reset_stats();
+        assert_eq!(session.stats().total_calls(), 0);
+        Ok(())
+    })
+}
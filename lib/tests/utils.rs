@@ -1,4 +1,8 @@
 #![allow(dead_code)]
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use hapi_rs::backend::{fake::FakeEngine, set_backend};
 use hapi_rs::server::{LicensePreference, ServerOptions};
 use hapi_rs::{
     Result,
@@ -6,20 +10,67 @@ use hapi_rs::{
     attribute::*,
     enums::{AttributeOwner, PartType},
     geometry::{Geometry, PartInfo},
-    session::{CookResult, Session, SessionInfo, SessionOptions, new_thrift_session},
+    session::{
+        CookBackoff, CookResult, Session, SessionInfo, SessionOptions, SessionPool,
+        new_in_process_session, new_thrift_session,
+    },
 };
 use once_cell::sync::Lazy;
 
+/// Engine the tests run against, selected with `HAPI_RS_TEST_BACKEND=houdini|fake`.
+/// Without the variable the fake engine is used if the crate is built without `link` and
+/// `dynamic-load`, since there is no way to load Houdini then. Tests which need the HDA files
+/// or engine features the fake engine doesn't implement are ignored in that build with
+/// `#[cfg_attr(not(any(feature = "link", feature = "dynamic-load")), ignore = "needs Houdini")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Houdini,
+    Fake,
+}
+
+pub fn backend() -> Backend {
+    static BACKEND: OnceLock<Backend> = OnceLock::new();
+    *BACKEND.get_or_init(|| match std::env::var("HAPI_RS_TEST_BACKEND").as_deref() {
+        Ok("fake") => Backend::Fake,
+        Ok("houdini") => Backend::Houdini,
+        Ok(other) => panic!("Unknown HAPI_RS_TEST_BACKEND: {other}, expected houdini or fake"),
+        Err(_) if cfg!(any(feature = "link", feature = "dynamic-load")) => Backend::Houdini,
+        Err(_) => Backend::Fake,
+    })
+}
+
+/// The fake engine, installed on first use. `None` when the tests run against Houdini.
+pub fn fake_engine() -> Option<&'static FakeEngine> {
+    static ENGINE: OnceLock<FakeEngine> = OnceLock::new();
+    if backend() != Backend::Fake {
+        return None;
+    }
+    Some(ENGINE.get_or_init(|| {
+        let _ = env_logger::try_init();
+        let engine = FakeEngine::new().with_cook_polls(3);
+        set_backend(engine.clone()).expect("Could not install fake backend");
+        engine
+    }))
+}
+
+/// Start a server and connect to it with the default session options, the fake engine
+/// simulates the server. The backend must be installed before any session options are created.
+pub fn start_session(server_options: ServerOptions) -> Result<Session> {
+    fake_engine();
+    new_thrift_session(SessionOptions::default(), server_options)
+}
+
 thread_local! {
     static SESSION: Lazy<Session> = Lazy::new(|| {
         let _ = env_logger::try_init();
         let server_options = ServerOptions::shared_memory_with_defaults().with_license_preference(LicensePreference::HoudiniEngineAndCore);
-        new_thrift_session(SessionOptions::default(), server_options)
+        start_session(server_options)
             .expect("Could not create test session")
     });
 
     static ASYNC_SESSION: Lazy<Session> = Lazy::new(|| {
         let _ = env_logger::try_init();
+        fake_engine();
         let mut session_info = SessionInfo::default();
         // For async attribute access connection_count must be > 0 according to SESI support, otherwise HARS crashes.
         println!("FIXME: H21.0 has a bug around connection count. Async tests are disabled for now.");
@@ -58,13 +109,62 @@ where
     SESSION.with(|session| f((*session).clone()))
 }
 
-pub fn with_async_session<F, R>(f: F) -> Result<R>
+pub fn with_async_session<F>(f: F) -> Result<()>
 where
-    F: FnOnce(Session) -> Result<R>,
+    F: FnOnce(Session) -> Result<()>,
 {
     ASYNC_SESSION.with(|session| f((*session).clone()))
 }
 
+/// Run `f` with the fake engine, unless the tests run against Houdini.
+/// For tests which control the engine, e.g kill servers or change the available licenses.
+pub fn with_fake_engine<F>(f: F) -> Result<()>
+where
+    F: FnOnce(&'static FakeEngine) -> Result<()>,
+{
+    match fake_engine() {
+        Some(engine) => f(engine),
+        None => Ok(()),
+    }
+}
+
+/// New in-process session of the fake engine, see [`with_fake_session`].
+pub fn fake_session(options: SessionOptions) -> Session {
+    fake_engine().expect("Tests must run against the fake engine");
+    new_in_process_session(Some(options)).expect("Could not create fake session")
+}
+
+/// Run `f` with a new session of the fake engine, unless the tests run against Houdini.
+/// For tests which rely on the node types and the node names of a fresh fake session.
+pub fn with_fake_session<F>(f: F) -> Result<()>
+where
+    F: FnOnce(Session) -> Result<()>,
+{
+    with_fake_engine(|_| f(fake_session(SessionOptions::default())))
+}
+
+/// Like [`with_fake_session`] with a threaded session which polls the cook state every 1ms.
+pub fn with_threaded_fake_session<F>(f: F) -> Result<()>
+where
+    F: FnOnce(Session) -> Result<()>,
+{
+    with_fake_engine(|_| {
+        let options = SessionOptions::default()
+            .threaded(true)
+            .cook_backoff(CookBackoff::fixed(Duration::from_millis(1)));
+        f(fake_session(options))
+    })
+}
+
+/// Pool of servers simulated by the fake engine, which must be installed.
+pub fn fake_session_pool(size: usize) -> SessionPool {
+    fake_engine().expect("Tests must run against the fake engine");
+    let server_options = ServerOptions::shared_memory_with_defaults()
+        .with_connection_timeout(Some(Duration::from_millis(100)));
+    SessionPool::new(size, SessionOptions::default(), server_options)
+        .expect("Could not create session pool")
+}
+
 pub fn with_session_asset<F>(hda_file: HdaFile, f: F) -> Result<()>
 where
     F: FnOnce(AssetLibrary) -> Result<()>,
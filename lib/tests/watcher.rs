@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use hapi_rs::{
+    Result,
+    node::{NodeFlags, NodeType},
+    parameter::Parameter,
+    watcher::{NodeEvent, NodeWatcher},
+};
+
+mod utils;
+use utils::with_fake_session;
+
+#[test]
+fn watcher_poll_and_spawn() -> Result<()> {
+    with_fake_session(|session| {
+        let geo = session.create_node("Object/geo")?;
+        let xform = session.node_builder("xform").with_parent(&geo).create()?;
+        let mut watcher = NodeWatcher::new(&session);
+        watcher.watch(&geo)?;
+        watcher.watch(&xform)?;
+        assert_eq!(watcher.poll()?, vec![]);
+
+        let Parameter::Float(t) = xform.parameter("t")? else {
+            panic!("t must be a float parameter");
+        };
+        t.set(1, 2.0)?;
+        let Parameter::String(group) = xform.parameter("group")? else {
+            panic!("group must be a string parameter");
+        };
+        group.set(0, "@P.y>0")?;
+        assert_eq!(
+            watcher.poll()?,
+            vec![NodeEvent::ParmsChanged {
+                node: xform.handle,
+                parms: vec!["group".to_string(), "t".to_string()],
+            }]
+        );
+
+        xform.cook_blocking()?;
+        let cook_count = xform.cook_count(NodeType::Any, NodeFlags::Any, false)?;
+        let events = watcher.poll()?;
+        assert!(
+            events.contains(&NodeEvent::Cooked {
+                node: xform.handle,
+                cook_count,
+            }),
+            "{events:?}"
+        );
+        assert!(events.contains(&NodeEvent::GeometryChanged { node: xform.handle }));
+        assert_eq!(watcher.poll()?, vec![]);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let handle = watcher.with_interval(Duration::from_millis(5)).spawn(tx)?;
+        t.set(0, 3.0)?;
+        assert_eq!(
+            rx.recv_timeout(Duration::from_secs(5)).unwrap(),
+            NodeEvent::ParmsChanged {
+                node: xform.handle,
+                parms: vec!["t".to_string()],
+            }
+        );
+        let xform_handle = xform.handle;
+        xform.delete()?;
+        assert_eq!(
+            rx.recv_timeout(Duration::from_secs(5)).unwrap(),
+            NodeEvent::Deleted { node: xform_handle }
+        );
+        handle.stop()?;
+        Ok(())
+    })
+}